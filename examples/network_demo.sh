@@ -68,7 +68,7 @@ echo ""
 
 # Check blockchain after mining
 echo "=== Blockchain After Mining ==="
-curl -s "${BASE_URL}/api/blockchain" | jq '{length: .chain | length, pending: .pending_transactions | length}'
+curl -s "${BASE_URL}/api/blockchain" | jq '{length: .chain | length, pending: .mempool.entries | length}'
 echo ""
 
 # Fire a shot
@@ -80,7 +80,7 @@ echo ""
 
 # Check pending transactions
 echo "=== Pending Transactions ==="
-curl -s "${BASE_URL}/api/blockchain" | jq '.pending_transactions'
+curl -s "${BASE_URL}/api/mempool" | jq '.data'
 echo ""
 
 # Mine again to include the shot
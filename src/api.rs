@@ -1,22 +1,54 @@
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::StatusCode,
     routing::{get, post},
     Json, Router,
 };
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 
-use crate::blockchain::{Block, Transaction};
+use crate::audit::CheatAudit;
+use crate::beacon::Beacon;
+use crate::blockchain::{Block, ShotBalance, Transaction};
+use crate::challenges::ReportChallenge;
+use crate::coordinator::GameStats;
+use crate::game::{HitReport, DEFAULT_GAME_ID};
+use crate::lifecycle::GameLifecycle;
+use crate::obligations::{ReportObligation, ShotResult};
+use crate::pool::MiningPool;
+use crate::rewards::RewardPolicy;
+use crate::trades::SwapOffer;
+use crate::template::BlockTemplate;
+use crate::mempool::PendingTransaction;
 use crate::network::{
+    CreateGameRequest,
     NetworkNode,
     RegisterPlayerRequest,
     FireShotRequest,
     MineRequest,
+    TurnRequest,
+    BeaconQuery,
+    BeaconSecretRequest,
+    ShareRequest,
     ShotBalanceRequest,
+    ObligationsRequest,
+    ShotResultsRequest,
+    ScanReportRequest,
+    DefeatDeclarationRequest,
+    SunkDeclarationRequest,
+    RevealBoardRequest,
+    MoveShipRequest,
+    ChallengeRequest,
+    ChallengeResponseRequest,
+    TransferRequest,
+    SwapOfferRequest,
+    SwapAcceptRequest,
     ApiResponse,
     Peer,
+    PeerAnnouncement,
     BlockchainInfo,
+    GenesisInfo,
     NodeInfo,
 };
 
@@ -28,115 +60,931 @@ pub fn create_router(state: AppState) -> Router {
     Router::new()
         // Blockchain endpoints
         .route("/api/blockchain", get(get_blockchain))
+        .route("/api/genesis", get(get_genesis))
         .route("/api/block", post(receive_block))
         .route("/api/transaction", post(receive_transaction))
+        .route("/api/template", get(get_block_template))
+        .route("/api/mempool", get(get_mempool))
+        .route("/api/beacon", get(get_beacon))
     
-        // Game endpoints
+        // Game endpoints (scoped by game ID)
+        .route("/api/games", get(list_games))
+        .route("/api/games", post(create_game))
+        .route("/api/games/:game_id", get(get_game_lifecycle_in_game))
+        .route("/api/games/:game_id/stats", get(get_game_stats_in_game))
+        .route("/api/games/:game_id/register", post(register_player_in_game))
+        .route("/api/games/:game_id/fire", post(fire_shot_in_game))
+        .route("/api/games/:game_id/mine", post(mine_for_shots_in_game))
+        .route("/api/games/:game_id/shots", post(get_shot_balance_in_game))
+        .route("/api/games/:game_id/obligations", post(get_obligations_in_game))
+        .route("/api/games/:game_id/report", post(report_hit_in_game))
+        .route("/api/games/:game_id/scan", post(report_scan_in_game))
+        .route("/api/games/:game_id/results", post(get_shot_results_in_game))
+        .route("/api/games/:game_id/defeat", post(declare_defeat_in_game))
+        .route("/api/games/:game_id/sunk", post(declare_sunk_in_game))
+        .route("/api/games/:game_id/reveal", post(reveal_board_in_game))
+        .route("/api/games/:game_id/move", post(move_ship_in_game))
+        .route("/api/games/:game_id/audit", get(get_audit_in_game))
+        .route("/api/games/:game_id/challenge", post(challenge_report_in_game))
+        .route("/api/games/:game_id/respond", post(respond_to_challenge_in_game))
+        .route("/api/games/:game_id/challenges", get(get_challenges_in_game))
+        .route("/api/games/:game_id/teams", get(get_teams_in_game))
+        .route("/api/games/:game_id/rewards", get(get_rewards_in_game))
+        .route("/api/games/:game_id/transfer", post(transfer_shots_in_game))
+        .route("/api/games/:game_id/swap/offer", post(offer_swap_in_game))
+        .route("/api/games/:game_id/swap/accept", post(accept_swap_in_game))
+        .route("/api/games/:game_id/swaps", get(get_swaps_in_game))
+        .route("/api/games/:game_id/pool", get(get_pool_in_game))
+        .route("/api/games/:game_id/pool/share", post(submit_share_in_game))
+        .route("/api/games/:game_id/pool/mine", post(mine_for_pool_in_game))
+        .route("/api/games/:game_id/round", post(next_round_in_game))
+        .route("/api/games/:game_id/pass", post(pass_turn_in_game))
+        .route("/api/games/:game_id/beacon/commit", post(commit_beacon_secret_in_game))
+        .route("/api/games/:game_id/beacon/reveal", post(reveal_beacon_secret_in_game))
+
+        // Default game endpoints
         .route("/api/register", post(register_player))
         .route("/api/fire", post(fire_shot))
         .route("/api/mine", post(mine_for_shots))
         .route("/api/shots", post(get_shot_balance))
+        .route("/api/obligations", post(get_obligations))
+        .route("/api/report", post(report_hit))
+        .route("/api/scan", post(report_scan))
+        .route("/api/results", post(get_shot_results))
+        .route("/api/defeat", post(declare_defeat))
+        .route("/api/sunk", post(declare_sunk))
+        .route("/api/reveal", post(reveal_board))
+        .route("/api/move", post(move_ship))
+        .route("/api/audit", get(get_audit))
+        .route("/api/challenge", post(challenge_report))
+        .route("/api/respond", post(respond_to_challenge))
+        .route("/api/challenges", get(get_challenges))
+        .route("/api/teams", get(get_teams))
+        .route("/api/rewards", get(get_rewards))
+        .route("/api/transfer", post(transfer_shots))
+        .route("/api/swap/offer", post(offer_swap))
+        .route("/api/swap/accept", post(accept_swap))
+        .route("/api/swaps", get(get_swaps))
+        .route("/api/pool", get(get_pool))
+        .route("/api/pool/share", post(submit_share))
+        .route("/api/pool/mine", post(mine_for_pool))
+        .route("/api/round", post(next_round))
+        .route("/api/pass", post(pass_turn))
+        .route("/api/beacon/commit", post(commit_beacon_secret))
+        .route("/api/beacon/reveal", post(reveal_beacon_secret))
+
+        // Network endpoints
+        .route("/api/peers", get(get_peers))
+        .route("/api/peers", post(add_peer))
+        .route("/api/sync", post(sync_blockchain))
+
+        // Info endpoints
+        .route("/api/info", get(get_node_info))
+        .route("/api/stats", get(get_game_stats))
+        .route("/api/game", get(get_game_lifecycle))
+
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
+
+/// Get the entire blockchain
+async fn get_blockchain(
+    State(node): State<AppState>,
+) -> Json<crate::blockchain::Blockchain> {
+    let coordinator = node.coordinator.read().await;
+    Json(coordinator.blockchain.clone())
+}
+
+/// Get the genesis config and its block hash
+async fn get_genesis(
+    State(node): State<AppState>,
+) -> Json<GenesisInfo> {
+    let coordinator = node.coordinator.read().await;
+    Json(GenesisInfo {
+        hash: coordinator.blockchain.genesis_hash().to_string(),
+        config: coordinator.genesis.clone(),
+    })
+}
+
+/// Receive a new block from a peer
+async fn receive_block(
+    State(node): State<AppState>,
+    Json(block): Json<Block>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    if let Err(e) = coordinator.accept_block(block) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        );
+    }
+
+    // Save blockchain after receiving new block
+    if let Err(e) = coordinator.save() {
+        eprintln!("Warning: Failed to save blockchain after receiving block: {}", e);
+    }
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success("Block accepted".to_string())),
+    )
+}
+
+/// Get the transactions the next block would hold, highest fees first
+async fn get_block_template(
+    State(node): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<BlockTemplate>>) {
+    let coordinator = node.coordinator.read().await;
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(coordinator.blockchain.block_template())),
+    )
+}
+
+/// Get the randomness beacon for a height and domain, optionally mixed with revealed secrets
+async fn get_beacon(
+    State(node): State<AppState>,
+    Query(query): Query<BeaconQuery>,
+) -> (StatusCode, Json<ApiResponse<Beacon>>) {
+    let coordinator = node.coordinator.read().await;
+    let beacon = if query.committed {
+        coordinator.blockchain.committed_beacon(query.height, &query.domain)
+    } else {
+        coordinator.blockchain.beacon(query.height, &query.domain)
+    };
+
+    match beacon {
+        Some(beacon) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(beacon)),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(format!("No beacon for height {} yet", query.height))),
+        ),
+    }
+}
+
+/// Get the pending transactions with their hashes and arrival heights
+async fn get_mempool(
+    State(node): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<PendingTransaction>>>) {
+    let coordinator = node.coordinator.read().await;
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(coordinator.blockchain.mempool.entries().to_vec())),
+    )
+}
+
+/// Receive a new transaction from a peer
+async fn receive_transaction(
+    State(node): State<AppState>,
+    Json(transaction): Json<Transaction>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+    if let Err(e) = coordinator.receive_transaction(transaction) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        );
+    }
+
+    // Save blockchain after receiving new transaction
+    if let Err(e) = coordinator.save() {
+        eprintln!("Warning: Failed to save blockchain after receiving transaction: {}", e);
+    }
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success("Transaction accepted".to_string())),
+    )
+}
+
+/// List every game hosted on this node
+async fn list_games(
+    State(node): State<AppState>,
+) -> Json<Vec<GameStats>> {
+    let coordinator = node.coordinator.read().await;
+    let stats = coordinator.game_ids()
+        .iter()
+        .filter_map(|id| coordinator.get_stats(id).ok())
+        .collect();
+    Json(stats)
+}
+
+/// Start a new game
+async fn create_game(
+    State(node): State<AppState>,
+    Json(req): Json<CreateGameRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+    let config = req.config.unwrap_or_else(|| coordinator.genesis.game.clone());
+
+    match coordinator.create_game(&req.game_id, config) {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(format!("Game {} created", req.game_id))),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Register a new player in the default game
+async fn register_player(
+    state: State<AppState>,
+    req: Json<RegisterPlayerRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    register_player_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Register a new player
+async fn register_player_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<RegisterPlayerRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.register_player_in_team(
+        &game_id,
+        req.player_id.clone(),
+        req.ships,
+        req.board_commitment,
+        req.salt,
+        req.ship_root,
+        req.team,
+    ) {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(format!("Player {} registered", req.player_id))),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Submit a mining pool share in the default game
+async fn submit_share(
+    state: State<AppState>,
+    req: Json<ShareRequest>,
+) -> (StatusCode, Json<ApiResponse<usize>>) {
+    submit_share_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Add a player's share to this node's mining pool and return their share count
+async fn submit_share_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ShareRequest>,
+) -> (StatusCode, Json<ApiResponse<usize>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.submit_share(&game_id, &req.player_id, req.nonce) {
+        Ok(shares) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(shares)),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Get the block template and shares of this node's mining pool for the default game
+async fn get_pool(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<MiningPool>>) {
+    get_pool_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// Get the block template and shares of this node's mining pool for a game
+async fn get_pool_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<MiningPool>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.pool(&game_id) {
+        Ok(pool) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(pool)),
+        ),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Mine the next block for the default game's pool
+async fn mine_for_pool(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<(String, u32)>>>) {
+    mine_for_pool_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// Mine the next block for a game's pool and broadcast it to peers
+async fn mine_for_pool_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<(String, u32)>>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.mine_for_pool(&game_id) {
+        Ok(payouts) => {
+            let block = coordinator.blockchain.chain.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(block) = block {
+                let _ = node.broadcast_block(&block).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(payouts)),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Get current unspent shots for a player in the default game
+async fn get_shot_balance(
+    state: State<AppState>,
+    req: Json<ShotBalanceRequest>,
+) -> (StatusCode, Json<ApiResponse<ShotBalance>>) {
+    get_shot_balance_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Get current unspent shots (UTXO-based) for a player, with how many expire soon
+async fn get_shot_balance_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ShotBalanceRequest>,
+) -> (StatusCode, Json<ApiResponse<ShotBalance>>) {
+    let coordinator = node.coordinator.read().await;
+
+    let registered = coordinator.game(&game_id)
+        .map(|game| game.players.contains_key(&req.player_id))
+        .unwrap_or(false);
+    if !registered {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error("Player not found".to_string())),
+        );
+    }
+
+    let balance = coordinator.shot_balance(&game_id, &req.player_id);
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(balance)),
+    )
+}
+
+/// List the hit reports a player still owes in the default game
+async fn get_obligations(
+    state: State<AppState>,
+    req: Json<ObligationsRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<ReportObligation>>>) {
+    get_obligations_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// List the hit reports a player still owes
+async fn get_obligations_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ObligationsRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<ReportObligation>>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.open_obligations(&game_id, &req.player_id) {
+        Ok(obligations) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(obligations)),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Submit a hit report in the default game
+async fn report_hit(
+    state: State<AppState>,
+    req: Json<HitReport>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    report_hit_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Verify a hit report, add it to the mempool and broadcast it to peers
+async fn report_hit_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(report): Json<HitReport>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.report_hit(&game_id, report) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Report accepted and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Answer a sonar with a count proof in the default game
+async fn report_scan(
+    state: State<AppState>,
+    req: Json<ScanReportRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    report_scan_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Verify a sonar count proof, add the report to the mempool and broadcast it to peers
+async fn report_scan_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ScanReportRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.report_scan(&game_id, &req.player_id, (req.x, req.y), req.proof) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Scan report accepted and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Get the results of a player's shots in the default game
+async fn get_shot_results(
+    state: State<AppState>,
+    req: Json<ShotResultsRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<ShotResult>>>) {
+    get_shot_results_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Get the results of a player's mined shots
+async fn get_shot_results_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ShotResultsRequest>,
+) -> (StatusCode, Json<ApiResponse<Vec<ShotResult>>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.shot_results(&game_id, &req.player_id) {
+        Ok(results) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(results)),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Declare defeat in the default game
+async fn declare_defeat(
+    state: State<AppState>,
+    req: Json<DefeatDeclarationRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    declare_defeat_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Verify a defeat declaration, add it to the mempool and broadcast it to peers
+async fn declare_defeat_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<DefeatDeclarationRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.declare_defeat(&game_id, &req.player_id, req.positions, req.salt) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Defeat declared and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Declare a ship sunk in the default game
+async fn declare_sunk(
+    state: State<AppState>,
+    req: Json<SunkDeclarationRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    declare_sunk_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Verify a sunk declaration, add it to the mempool and broadcast it to peers
+async fn declare_sunk_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<SunkDeclarationRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.declare_sunk(&game_id, &req.player_id, &req.ship_name, req.proof) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(format!("{} declared sunk and broadcasted", req.ship_name))),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Move a ship in the default game
+async fn move_ship(
+    state: State<AppState>,
+    req: Json<MoveShipRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    move_ship_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Move a ship, add the move with its new board commitment to the mempool and broadcast it to peers
+async fn move_ship_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<MoveShipRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.move_ship_with_ship_root(&game_id, &req.player_id, &req.ship_name, req.positions, req.salt, req.ship_root) {
+        Ok(commitment) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(commitment)),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Reveal a board in the default game
+async fn reveal_board(
+    state: State<AppState>,
+    req: Json<RevealBoardRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    reveal_board_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Verify an end-of-game board reveal, add it to the mempool and broadcast it to peers
+async fn reveal_board_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<RevealBoardRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.reveal_board_with_history(&game_id, &req.player_id, req.positions, req.salt, req.earlier_boards) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Board revealed and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Get the cheat audit of the default game
+async fn get_audit(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<CheatAudit>>) {
+    get_audit_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// Check revealed boards against mined reports and return the adjusted standings
+async fn get_audit_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<CheatAudit>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.audit(&game_id) {
+        Ok(audit) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(audit)),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Challenge a report in the default game
+async fn challenge_report(
+    state: State<AppState>,
+    req: Json<ChallengeRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    challenge_report_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Lock the bond, add the challenge to the mempool and broadcast it, returning the challenge ID
+async fn challenge_report_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ChallengeRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
 
-        // Network endpoints
-        .route("/api/peers", get(get_peers))
-        .route("/api/peers", post(add_peer))
-        .route("/api/sync", post(sync_blockchain))
+    match coordinator.challenge_report_at(&game_id, &req.player_id, &req.reporter, &req.shot_id, req.cell) {
+        Ok(challenge_id) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
 
-        // Info endpoints
-        .route("/api/info", get(get_node_info))
-        .route("/api/stats", get(get_game_stats))
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
 
-        .layer(CorsLayer::permissive())
-        .with_state(state)
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(challenge_id)),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
 }
 
-/// Get the entire blockchain
-async fn get_blockchain(
-    State(node): State<AppState>,
-) -> Json<crate::blockchain::Blockchain> {
-    let coordinator = node.coordinator.read().await;
-    Json(coordinator.blockchain.clone())
+/// Answer a challenge in the default game
+async fn respond_to_challenge(
+    state: State<AppState>,
+    req: Json<ChallengeResponseRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    respond_to_challenge_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
 }
 
-/// Receive a new block from a peer
-async fn receive_block(
+/// Verify a challenge response, add it to the mempool and broadcast it to peers
+async fn respond_to_challenge_in_game(
     State(node): State<AppState>,
-    Json(block): Json<Block>,
+    Path(game_id): Path<String>,
+    Json(req): Json<ChallengeResponseRequest>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let mut coordinator = node.coordinator.write().await;
-    
-    // Validate the block
-    if block.index != coordinator.blockchain.chain.len() as u64 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("Invalid block index".to_string())),
-        );
-    }
 
-    if block.previous_hash != coordinator.blockchain.get_latest_block().hash {
-        return (
+    match coordinator.respond_to_challenge(&game_id, &req.player_id, &req.challenge_id, req.positions, req.salt) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Challenge answered and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("Invalid previous hash".to_string())),
-        );
+            Json(ApiResponse::error(e)),
+        ),
     }
+}
 
-    if !block.hash.starts_with(&"0".repeat(coordinator.blockchain.difficulty)) {
-        return (
+/// List the challenges of the default game
+async fn get_challenges(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<ReportChallenge>>>) {
+    get_challenges_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// List every mined challenge with its status
+async fn get_challenges_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<ReportChallenge>>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.challenges(&game_id) {
+        Ok(challenges) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(challenges)),
+        ),
+        Err(e) => (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("Invalid proof of work".to_string())),
-        );
+            Json(ApiResponse::error(e)),
+        ),
     }
+}
 
-    // Add the block to our chain
-    coordinator.blockchain.chain.push(block);
-    coordinator.blockchain.pending_transactions.clear();
+/// Transfer shots in the default game
+async fn transfer_shots(
+    state: State<AppState>,
+    req: Json<TransferRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    transfer_shots_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
 
-    // Save blockchain after receiving new block
-    if let Err(e) = coordinator.save() {
-        eprintln!("Warning: Failed to save blockchain after receiving block: {}", e);
+/// Lock a player's shots for another player, add the transfer to the mempool and broadcast it
+async fn transfer_shots_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<TransferRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.transfer_shots_with_fee(&game_id, &req.player_id, &req.to, req.amount, req.fee) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Transfer submitted and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
     }
+}
 
-    (
-        StatusCode::OK,
-        Json(ApiResponse::success("Block accepted".to_string())),
-    )
+/// Offer a shot swap in the default game
+async fn offer_swap(
+    state: State<AppState>,
+    req: Json<SwapOfferRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    offer_swap_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
 }
 
-/// Receive a new transaction from a peer
-async fn receive_transaction(
+/// Lock the maker's side of a swap, broadcast the offer and return its ID
+async fn offer_swap_in_game(
     State(node): State<AppState>,
-    Json(transaction): Json<Transaction>,
+    Path(game_id): Path<String>,
+    Json(req): Json<SwapOfferRequest>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let mut coordinator = node.coordinator.write().await;
-    coordinator.blockchain.add_transaction(transaction);
 
-    // Save blockchain after receiving new transaction
-    if let Err(e) = coordinator.save() {
-        eprintln!("Warning: Failed to save blockchain after receiving transaction: {}", e);
+    match coordinator.offer_swap(&game_id, &req.player_id, &req.taker, req.give, req.want) {
+        Ok(offer_id) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(offer_id)),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
     }
+}
 
-    (
-        StatusCode::OK,
-        Json(ApiResponse::success("Transaction accepted".to_string())),
-    )
+/// Accept a shot swap in the default game
+async fn accept_swap(
+    state: State<AppState>,
+    req: Json<SwapAcceptRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    accept_swap_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
 }
 
-/// Register a new player
-async fn register_player(
+/// Lock the taker's side of a swap, add the acceptance to the mempool and broadcast it
+async fn accept_swap_in_game(
     State(node): State<AppState>,
-    Json(req): Json<RegisterPlayerRequest>,
+    Path(game_id): Path<String>,
+    Json(req): Json<SwapAcceptRequest>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let mut coordinator = node.coordinator.write().await;
 
-    match coordinator.register_player(
-        req.player_id.clone(),
-        req.ships,
-        req.board_commitment,
-        req.salt,
-    ) {
-        Ok(_) => (
+    match coordinator.accept_swap(&game_id, &req.player_id, &req.offer_id) {
+        Ok(_) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator); // Release the lock before broadcasting
+
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Swap accepted and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// List the swap offers of the default game
+async fn get_swaps(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<Vec<SwapOffer>>>) {
+    get_swaps_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// List every mined swap offer with its status
+async fn get_swaps_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<SwapOffer>>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.swaps(&game_id) {
+        Ok(swaps) => (
             StatusCode::OK,
-            Json(ApiResponse::success(format!("Player {} registered", req.player_id))),
+            Json(ApiResponse::success(swaps)),
         ),
         Err(e) => (
             StatusCode::BAD_REQUEST,
@@ -145,39 +993,88 @@ async fn register_player(
     }
 }
 
-/// Get current unspent shots (UTXO-based) for a player
-async fn get_shot_balance(
+/// List the teams of the default game
+async fn get_teams(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<BTreeMap<String, Vec<String>>>>) {
+    get_teams_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// List every team with its players
+async fn get_teams_in_game(
     State(node): State<AppState>,
-    Json(req): Json<ShotBalanceRequest>,
-) -> (StatusCode, Json<ApiResponse<u32>>) {
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<BTreeMap<String, Vec<String>>>>) {
     let coordinator = node.coordinator.read().await;
 
-    if !coordinator.players.contains_key(&req.player_id) {
-        return (
+    match coordinator.teams(&game_id) {
+        Ok(teams) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(teams)),
+        ),
+        Err(e) => (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("Player not found".to_string())),
-        );
+            Json(ApiResponse::error(e)),
+        ),
     }
+}
 
-    let count = coordinator.blockchain.get_unspent_shots(&req.player_id) as u32;
+/// Get the reward policy of the default game
+async fn get_rewards(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<RewardPolicy>>) {
+    get_rewards_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
 
-    (
-        StatusCode::OK,
-        Json(ApiResponse::success(count)),
-    )
+/// Get the reward policy a game pays shots by
+async fn get_rewards_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<RewardPolicy>>) {
+    let coordinator = node.coordinator.read().await;
+
+    match coordinator.reward_policy(&game_id) {
+        Ok(policy) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(policy)),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
 }
 
-/// Fire a shot
+/// Fire a shot in the default game
 async fn fire_shot(
+    state: State<AppState>,
+    req: Json<FireShotRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    fire_shot_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Fire a shot
+async fn fire_shot_in_game(
     State(node): State<AppState>,
+    Path(game_id): Path<String>,
     Json(req): Json<FireShotRequest>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let mut coordinator = node.coordinator.write().await;
+    let height = coordinator.current_height();
 
-    match coordinator.fire_shot(req.player_id.clone(), req.target_x, req.target_y) {
+    match coordinator.fire_weapon_with_fee(&game_id, req.player_id.clone(), req.target_x, req.target_y, req.weapon, req.fee) {
         Ok(_) => {
-            // Get the transaction that was just added
-            if let Some(transaction) = coordinator.blockchain.pending_transactions.last() {
+            // The last shot of a turn-based round seals the round's block
+            if coordinator.current_height() != height {
+                let block = coordinator.blockchain.get_latest_block().clone();
+                drop(coordinator);
+                let _ = node.broadcast_block(&block).await;
+
+                (
+                    StatusCode::OK,
+                    Json(ApiResponse::success("Shot fired and round sealed".to_string())),
+                )
+            } else if let Some(transaction) = coordinator.blockchain.mempool.last() {
                 let tx = transaction.clone();
                 drop(coordinator); // Release the lock before broadcasting
                 
@@ -202,14 +1099,164 @@ async fn fire_shot(
     }
 }
 
-/// Mine for shots
+/// Start the next round of the default game
+async fn next_round(
+    state: State<AppState>,
+) -> (StatusCode, Json<ApiResponse<u32>>) {
+    next_round_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// Start a game's next round, broadcasting the block a turn-based game seals for it
+async fn next_round_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<u32>>) {
+    let mut coordinator = node.coordinator.write().await;
+    let height = coordinator.current_height();
+
+    match coordinator.next_round(&game_id) {
+        Ok(()) => {
+            let round = coordinator.game(&game_id).map_or(0, |game| game.round);
+            if coordinator.current_height() != height {
+                let block = coordinator.blockchain.get_latest_block().clone();
+                drop(coordinator);
+                let _ = node.broadcast_block(&block).await;
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(round)),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Pass a player's turn in the default game
+async fn pass_turn(
+    state: State<AppState>,
+    req: Json<TurnRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    pass_turn_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Pass a player's turn in a turn-based game without firing
+async fn pass_turn_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<TurnRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+    let height = coordinator.current_height();
+
+    match coordinator.pass_turn(&game_id, &req.player_id) {
+        Ok(()) => {
+            if coordinator.current_height() != height {
+                let block = coordinator.blockchain.get_latest_block().clone();
+                drop(coordinator);
+                let _ = node.broadcast_block(&block).await;
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Turn passed".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Commit to a beacon secret in the default game
+async fn commit_beacon_secret(
+    state: State<AppState>,
+    req: Json<BeaconSecretRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    commit_beacon_secret_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Add a player's beacon commitment to the mempool, broadcast it and return the commitment
+async fn commit_beacon_secret_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<BeaconSecretRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.commit_beacon_secret(&game_id, &req.player_id, &req.domain, &req.secret) {
+        Ok(commitment) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator);
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(commitment)),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Reveal a beacon secret in the default game
+async fn reveal_beacon_secret(
+    state: State<AppState>,
+    req: Json<BeaconSecretRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    reveal_beacon_secret_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Add the reveal of a player's beacon secret to the mempool and broadcast it
+async fn reveal_beacon_secret_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+    Json(req): Json<BeaconSecretRequest>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let mut coordinator = node.coordinator.write().await;
+
+    match coordinator.reveal_beacon_secret(&game_id, &req.player_id, &req.domain, &req.secret) {
+        Ok(()) => {
+            let tx = coordinator.blockchain.mempool.last().cloned();
+            drop(coordinator);
+            if let Some(tx) = tx {
+                let _ = node.broadcast_transaction(&tx).await;
+            }
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success("Secret revealed and broadcasted".to_string())),
+            )
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(e)),
+        ),
+    }
+}
+
+/// Mine for shots in the default game
 async fn mine_for_shots(
+    state: State<AppState>,
+    req: Json<MineRequest>,
+) -> (StatusCode, Json<ApiResponse<u32>>) {
+    mine_for_shots_in_game(state, Path(DEFAULT_GAME_ID.to_string()), req).await
+}
+
+/// Mine for shots
+async fn mine_for_shots_in_game(
     State(node): State<AppState>,
+    Path(game_id): Path<String>,
     Json(req): Json<MineRequest>,
 ) -> (StatusCode, Json<ApiResponse<u32>>) {
     let mut coordinator = node.coordinator.write().await;
 
-    match coordinator.mine_for_shots(&req.player_id) {
+    match coordinator.mine_for_shots(&game_id, &req.player_id) {
         Ok(shots) => {
             // Get the newly mined block
             if let Some(block) = coordinator.blockchain.chain.last() {
@@ -248,8 +1295,18 @@ async fn get_peers(
 /// Add a new peer
 async fn add_peer(
     State(node): State<AppState>,
-    Json(peer): Json<Peer>,
+    Json(announcement): Json<PeerAnnouncement>,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
+    let peer = announcement.peer;
+
+    // Refuse peers that were started from a different genesis config
+    if announcement.genesis_hash != node.genesis_hash().await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(format!("Peer {} uses a different genesis", peer.url()))),
+        );
+    }
+
     node.add_peer(peer.clone()).await;
     
     (
@@ -284,8 +1341,8 @@ async fn get_node_info(
     let blockchain_info = BlockchainInfo {
         length: coordinator.blockchain.chain.len(),
         difficulty: coordinator.blockchain.difficulty,
-        pending_transactions: coordinator.blockchain.pending_transactions.len(),
-        is_valid: coordinator.blockchain.is_chain_valid(),
+        pending_transactions: coordinator.blockchain.mempool.len(),
+        is_valid: coordinator.verify_blockchain(),
     };
 
     Json(NodeInfo {
@@ -296,10 +1353,38 @@ async fn get_node_info(
     })
 }
 
-/// Get game statistics
+/// Get statistics for the default game
 async fn get_game_stats(
+    state: State<AppState>,
+) -> Result<Json<GameStats>, (StatusCode, Json<ApiResponse<String>>)> {
+    get_game_stats_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// Get game statistics
+async fn get_game_stats_in_game(
+    State(node): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<Json<GameStats>, (StatusCode, Json<ApiResponse<String>>)> {
+    let coordinator = node.coordinator.read().await;
+    coordinator.get_stats(&game_id)
+        .map(Json)
+        .map_err(|e| (StatusCode::NOT_FOUND, Json(ApiResponse::error(e))))
+}
+
+/// Get the phase and outcome of the default game
+async fn get_game_lifecycle(
+    state: State<AppState>,
+) -> Result<Json<GameLifecycle>, (StatusCode, Json<ApiResponse<String>>)> {
+    get_game_lifecycle_in_game(state, Path(DEFAULT_GAME_ID.to_string())).await
+}
+
+/// Get the game phase, eliminations and final outcome
+async fn get_game_lifecycle_in_game(
     State(node): State<AppState>,
-) -> (StatusCode, Json<crate::coordinator::GameStats>) {
+    Path(game_id): Path<String>,
+) -> Result<Json<GameLifecycle>, (StatusCode, Json<ApiResponse<String>>)> {
     let coordinator = node.coordinator.read().await;
-    (StatusCode::OK, Json(coordinator.get_stats()))
+    coordinator.game(&game_id)
+        .map(|game| Json(game.lifecycle.clone()))
+        .map_err(|e| (StatusCode::NOT_FOUND, Json(ApiResponse::error(e))))
 }
@@ -0,0 +1,100 @@
+use serde::{Serialize, Deserialize};
+use crate::obligations::ReportObligation;
+use crate::relocation::RevealedBoard;
+use crate::weapons::sonar_count;
+
+/// A mined report that contradicts the board the player revealed after the game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportDiscrepancy {
+    pub player_id: String,
+    pub shot_id: String,
+    pub shot_x: u16,
+    pub shot_y: u16,
+    /// What the player reported (the revealed board says the opposite)
+    pub reported_hit: bool,
+    /// Ship cells a sonar report claimed (the revealed board holds a different number, or
+    /// the report's proof does not open to it)
+    #[serde(default)]
+    pub reported_count: Option<u8>,
+}
+
+/// A mined ship move that the boards revealed around it show broke the relocation rules
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IllegalMove {
+    pub player_id: String,
+    /// Block in which the move was mined
+    pub moved_in_block: u64,
+    pub reason: String,
+}
+
+/// Outcome of checking every revealed board against the reports on the chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheatAudit {
+    /// Every report that disagrees with its player's revealed board
+    pub discrepancies: Vec<ReportDiscrepancy>,
+    /// Every ship move whose revealed boards are not one legal slide apart
+    #[serde(default)]
+    pub illegal_moves: Vec<IllegalMove>,
+    /// Players with at least one discrepancy or illegal move
+    pub cheaters: Vec<String>,
+    /// Players who have not revealed their board
+    pub unrevealed: Vec<String>,
+    /// Whether the reveal window has closed, so unrevealed players are flagged for good
+    pub finalized: bool,
+    /// Final standings with cheaters (and, once finalized, unrevealed players) moved down
+    pub standings: Vec<String>,
+    /// First player in the adjusted standings who is neither a cheater nor flagged
+    pub winner: Option<String>,
+}
+
+/// Replay a player's mined reports against their revealed board
+///
+/// Sonar counts must match the board and so must every cell commitment of their proofs;
+/// a sonar report without a proof counts as a discrepancy.
+pub fn audit_reports(player_id: &str, board: &RevealedBoard, obligations: &[ReportObligation]) -> Vec<ReportDiscrepancy> {
+    obligations.iter()
+        .filter(|o| o.player_id == player_id && o.fulfilled_in_block.is_some())
+        .filter_map(|o| {
+            let cell = (o.shot_x, o.shot_y);
+            let (reported_hit, reported_count) = match o.count {
+                Some(count) => {
+                    let scanned = match o.region {
+                        Some(region) => region.count(&board.positions),
+                        None => sonar_count(&board.positions, cell),
+                    };
+                    let proof_opens = o.count_proof.as_ref()
+                        .is_some_and(|proof| proof.opens_to(&board.positions, &board.salt));
+                    if count == scanned && proof_opens {
+                        return None;
+                    }
+                    (false, Some(count))
+                }
+                None => {
+                    let reported_hit = o.is_hit?;
+                    if reported_hit == board.positions.contains(&cell) {
+                        return None;
+                    }
+                    (reported_hit, None)
+                }
+            };
+            Some(ReportDiscrepancy {
+                player_id: player_id.to_string(),
+                shot_id: o.shot_id.clone(),
+                shot_x: o.shot_x,
+                shot_y: o.shot_y,
+                reported_hit,
+                reported_count,
+            })
+        })
+        .collect()
+}
+
+/// Move flagged players below everyone else, keeping the relative order within each group
+///
+/// Unrevealed players rank above proven cheaters.
+pub fn adjust_standings(standings: &[String], cheaters: &[String], unrevealed: &[String]) -> Vec<String> {
+    let honest = standings.iter().filter(|id| !cheaters.contains(id) && !unrevealed.contains(id));
+    let silent = standings.iter().filter(|id| !cheaters.contains(id) && unrevealed.contains(id));
+    let caught = standings.iter().filter(|id| cheaters.contains(id));
+    honest.chain(silent).chain(caught).cloned().collect()
+}
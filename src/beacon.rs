@@ -0,0 +1,97 @@
+use sha2::{Sha256, Digest};
+use serde::{Serialize, Deserialize};
+
+/// How long commit-reveal contributions to a beacon stay open
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BeaconRules {
+    /// Blocks after the beacon's height in which committed secrets can be revealed
+    pub reveal_blocks: u64,
+}
+
+impl Default for BeaconRules {
+    fn default() -> Self {
+        Self { reveal_blocks: 3 }
+    }
+}
+
+/// Commitment a player publishes before revealing `secret` for a beacon domain
+///
+/// The commitment binds the domain and the sender so nobody can replay another
+/// player's commitment under their own name.
+pub fn beacon_commitment(domain: &str, sender: &str, secret: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("beacon-commit:{}:{}:{}", domain, sender, secret).as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Random value every node derives alike for a block height and a domain tag
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Beacon {
+    pub height: u64,
+    pub domain: String,
+    /// Hex-encoded 32-byte value
+    pub value: String,
+    /// Revealed secrets mixed into the value (0 for a plain block-hash beacon)
+    pub reveals: usize,
+}
+
+impl Beacon {
+    /// Beacon derived from the hash of the block at `height` alone
+    pub fn from_block_hash(height: u64, domain: &str, block_hash: &str) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("beacon:{}:{}:{}", domain, height, block_hash).as_bytes());
+        Self {
+            height,
+            domain: domain.to_string(),
+            value: hex::encode(hasher.finalize()),
+            reveals: 0,
+        }
+    }
+
+    /// Mix revealed `(sender, secret)` pairs into the beacon, in sender order
+    pub fn mix(&self, reveals: &[(String, String)]) -> Self {
+        let mut sorted = reveals.to_vec();
+        sorted.sort();
+        let mut hasher = Sha256::new();
+        hasher.update(self.value.as_bytes());
+        for (sender, secret) in &sorted {
+            hasher.update(format!(":{}:{}", sender, secret).as_bytes());
+        }
+        Self {
+            height: self.height,
+            domain: self.domain.clone(),
+            value: hex::encode(hasher.finalize()),
+            reveals: self.reveals + sorted.len(),
+        }
+    }
+
+    /// Uniform draw in `0..bound` for the `index`-th use of this beacon
+    ///
+    /// Draws that would favour low values are rejected and redrawn, so every
+    /// result is equally likely. A `bound` of 0 returns 0.
+    pub fn random_below(&self, index: u64, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        let limit = u64::MAX - u64::MAX % bound;
+        (0u64..)
+            .map(|attempt| self.draw(index, attempt))
+            .find(|&x| x < limit)
+            .map_or(0, |x| x % bound)
+    }
+
+    /// Shuffle `items` with a Fisher-Yates pass driven by this beacon
+    pub fn shuffle<T>(&self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.random_below(i as u64, i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    fn draw(&self, index: u64, attempt: u64) -> u64 {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{}:{}:{}", self.value, index, attempt).as_bytes());
+        let digest = hasher.finalize();
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+}
@@ -1,45 +1,338 @@
 use sha2::{Sha256, Digest};
 use serde::{Serialize, Deserialize};
 use chrono::Utc;
+use std::collections::HashSet;
 use std::fmt;
 use std::fs;
 use std::path::Path;
+use crate::beacon::{beacon_commitment, Beacon, BeaconRules};
+use crate::coinbase::Coinbase;
+use crate::crypto::{MoveProof, ShipProof};
+use crate::genesis::GenesisConfig;
+use crate::mempool::Mempool;
+use crate::game::{HitReport, DEFAULT_GAME_ID};
+use crate::relocation::RevealedBoard;
+use crate::rewards::RewardPolicy;
+use crate::template::{BlockLimits, BlockTemplate};
+use crate::weapons::Weapon;
 
 /// Represents an unspent transaction output (UTXO) for a single shot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShotUtxo {
     /// Unique identifier for this UTXO
     pub id: String,
-    /// Owner (player ID) who can spend this shot
+    /// Account that can spend this shot: a player, or a team whose members all share it
     pub owner: String,
     /// Index of the block in which this UTXO was created
     pub created_in_block: u64,
     /// Whether this UTXO has been spent
     pub spent: bool,
+    /// Challenge whose bond holds this UTXO (escrowed UTXOs cannot be spent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escrow: Option<String>,
+    /// First block in which this UTXO can no longer be spent (None never expires)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
-/// Represents a transaction in the blockchain (a shot fired by a player)
+impl ShotUtxo {
+    /// Whether this UTXO has expired by the block at `height`
+    pub fn is_expired(&self, height: u64) -> bool {
+        self.expires_at.is_some_and(|expiry| height >= expiry)
+    }
+
+    /// Whether the owner can spend this UTXO in the block at `height`
+    pub fn is_spendable(&self, height: u64) -> bool {
+        !self.spent && self.escrow.is_none() && !self.is_expired(height)
+    }
+}
+
+/// Blocks ahead in which expiring shots are reported as expiring soon
+pub const EXPIRY_WARNING_BLOCKS: u64 = 3;
+
+/// Shots an account can spend in the next block and how many of them expire soon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShotBalance {
+    pub spendable: usize,
+    /// Spendable shots that expire within `EXPIRY_WARNING_BLOCKS` blocks
+    pub expiring: usize,
+    /// Block from which the next spendable shot expires
+    pub next_expiry: Option<u64>,
+}
+
+fn default_game_id() -> String {
+    DEFAULT_GAME_ID.to_string()
+}
+
+fn is_zero(fee: &u32) -> bool {
+    *fee == 0
+}
+
+/// Sequence a player's nonces belong to: one per player per game
+pub fn sender_id(game_id: &str, player_id: &str) -> String {
+    format!("{}/{}", game_id, player_id)
+}
+
+/// Check a transaction reuses none of the given nonces and spends none of the given UTXOs
+fn check_conflicts(transaction: &Transaction, nonces: &HashSet<(String, u64)>, spent: &HashSet<String>) -> Result<(), String> {
+    let sender = transaction.sender();
+    if nonces.contains(&(sender.clone(), transaction.nonce)) {
+        return Err(format!("Nonce {} of {} is already used on-chain", transaction.nonce, sender));
+    }
+    if let Some(id) = transaction.spent_utxos.iter().find(|id| spent.contains(*id)) {
+        return Err(format!("Shot UTXO {} is already spent on-chain", id));
+    }
+    Ok(())
+}
+
+/// What a transaction records
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TransactionKind {
+    /// A shot fired at the target cell
+    #[default]
+    Shot,
+    /// A hit/miss report for the shot with the given ID at the target cell
+    HitReport {
+        shot_id: String,
+        is_hit: bool,
+        proof: Vec<u8>,
+        /// Ship cells in the scanned area (sonar reports only)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        count: Option<u8>,
+    },
+    /// A player conceding the game, proven by revealing the board behind their commitment
+    DefeatDeclaration {
+        positions: Vec<(u16, u16)>,
+        salt: String,
+    },
+    /// A player publishing their full board after the game for the cheat audit
+    BoardReveal {
+        positions: Vec<(u16, u16)>,
+        salt: String,
+        /// Boards held before each ship move, oldest first
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        earlier_boards: Vec<RevealedBoard>,
+    },
+    /// A player announcing one of their ships sank, proven against their fleet root
+    SunkDeclaration {
+        ship_name: String,
+        proof: ShipProof,
+    },
+    /// A bonded challenge to another player's report for the shot at the target cell
+    Challenge {
+        shot_id: String,
+        reporter: String,
+    },
+    /// A reporter opening a challenged cell by revealing the board behind their commitment
+    ChallengeResponse {
+        challenge_id: String,
+        positions: Vec<(u16, u16)>,
+        salt: String,
+    },
+    /// A player sliding one unhit ship and committing to the resulting board
+    ShipMove {
+        proof: MoveProof,
+        /// Per-ship fleet root for the new board, so the moved ship can still be declared sunk
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ship_root: Option<String>,
+    },
+    /// Shot UTXOs handed from the sender to another player
+    Transfer { to: String, amount: u32 },
+    /// Offer to trade `give` of the maker's shots for `want` of the taker's
+    SwapOffer { taker: String, give: u32, want: u32 },
+    /// Acceptance of a swap offer, locking the taker's side of the trade
+    SwapAccept { offer_id: String },
+    /// Commitment to a secret the sender will mix into a randomness beacon
+    BeaconCommit { domain: String, commitment: String },
+    /// The secret behind an earlier beacon commitment
+    BeaconReveal { domain: String, secret: String },
+}
+
+impl TransactionKind {
+    pub fn is_shot(&self) -> bool {
+        matches!(self, TransactionKind::Shot)
+    }
+
+    /// Whether the transaction holds its shots in escrow instead of spending them
+    pub fn escrows(&self) -> bool {
+        matches!(
+            self,
+            TransactionKind::Transfer { .. } | TransactionKind::SwapOffer { .. }
+                | TransactionKind::SwapAccept { .. } | TransactionKind::Challenge { .. }
+        )
+    }
+}
+
+/// Represents a transaction in the blockchain (a shot fired or a hit report)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
+    /// Game this transaction belongs to
+    #[serde(default = "default_game_id")]
+    pub game_id: String,
     pub player_id: String,
-    pub target_x: u8,
-    pub target_y: u8,
+    pub target_x: u16,
+    pub target_y: u16,
     pub timestamp: i64,
     pub nonce: u64,
+    /// Shots omit this field so blocks mined before reports existed keep their hashes
+    #[serde(default, skip_serializing_if = "TransactionKind::is_shot")]
+    pub kind: TransactionKind,
+    /// Weapon fired by a shot (single-cell shots omit it, keeping older hashes)
+    #[serde(default, skip_serializing_if = "Weapon::is_single")]
+    pub weapon: Weapon,
+    /// Shot UTXOs the transaction spends, so validators can reject expired ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spent_utxos: Vec<String>,
+    /// Shots paid to the miner who includes the transaction (fee-less transactions omit it)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub fee: u32,
 }
 
 impl Transaction {
-    pub fn new(player_id: String, target_x: u8, target_y: u8, nonce: u64) -> Self {
+    pub fn new(player_id: String, target_x: u16, target_y: u16, nonce: u64) -> Self {
         Self {
+            game_id: default_game_id(),
             player_id,
             target_x,
             target_y,
             timestamp: Utc::now().timestamp(),
             nonce,
+            kind: TransactionKind::Shot,
+            weapon: Weapon::Single,
+            spent_utxos: Vec::new(),
+            fee: 0,
         }
     }
 
-    #[allow(dead_code)]
+    /// Create a transaction recording a player's hit/miss report for a shot
+    pub fn hit_report(game_id: &str, report: &HitReport) -> Self {
+        let mut transaction = Self::new(report.player_id.clone(), report.shot_x, report.shot_y, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::HitReport {
+            shot_id: report.shot_id.clone(),
+            is_hit: report.is_hit,
+            proof: report.proof.clone(),
+            count: report.count,
+        };
+        transaction
+    }
+
+    /// Create a transaction recording a player's defeat declaration
+    pub fn defeat_declaration(game_id: &str, player_id: &str, positions: Vec<(u16, u16)>, salt: String) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::DefeatDeclaration { positions, salt };
+        transaction
+    }
+
+    /// Create a transaction revealing a player's full board
+    pub fn board_reveal(
+        game_id: &str,
+        player_id: &str,
+        positions: Vec<(u16, u16)>,
+        salt: String,
+        earlier_boards: Vec<RevealedBoard>,
+    ) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::BoardReveal { positions, salt, earlier_boards };
+        transaction
+    }
+
+    /// Create a transaction recording a ship move and the player's new board commitment
+    pub fn ship_move(game_id: &str, player_id: &str, proof: MoveProof, ship_root: Option<String>) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::ShipMove { proof, ship_root };
+        transaction
+    }
+
+    /// Create a transaction handing `amount` shots from one player to another
+    pub fn transfer(game_id: &str, from: &str, to: &str, amount: u32) -> Self {
+        let mut transaction = Self::new(from.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::Transfer { to: to.to_string(), amount };
+        transaction
+    }
+
+    /// Create a transaction offering to swap shots with another player
+    pub fn swap_offer(game_id: &str, maker: &str, taker: &str, give: u32, want: u32) -> Self {
+        let mut transaction = Self::new(maker.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::SwapOffer { taker: taker.to_string(), give, want };
+        transaction
+    }
+
+    /// Create a transaction accepting a swap offer
+    pub fn swap_accept(game_id: &str, taker: &str, offer_id: &str) -> Self {
+        let mut transaction = Self::new(taker.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::SwapAccept { offer_id: offer_id.to_string() };
+        transaction
+    }
+
+    /// Create a transaction recording a player's sunk-ship declaration
+    pub fn sunk_declaration(game_id: &str, player_id: &str, ship_name: &str, proof: ShipProof) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::SunkDeclaration {
+            ship_name: ship_name.to_string(),
+            proof,
+        };
+        transaction
+    }
+
+    /// Create a transaction challenging a reporter's answer to a shot
+    pub fn challenge(game_id: &str, challenger: &str, reporter: &str, shot_id: &str, cell: (u16, u16)) -> Self {
+        let mut transaction = Self::new(challenger.to_string(), cell.0, cell.1, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::Challenge {
+            shot_id: shot_id.to_string(),
+            reporter: reporter.to_string(),
+        };
+        transaction
+    }
+
+    /// Create a transaction answering a challenge with the reporter's board
+    pub fn challenge_response(game_id: &str, player_id: &str, challenge_id: &str, positions: Vec<(u16, u16)>, salt: String) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::ChallengeResponse {
+            challenge_id: challenge_id.to_string(),
+            positions,
+            salt,
+        };
+        transaction
+    }
+
+    /// Create a transaction committing to a secret for a beacon domain
+    pub fn beacon_commit(game_id: &str, player_id: &str, domain: &str, commitment: &str) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::BeaconCommit {
+            domain: domain.to_string(),
+            commitment: commitment.to_string(),
+        };
+        transaction
+    }
+
+    /// Create a transaction revealing the secret behind a beacon commitment
+    pub fn beacon_reveal(game_id: &str, player_id: &str, domain: &str, secret: &str) -> Self {
+        let mut transaction = Self::new(player_id.to_string(), 0, 0, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.kind = TransactionKind::BeaconReveal {
+            domain: domain.to_string(),
+            secret: secret.to_string(),
+        };
+        transaction
+    }
+
+    /// Player whose nonce sequence this transaction belongs to
+    pub fn sender(&self) -> String {
+        sender_id(&self.game_id, &self.player_id)
+    }
+
     pub fn hash(&self) -> String {
         let data = serde_json::to_string(self).unwrap();
         let mut hasher = Sha256::new();
@@ -57,6 +350,12 @@ pub struct Block {
     pub previous_hash: String,
     pub hash: String,
     pub nonce: u64,
+    /// Hash of the genesis config (only set on block 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_hash: Option<String>,
+    /// Shots the block mints and who earned them (every block but block 0 has one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Coinbase>,
 }
 
 impl Block {
@@ -69,13 +368,31 @@ impl Block {
             previous_hash,
             hash: String::new(),
             nonce: 0,
+            config_hash: None,
+            coinbase: None,
+        };
+        block.hash = block.calculate_hash();
+        block
+    }
+
+    /// Create the genesis block committing to the given config
+    pub fn genesis(config: &GenesisConfig) -> Self {
+        let mut block = Self {
+            index: 0,
+            timestamp: config.timestamp,
+            transactions: Vec::new(),
+            previous_hash: String::from("0"),
+            hash: String::new(),
+            nonce: 0,
+            config_hash: Some(config.hash()),
+            coinbase: None,
         };
         block.hash = block.calculate_hash();
         block
     }
 
     pub fn calculate_hash(&self) -> String {
-        let data = format!(
+        let mut data = format!(
             "{}{}{}{}{}",
             self.index,
             self.timestamp,
@@ -83,11 +400,22 @@ impl Block {
             self.previous_hash,
             self.nonce
         );
+        if let Some(config_hash) = &self.config_hash {
+            data.push_str(config_hash);
+        }
+        if let Some(coinbase) = &self.coinbase {
+            data.push_str(&serde_json::to_string(coinbase).unwrap());
+        }
         let mut hasher = Sha256::new();
         hasher.update(data.as_bytes());
         hex::encode(hasher.finalize())
     }
 
+    /// Shot fees paid by the block's transactions
+    pub fn fees(&self) -> u32 {
+        self.transactions.iter().fold(0, |fees, tx| fees.saturating_add(tx.fee))
+    }
+
     /// Mine the block with proof-of-work
     pub fn mine(&mut self, difficulty: usize) {
         let target = "0".repeat(difficulty);
@@ -115,72 +443,255 @@ impl fmt::Display for Block {
 pub struct Blockchain {
     pub chain: Vec<Block>,
     pub difficulty: usize,
-    pub pending_transactions: Vec<Transaction>,
-    pub mining_reward: u32,
+    /// Transactions waiting to be mined
+    #[serde(default)]
+    pub mempool: Mempool,
+    /// Chain-wide reward policy from the genesis config
+    #[serde(default)]
+    pub rewards: RewardPolicy,
+    /// Leading zeros a mining pool share needs
+    #[serde(default = "default_share_difficulty")]
+    pub share_difficulty: usize,
+    /// Caps on the transactions a block may hold
+    #[serde(default)]
+    pub limits: BlockLimits,
+    /// Reveal window of commit-reveal beacons
+    #[serde(default)]
+    pub beacon: BeaconRules,
     /// UTXO set representing unspent shot rewards
     pub shot_utxos: Vec<ShotUtxo>,
 }
 
+fn default_share_difficulty() -> usize {
+    1
+}
+
 impl Blockchain {
     pub fn new(difficulty: usize) -> Self {
-        let mut blockchain = Self {
-            chain: Vec::new(),
-            difficulty,
-            pending_transactions: Vec::new(),
-            mining_reward: 1,
+        let mut genesis = GenesisConfig::default();
+        genesis.consensus.difficulty = difficulty;
+        Self::from_genesis(&genesis)
+    }
+
+    /// Create a blockchain whose block 0 and parameters come from a genesis config
+    pub fn from_genesis(genesis: &GenesisConfig) -> Self {
+        Self {
+            chain: vec![Block::genesis(genesis)],
+            difficulty: genesis.consensus.difficulty,
+            mempool: Mempool::new(genesis.consensus.mempool),
+            rewards: genesis.rewards.clone(),
+            share_difficulty: genesis.consensus.share_difficulty,
+            limits: genesis.consensus.limits,
+            beacon: genesis.consensus.beacon,
             shot_utxos: Vec::new(),
-        };
-        blockchain.create_genesis_block();
-        blockchain
+        }
     }
 
-    fn create_genesis_block(&mut self) {
-        let genesis = Block::new(0, Vec::new(), String::from("0"));
-        self.chain.push(genesis);
+    /// Hash of block 0, identifying the network this chain belongs to
+    pub fn genesis_hash(&self) -> &str {
+        &self.chain[0].hash
     }
 
     pub fn get_latest_block(&self) -> &Block {
         self.chain.last().unwrap()
     }
 
-    pub fn add_transaction(&mut self, transaction: Transaction) {
-        self.pending_transactions.push(transaction);
+    /// Add a transaction to the mempool, returning its hash
+    ///
+    /// Transactions reusing a nonce or spending a shot UTXO already used on-chain or
+    /// by a pending transaction are rejected. A transaction evicted to make room has
+    /// its shots handed back.
+    pub fn add_transaction(&mut self, transaction: Transaction) -> Result<String, String> {
+        let (nonces, spent) = self.confirmed_before(self.chain.len());
+        check_conflicts(&transaction, &nonces, &spent)?;
+        let height = self.get_latest_block().index;
+        let (hash, evicted) = self.mempool.insert(transaction, height)?;
+        if let Some(evicted) = evicted {
+            self.unlock(&evicted);
+        }
+        Ok(hash)
+    }
+
+    /// Next sequence nonce of a player in a game, following every nonce on-chain and in the mempool
+    pub fn next_nonce(&self, game_id: &str, player_id: &str) -> u64 {
+        let sender = sender_id(game_id, player_id);
+        let confirmed = self.chain.iter()
+            .flat_map(|block| &block.transactions)
+            .filter(|tx| tx.sender() == sender)
+            .map(|tx| tx.nonce + 1)
+            .max();
+        confirmed.max(self.mempool.next_nonce(&sender)).unwrap_or(0)
+    }
+
+    /// Hand back the shots a dropped transaction spent or locked
+    pub fn unlock(&mut self, transaction: &Transaction) {
+        self.unlock_except(transaction, &HashSet::new());
+    }
+
+    /// Hand back a dropped transaction's shots, except the ones in `keep` that stay spent
+    fn unlock_except(&mut self, transaction: &Transaction, keep: &HashSet<String>) {
+        let escrow_id = transaction.hash();
+        for utxo in self.shot_utxos.iter_mut() {
+            if transaction.spent_utxos.contains(&utxo.id) && !keep.contains(&utxo.id) {
+                utxo.spent = false;
+            }
+            if utxo.escrow.as_deref() == Some(escrow_id.as_str()) {
+                utxo.escrow = None;
+            }
+        }
+    }
+
+    /// Update the mempool after the latest block was added
+    ///
+    /// The block's transactions are removed, along with pending ones it made invalid
+    /// or that spend shots expiring before the next block. Transactions past their
+    /// time to live are dropped too. Every dropped transaction hands back its shots,
+    /// except the ones the block itself spent.
+    pub fn settle_mempool(&mut self) {
+        let latest = self.get_latest_block();
+        let (confirmed, height) = (latest.transactions.clone(), latest.index);
+        let spent_by_block: HashSet<String> = confirmed.iter()
+            .flat_map(|tx| tx.spent_utxos.iter().cloned())
+            .collect();
+        for transaction in self.mempool.remove_confirmed(&confirmed) {
+            self.unlock_except(&transaction, &spent_by_block);
+        }
+
+        let next = self.next_height();
+        let stale: Vec<Transaction> = self.mempool.iter()
+            .filter(|tx| self.check_expiry(tx, next).is_err())
+            .cloned()
+            .collect();
+        self.mempool.remove(&stale.iter().map(Transaction::hash).collect::<Vec<_>>());
+        for transaction in &stale {
+            self.unlock(transaction);
+        }
+
+        for transaction in self.mempool.expire(height) {
+            self.unlock(&transaction);
+        }
+    }
+
+    /// Block reward the chain's policy pays for the block at `height`
+    pub fn block_reward(&self, height: u64) -> u32 {
+        self.rewards.block_reward(height)
+    }
+
+    /// Height of the next block, in which newly created transactions are mined
+    pub fn next_height(&self) -> u64 {
+        self.chain.len() as u64
     }
 
     /// Mine pending transactions and reward the miner with shot UTXOs
+    ///
+    /// The miner is paid the chain's block reward plus the fees of the transactions
+    /// in the block; the total is returned.
     pub fn mine_pending_transactions(&mut self, miner_address: &str) -> u32 {
-        let next_index = self.chain.len() as u64;
+        let height = self.next_height();
+        let template = self.block_template();
+        let mut coinbase = Coinbase::solo(DEFAULT_GAME_ID, miner_address);
+        coinbase.payouts = vec![(miner_address.to_string(), self.block_reward(height).saturating_add(template.fees))];
+        self.mine_block(template, coinbase, self.rewards.expiry(height))
+    }
+
+    /// Block template for the next block built from the mempool
+    ///
+    /// Transactions spending shots that expire before the next block are left out.
+    pub fn block_template(&self) -> BlockTemplate {
+        let height = self.next_height();
+        let valid = self.mempool.iter()
+            .filter(|tx| self.check_expiry(tx, height).is_ok())
+            .cloned()
+            .collect();
+        BlockTemplate::build(valid, &self.limits).0
+    }
+
+    /// Mine a block holding the template's transactions and minting the coinbase's payouts
+    ///
+    /// The minted shot UTXOs expire at `expires_at`. Returns how many shots were minted.
+    pub fn mine_block(&mut self, template: BlockTemplate, coinbase: Coinbase, expires_at: Option<u64>) -> u32 {
+        let minted = coinbase.total();
         let mut block = Block::new(
-            next_index,
-            self.pending_transactions.clone(),
+            self.next_height(),
+            template.transactions,
             self.get_latest_block().hash.clone(),
         );
+        block.coinbase = Some(coinbase);
+        block.hash = block.calculate_hash();
 
         block.mine(self.difficulty);
-        let block_hash = block.hash.clone();
+        self.append_block(block, expires_at);
+        minted
+    }
+
+    /// Add a validated block to the chain
+    ///
+    /// The shots its transactions spend or lock are marked on this node, the mempool
+    /// is settled and the coinbase's payouts are minted as shot UTXOs expiring at `expires_at`.
+    pub fn append_block(&mut self, block: Block, expires_at: Option<u64>) {
+        for transaction in &block.transactions {
+            self.lock_transaction(transaction);
+        }
+        let payouts = block.coinbase.as_ref().map(|coinbase| coinbase.payouts.clone()).unwrap_or_default();
+        let (index, block_hash) = (block.index, block.hash.clone());
         self.chain.push(block);
-        self.pending_transactions.clear();
+        self.settle_mempool();
 
-        // Create shot UTXOs for the miner
-        for i in 0..self.mining_reward {
-            let utxo_id_input = format!("{}:{}:{}", miner_address, block_hash, i);
-            let mut hasher = Sha256::new();
-            hasher.update(utxo_id_input.as_bytes());
-            let id = hex::encode(hasher.finalize());
+        for (owner, shots) in payouts {
+            for i in 0..shots {
+                let utxo_id_input = format!("{}:{}:{}", owner, block_hash, i);
+                let mut hasher = Sha256::new();
+                hasher.update(utxo_id_input.as_bytes());
+                let id = hex::encode(hasher.finalize());
 
-            self.shot_utxos.push(ShotUtxo {
-                id,
-                owner: miner_address.to_string(),
-                created_in_block: next_index,
-                spent: false,
-            });
+                self.shot_utxos.push(ShotUtxo {
+                    id,
+                    owner: owner.clone(),
+                    created_in_block: index,
+                    spent: false,
+                    escrow: None,
+                    expires_at,
+                });
+            }
+        }
+    }
+
+    /// Spend or lock the shots a transaction names, as its sender's node did when creating it
+    ///
+    /// Transactions that escrow shots spend their fee and hold the rest under their hash;
+    /// the others spend every shot they name. Nodes that already locked them are left as they are.
+    /// Callers check the transaction spends its sender's own shots first (`check_spends`).
+    pub fn lock_transaction(&mut self, transaction: &Transaction) {
+        if transaction.kind.escrows() {
+            self.escrow_transaction(transaction);
+        } else {
+            self.spend_utxos(&transaction.spent_utxos);
         }
+    }
 
-        // Return the number of shots earned
-        self.mining_reward
+    /// Unspent shots an account holds going into the block at `height` holding `block_transactions`
+    ///
+    /// Shots locked by pending transactions count as unspent, so every node computes
+    /// the same balance whatever its mempool holds.
+    pub fn confirmed_balance(&self, account: &str, height: u64, block_transactions: &[Transaction]) -> usize {
+        let spent_by_block: HashSet<&String> = block_transactions.iter()
+            .flat_map(|tx| &tx.spent_utxos)
+            .collect();
+        let pending: HashSet<&String> = self.mempool.iter()
+            .flat_map(|tx| &tx.spent_utxos)
+            .collect();
+        self.shot_utxos.iter()
+            .filter(|u| u.owner == account && !u.is_expired(height) && !spent_by_block.contains(&u.id))
+            .filter(|u| u.is_spendable(height) || pending.contains(&u.id))
+            .count()
     }
 
     pub fn is_chain_valid(&self) -> bool {
+        match self.chain.first() {
+            Some(genesis) if genesis.index == 0 && genesis.hash == genesis.calculate_hash() => {}
+            _ => return false,
+        }
+
         for i in 1..self.chain.len() {
             let current_block = &self.chain[i];
             let previous_block = &self.chain[i - 1];
@@ -196,50 +707,309 @@ impl Blockchain {
             if !current_block.hash.starts_with(&"0".repeat(self.difficulty)) {
                 return false;
             }
+
+            match &current_block.coinbase {
+                Some(coinbase) if coinbase.check_shares(&current_block.previous_hash, self.share_difficulty).is_ok() => {}
+                _ => return false,
+            }
+
+            if self.limits.check(&current_block.transactions).is_err() {
+                return false;
+            }
+
+            let expired = current_block.transactions.iter()
+                .any(|transaction| self.check_expiry(transaction, current_block.index).is_err());
+            if expired || self.check_block_conflicts(current_block).is_err() {
+                return false;
+            }
         }
         true
     }
 
+    /// Check a transaction spends no shot UTXO that has expired by the block at `height`
+    ///
+    /// Only UTXOs in this node's set can be checked; unknown IDs are left to `check_spends`.
+    pub fn check_expiry(&self, transaction: &Transaction, height: u64) -> Result<(), String> {
+        for id in &transaction.spent_utxos {
+            if let Some(utxo) = self.shot_utxos.iter().find(|u| u.id == *id) {
+                if utxo.is_expired(height) {
+                    return Err(format!(
+                        "Transaction spends shot UTXO {} which expired at block {}",
+                        id, utxo.expires_at.unwrap_or_default()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a transaction spends distinct shot UTXOs of `account` covering `cost` and its fee, none expired by the block at `height`
+    ///
+    /// Whether the UTXOs were spent before is left to the nonce and double-spend checks.
+    pub fn check_spends(&self, transaction: &Transaction, account: &str, cost: u32, height: u64) -> Result<(), String> {
+        for (i, id) in transaction.spent_utxos.iter().enumerate() {
+            let utxo = self.shot_utxos.iter()
+                .find(|u| u.id == *id)
+                .ok_or_else(|| format!("Transaction spends unknown shot UTXO {}", id))?;
+            if utxo.owner != account {
+                return Err(format!("Transaction spends shot UTXO {} of {}, not {}", id, utxo.owner, account));
+            }
+            if transaction.spent_utxos[..i].contains(id) {
+                return Err(format!("Transaction spends shot UTXO {} twice", id));
+            }
+        }
+        let needed = cost.saturating_add(transaction.fee);
+        if transaction.spent_utxos.len() < needed as usize {
+            return Err(format!(
+                "Transaction spends {} shot UTXOs but needs {} for its cost and fee",
+                transaction.spent_utxos.len(), needed
+            ));
+        }
+        self.check_expiry(transaction, height)
+    }
+
+    /// Check every transaction in a block spends what `spend_of` says it must
+    ///
+    /// `spend_of` gives the account a transaction's shots come from and what it costs.
+    pub fn check_block_spends<F>(&self, block: &Block, spend_of: F) -> Result<(), String>
+    where
+        F: Fn(&Transaction) -> Result<(String, u32), String>,
+    {
+        for transaction in &block.transactions {
+            let (account, cost) = spend_of(transaction)?;
+            self.check_spends(transaction, &account, cost, block.index)?;
+        }
+        Ok(())
+    }
+
+    /// Check no transaction in a block reuses a nonce or spends a shot UTXO used earlier in the chain or the block
+    pub fn check_block_conflicts(&self, block: &Block) -> Result<(), String> {
+        let (mut nonces, mut spent) = self.confirmed_before(block.index as usize);
+        for transaction in &block.transactions {
+            check_conflicts(transaction, &nonces, &spent)?;
+            nonces.insert((transaction.sender(), transaction.nonce));
+            spent.extend(transaction.spent_utxos.iter().cloned());
+        }
+        Ok(())
+    }
+
+    /// Sender nonces used and shot UTXOs spent by the transactions in blocks before `index`
+    fn confirmed_before(&self, index: usize) -> (HashSet<(String, u64)>, HashSet<String>) {
+        let mut nonces = HashSet::new();
+        let mut spent = HashSet::new();
+        for transaction in self.chain.iter().take(index).flat_map(|block| &block.transactions) {
+            nonces.insert((transaction.sender(), transaction.nonce));
+            spent.extend(transaction.spent_utxos.iter().cloned());
+        }
+        (nonces, spent)
+    }
+
+    /// Randomness for `domain` derived from the hash of the block at `height`
+    ///
+    /// The miner of that block could grind its hash to steer the value; use
+    /// `committed_beacon` where that matters.
+    pub fn beacon(&self, height: u64, domain: &str) -> Option<Beacon> {
+        let block = self.chain.get(height as usize)?;
+        Some(Beacon::from_block_hash(height, domain, &block.hash))
+    }
+
+    /// Randomness for `domain` at `height` mixed with the secrets players committed to
+    ///
+    /// Secrets count if they were committed in a block up to `height` and revealed
+    /// in one of the `reveal_blocks` blocks after it; the block hash is unknown when
+    /// a secret is committed, so the miner cannot grind the outcome. Returns None
+    /// until the reveal window has closed.
+    pub fn committed_beacon(&self, height: u64, domain: &str) -> Option<Beacon> {
+        let end = height.checked_add(self.beacon.reveal_blocks)?;
+        if end >= self.chain.len() as u64 {
+            return None;
+        }
+        let beacon = self.beacon(height, domain)?;
+
+        let mut commitments = HashSet::new();
+        for tx in self.chain.iter().take(height as usize + 1).flat_map(|block| &block.transactions) {
+            if let TransactionKind::BeaconCommit { domain: d, commitment } = &tx.kind {
+                if d == domain {
+                    commitments.insert((tx.sender(), commitment.clone()));
+                }
+            }
+        }
+
+        let mut reveals = Vec::new();
+        for block in &self.chain[height as usize + 1..=end as usize] {
+            for tx in &block.transactions {
+                if let TransactionKind::BeaconReveal { domain: d, secret } = &tx.kind {
+                    let sender = tx.sender();
+                    let commitment = beacon_commitment(domain, &sender, secret);
+                    if d == domain && commitments.remove(&(sender.clone(), commitment)) {
+                        reveals.push((sender, secret.clone()));
+                    }
+                }
+            }
+        }
+        Some(beacon.mix(&reveals))
+    }
+
     pub fn get_transaction_count(&self) -> usize {
         self.chain.iter().map(|block| block.transactions.len()).sum()
     }
 
-    /// Get the number of unspent shot UTXOs for a given player
+    /// Get the number of shot UTXOs a given player can spend in the next block
     pub fn get_unspent_shots(&self, player_id: &str) -> usize {
-        self.shot_utxos
+        self.shot_balance(player_id).spendable
+    }
+
+    /// Spendable shots of a player, with how many expire within `EXPIRY_WARNING_BLOCKS` blocks
+    pub fn shot_balance(&self, player_id: &str) -> ShotBalance {
+        let height = self.next_height();
+        let spendable: Vec<&ShotUtxo> = self.shot_utxos
             .iter()
-            .filter(|u| u.owner == player_id && !u.spent)
-            .count()
+            .filter(|u| u.owner == player_id && u.is_spendable(height))
+            .collect();
+        ShotBalance {
+            spendable: spendable.len(),
+            expiring: spendable.iter()
+                .filter(|u| u.is_expired(height + EXPIRY_WARNING_BLOCKS))
+                .count(),
+            next_expiry: spendable.iter().filter_map(|u| u.expires_at).min(),
+        }
     }
 
-    /// Consume a single shot UTXO for the given player
-    pub fn consume_shot(&mut self, player_id: &str) -> Result<(), String> {
+    /// Consume a single shot UTXO for the given player, returning its ID
+    ///
+    /// The UTXO closest to expiring is spent first.
+    pub fn consume_shot(&mut self, player_id: &str) -> Result<String, String> {
+        let height = self.next_height();
         if let Some(utxo) = self
             .shot_utxos
             .iter_mut()
-            .find(|u| u.owner == player_id && !u.spent)
+            .filter(|u| u.owner == player_id && u.is_spendable(height))
+            .min_by_key(|u| u.expires_at.unwrap_or(u64::MAX))
         {
             utxo.spent = true;
-            Ok(())
+            Ok(utxo.id.clone())
         } else {
             Err("No unspent shot UTXOs available".to_string())
         }
     }
 
-    /// Award a single registration shot UTXO to a player
+    /// Consume `count` shot UTXOs at once, or none if the player cannot cover them all
+    pub fn consume_shots(&mut self, player_id: &str, count: u32) -> Result<Vec<String>, String> {
+        if self.get_unspent_shots(player_id) < count as usize {
+            return Err(format!("Need {} unspent shot UTXOs", count));
+        }
+        (0..count).map(|_| self.consume_shot(player_id)).collect()
+    }
+
+    /// IDs of `count` shot UTXOs a player can spend in the next block, closest to expiring first
+    pub fn select_shots(&self, player_id: &str, count: u32) -> Result<Vec<String>, String> {
+        let height = self.next_height();
+        let mut utxos: Vec<&ShotUtxo> = self.shot_utxos
+            .iter()
+            .filter(|u| u.owner == player_id && u.is_spendable(height))
+            .collect();
+        if utxos.len() < count as usize {
+            return Err(format!("Need {} unspent shot UTXOs", count));
+        }
+        utxos.sort_by_key(|u| u.expires_at.unwrap_or(u64::MAX));
+        Ok(utxos.iter().take(count as usize).map(|u| u.id.clone()).collect())
+    }
+
+    /// Mark the given shot UTXOs as spent
+    pub fn spend_utxos(&mut self, ids: &[String]) {
+        for utxo in self.shot_utxos.iter_mut().filter(|u| ids.contains(&u.id)) {
+            utxo.spent = true;
+        }
+    }
+
+    /// Lock the given shot UTXOs under an escrow ID
+    pub fn escrow_utxos(&mut self, ids: &[String], escrow_id: &str) {
+        for utxo in self.shot_utxos.iter_mut().filter(|u| ids.contains(&u.id)) {
+            utxo.escrow = Some(escrow_id.to_string());
+        }
+    }
+
+    /// Lock the shots a mined transaction locks, on nodes that did not create it
+    ///
+    /// The UTXOs after the fee in `spent_utxos` are held under the transaction's
+    /// hash, just as the sender's node locked them. Only call it for transactions
+    /// whose spends passed `check_spends`.
+    pub fn escrow_transaction(&mut self, transaction: &Transaction) {
+        let escrow_id = transaction.hash();
+        if self.escrowed_shots(&escrow_id) > 0 {
+            return;
+        }
+        let fee = (transaction.fee as usize).min(transaction.spent_utxos.len());
+        let (fee, locked) = transaction.spent_utxos.split_at(fee);
+        self.spend_utxos(fee);
+        self.escrow_utxos(locked, &escrow_id);
+    }
+
+    /// Number of UTXOs held under the given escrow ID
+    pub fn escrowed_shots(&self, escrow_id: &str) -> usize {
+        self.shot_utxos
+            .iter()
+            .filter(|u| u.escrow.as_deref() == Some(escrow_id))
+            .count()
+    }
+
+    /// Release the UTXOs held under an escrow ID to the given owner
+    pub fn release_escrow(&mut self, escrow_id: &str, owner: &str) {
+        for utxo in self.shot_utxos.iter_mut().filter(|u| u.escrow.as_deref() == Some(escrow_id)) {
+            utxo.escrow = None;
+            utxo.owner = owner.to_string();
+        }
+    }
+
+    /// Move up to `count` unspent shot UTXOs from one owner to another, returning how many moved
+    pub fn transfer_shots(&mut self, from: &str, to: &str, count: u32) -> u32 {
+        let mut moved = 0;
+        let height = self.next_height();
+        for utxo in self.shot_utxos.iter_mut()
+            .filter(|u| u.owner == from && u.is_spendable(height))
+            .take(count as usize)
+        {
+            utxo.owner = to.to_string();
+            moved += 1;
+        }
+        moved
+    }
+
+    /// Award the registration shot UTXOs to a player
     pub fn award_registration_shot(&mut self, player_id: &str) {
+        let expires_at = self.rewards.expiry(self.get_latest_block().index);
+        self.award_registration_shots(player_id, self.rewards.registration_shots, expires_at);
+    }
+
+    /// Award `count` registration shot UTXOs, expiring at `expires_at`, to a player
+    pub fn award_registration_shots(&mut self, player_id: &str, count: u32, expires_at: Option<u64>) {
         let latest_block = self.get_latest_block();
-        let utxo_id_input = format!("{}:{}:registration", player_id, latest_block.hash);
-        let mut hasher = Sha256::new();
-        hasher.update(utxo_id_input.as_bytes());
-        let id = hex::encode(hasher.finalize());
-
-        self.shot_utxos.push(ShotUtxo {
-            id,
-            owner: player_id.to_string(),
-            created_in_block: latest_block.index,
-            spent: false,
-        });
+        let block_hash = latest_block.hash.clone();
+        let block_index = latest_block.index;
+
+        // Team members registering into one pool on the same block keep counting up, so IDs stay unique
+        let mut i = 0;
+        for _ in 0..count {
+            let id = loop {
+                let utxo_id_input = format!("{}:{}:registration:{}", player_id, block_hash, i);
+                let mut hasher = Sha256::new();
+                hasher.update(utxo_id_input.as_bytes());
+                let id = hex::encode(hasher.finalize());
+                i += 1;
+                if !self.shot_utxos.iter().any(|u| u.id == id) {
+                    break id;
+                }
+            };
+
+            self.shot_utxos.push(ShotUtxo {
+                id,
+                owner: player_id.to_string(),
+                created_in_block: block_index,
+                spent: false,
+                escrow: None,
+                expires_at,
+            });
+        }
     }
 
     /// Save the blockchain to a JSON file
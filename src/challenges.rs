@@ -0,0 +1,84 @@
+use serde::{Serialize, Deserialize};
+
+/// Optimistic reporting: reports are accepted without a proof but can be challenged
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChallengeRules {
+    /// Accept hit/miss reports without checking their proof
+    pub optimistic: bool,
+    /// Shot UTXOs the challenger locks in escrow until the challenge is resolved
+    pub bond_shots: u32,
+    /// Blocks after the challenge's block in which the reporter must open the cell
+    pub response_blocks: u64,
+    /// Unspent shots a reporter caught lying (or staying silent) hands to the challenger
+    pub slash_shots: u32,
+}
+
+impl Default for ChallengeRules {
+    fn default() -> Self {
+        Self {
+            optimistic: false,
+            bond_shots: 1,
+            response_blocks: 5,
+            slash_shots: 1,
+        }
+    }
+}
+
+/// How a challenge ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ChallengeStatus {
+    /// Waiting for the reporter to open the cell
+    Open,
+    /// The report was false or the reporter did not answer in time
+    Upheld,
+    /// The opened cell matches the report
+    Rejected,
+}
+
+/// A challenge to one mined hit/miss report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportChallenge {
+    /// Hash of the challenge transaction (also the ID of the escrowed bond)
+    pub id: String,
+    /// Player who posted the bond
+    pub challenger: String,
+    /// Player whose report is challenged
+    pub player_id: String,
+    /// Shot the challenged report answered
+    pub shot_id: String,
+    pub shot_x: u16,
+    pub shot_y: u16,
+    /// What the challenged report claimed
+    pub reported_hit: bool,
+    /// Block in which the challenge was mined
+    pub challenged_in_block: u64,
+    /// Last block in which the reporter's response is accepted
+    pub deadline_block: u64,
+    pub status: ChallengeStatus,
+    /// Whether the opened cell holds a ship (None until the reporter answers)
+    pub revealed_hit: Option<bool>,
+    pub resolved_in_block: Option<u64>,
+}
+
+impl ReportChallenge {
+    /// Whether the reporter still has to answer
+    pub fn is_open(&self) -> bool {
+        self.status == ChallengeStatus::Open
+    }
+
+    /// Settle the challenge with the opened cell, or with None when the deadline passed
+    pub fn resolve(&mut self, revealed_hit: Option<bool>, block_index: u64) {
+        self.revealed_hit = revealed_hit;
+        self.resolved_in_block = Some(block_index);
+        self.status = if revealed_hit == Some(self.reported_hit) {
+            ChallengeStatus::Rejected
+        } else {
+            ChallengeStatus::Upheld
+        };
+    }
+
+    /// The report's value after the challenge (silent reporters are treated as hit)
+    pub fn corrected_hit(&self) -> bool {
+        self.revealed_hit.unwrap_or(true)
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Serialize, Deserialize};
+use crate::game::DEFAULT_GAME_ID;
+use crate::pool::Share;
+
+fn default_game_id() -> String {
+    DEFAULT_GAME_ID.to_string()
+}
+
+/// Shots a block mints and who earned them
+///
+/// Solo blocks name the player who mined them and pool blocks carry the shares
+/// behind them; blocks sealed for a turn-based round have neither and mint nothing.
+/// Nodes hosting the game recompute the payouts from its reward policy before
+/// accepting a block, so a miner cannot pay itself more than the game allows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Coinbase {
+    /// Game whose reward policy pays the block
+    #[serde(default = "default_game_id")]
+    pub game_id: String,
+    /// Player who mined a solo block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub miner: Option<String>,
+    /// Shares of a pool-mined block
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shares: Vec<Share>,
+    /// Shot UTXOs minted to each account
+    pub payouts: Vec<(String, u32)>,
+}
+
+impl Coinbase {
+    /// Coinbase of a block mined by a single player, before its payouts are filled in
+    pub fn solo(game_id: &str, miner: &str) -> Self {
+        Self {
+            game_id: game_id.to_string(),
+            miner: Some(miner.to_string()),
+            shares: Vec::new(),
+            payouts: Vec::new(),
+        }
+    }
+
+    /// Coinbase of a pool-mined block, before its payouts are filled in
+    pub fn pool(game_id: &str, shares: Vec<Share>) -> Self {
+        Self {
+            game_id: game_id.to_string(),
+            miner: None,
+            shares,
+            payouts: Vec::new(),
+        }
+    }
+
+    /// Coinbase of a block that mints nothing
+    pub fn sealed(game_id: &str) -> Self {
+        Self {
+            game_id: game_id.to_string(),
+            miner: None,
+            shares: Vec::new(),
+            payouts: Vec::new(),
+        }
+    }
+
+    pub fn is_pool(&self) -> bool {
+        !self.shares.is_empty()
+    }
+
+    /// Total shots the coinbase mints
+    pub fn total(&self) -> u32 {
+        self.payouts.iter().fold(0, |total, (_, shots)| total.saturating_add(*shots))
+    }
+
+    /// Check the coinbase's shape and that every pool share was worked on top of `previous_hash`
+    pub fn check_shares(&self, previous_hash: &str, share_difficulty: usize) -> Result<(), String> {
+        if self.miner.is_some() && self.is_pool() {
+            return Err("Coinbase names a miner and pool shares".to_string());
+        }
+        for (i, share) in self.shares.iter().enumerate() {
+            if !share.meets(previous_hash, share_difficulty) {
+                return Err(format!("Share {} from {} does not meet the share difficulty", i, share.worker));
+            }
+            if self.shares[..i].contains(share) {
+                return Err(format!("Share {} from {} is submitted twice", i, share.worker));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the coinbase mints exactly the expected payouts
+    pub fn check_payouts(&self, expected: &[(String, u32)]) -> Result<(), String> {
+        if self.payouts != expected {
+            return Err(format!(
+                "Coinbase payouts {:?} do not match the {:?} game {} pays",
+                self.payouts, expected, self.game_id
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,56 +1,115 @@
-use crate::blockchain::{Blockchain, Transaction};
-use crate::game::{Grid, Player, Ship, HitReport};
-use crate::crypto::{verify_commitment, HitProof};
-use std::collections::HashMap;
+use crate::blockchain::{sender_id, Block, Blockchain, ShotBalance, Transaction, TransactionKind};
+use crate::genesis::{GameConfig, GenesisConfig};
+use crate::lifecycle::{GameAction, GamePhase};
+use crate::game::{Game, Ship, HitReport, DEFAULT_GAME_ID};
+use crate::audit::CheatAudit;
+use crate::beacon::{beacon_commitment, Beacon};
+use crate::coinbase::Coinbase;
+use crate::challenges::{ChallengeStatus, ReportChallenge};
+use crate::crypto::{verify_commitment, CountProof, MoveProof, ShipProof};
+use crate::obligations::{MissedReportPenalty, ReportObligation, ShotResult};
+use crate::pool::{split_reward, MiningPool, Share};
+use crate::relocation::{check_slide, relocate, PlannedMove, RevealedBoard};
+use crate::rewards::RewardPolicy;
+use crate::setup::{self, SetupAction, SetupEvent};
+use crate::trades::SwapOffer;
+use crate::weapons::Weapon;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
-/// Coordinates the entire game including blockchain and game state
+/// Coordinates every game hosted on this node's blockchain
 pub struct GameCoordinator {
+    pub genesis: GenesisConfig,
     pub blockchain: Blockchain,
-    pub grid: Grid,
-    pub players: HashMap<String, Player>,
-    pub round: u32,
+    /// Games keyed by game ID (the default game is created from the genesis config)
+    pub games: HashMap<String, Game>,
+    /// Shares this node collects as a mining pool coordinator, keyed by game ID
+    pub pools: HashMap<String, MiningPool>,
+    /// Games created and changed on this node, replayed when its games are rebuilt
+    pub setup: Vec<SetupEvent>,
     blockchain_path: Option<PathBuf>,
 }
 
 impl GameCoordinator {
-    pub fn new(grid_size: u8, mining_difficulty: usize) -> Self {
+    pub fn new(grid_size: u16, mining_difficulty: usize) -> Self {
+        Self::from_genesis(GenesisConfig::new(grid_size, mining_difficulty))
+    }
+
+    /// Create a new GameCoordinator whose rules come from a genesis config
+    pub fn from_genesis(genesis: GenesisConfig) -> Self {
         Self {
-            blockchain: Blockchain::new(mining_difficulty),
-            grid: Grid::new(grid_size),
-            players: HashMap::new(),
-            round: 0,
+            blockchain: Blockchain::from_genesis(&genesis),
+            games: Self::default_games(&genesis),
+            genesis,
+            pools: HashMap::new(),
+            setup: Vec::new(),
             blockchain_path: None,
         }
     }
 
     /// Create a new GameCoordinator with blockchain persistence
-    pub fn with_persistence(grid_size: u8, mining_difficulty: usize, blockchain_path: PathBuf) -> Self {
-        let blockchain = if Blockchain::file_exists(&blockchain_path) {
+    pub fn with_persistence(grid_size: u16, mining_difficulty: usize, blockchain_path: PathBuf) -> Self {
+        Self::from_genesis_with_persistence(GenesisConfig::new(grid_size, mining_difficulty), blockchain_path)
+    }
+
+    /// Create a new GameCoordinator from a genesis config with blockchain persistence
+    ///
+    /// A stored blockchain is only reused if it was created from the same genesis config.
+    /// Its blocks are replayed with the setup log stored next to it, so the games,
+    /// registrations and rounds this node set up survive a restart.
+    pub fn from_genesis_with_persistence(genesis: GenesisConfig, blockchain_path: PathBuf) -> Self {
+        let stored = if Blockchain::file_exists(&blockchain_path) {
             println!("Loading existing blockchain from {:?}...", blockchain_path);
+            let expected_genesis = Blockchain::from_genesis(&genesis);
             match Blockchain::load_from_file(&blockchain_path) {
-                Ok(bc) => {
+                Ok(bc) if bc.genesis_hash() == expected_genesis.genesis_hash() => {
                     println!("✓ Loaded blockchain with {} blocks", bc.chain.len());
                     bc
                 }
+                Ok(_) => {
+                    eprintln!("✗ Stored blockchain was created from a different genesis config");
+                    eprintln!("  Creating new blockchain instead");
+                    expected_genesis
+                }
                 Err(e) => {
                     eprintln!("✗ Failed to load blockchain: {}", e);
                     eprintln!("  Creating new blockchain instead");
-                    Blockchain::new(mining_difficulty)
+                    expected_genesis
                 }
             }
         } else {
             println!("No existing blockchain found, creating new one");
-            Blockchain::new(mining_difficulty)
+            Blockchain::from_genesis(&genesis)
         };
+        let setup = setup::load_log(&setup::log_path(&blockchain_path)).unwrap_or_else(|e| {
+            eprintln!("✗ {}", e);
+            eprintln!("  Starting without this node's games");
+            Vec::new()
+        });
 
+        // Rebuild this node's games by replaying the stored blocks with the setup log
         let coordinator = Self {
-            blockchain,
-            grid: Grid::new(grid_size),
-            players: HashMap::new(),
-            round: 0,
-            blockchain_path: Some(blockchain_path),
+            blockchain: Blockchain::from_genesis(&genesis),
+            games: Self::default_games(&genesis),
+            genesis,
+            pools: HashMap::new(),
+            setup,
+            blockchain_path: None,
+        };
+        let mut coordinator = match coordinator.replay(&stored.chain, &stored.mempool.transactions()) {
+            Ok(rebuilt) => rebuilt,
+            Err(e) => {
+                eprintln!("✗ Failed to replay stored blockchain: {}", e);
+                eprintln!("  Keeping its blocks without this node's games");
+                Self {
+                    blockchain: stored,
+                    setup: Vec::new(),
+                    ..coordinator
+                }
+            }
         };
+        coordinator.blockchain_path = Some(blockchain_path);
+        coordinator.advance_lifecycle();
 
         // Save the initial blockchain to disk
         if let Err(e) = coordinator.save_blockchain() {
@@ -60,10 +119,20 @@ impl GameCoordinator {
         coordinator
     }
 
+    fn default_games(genesis: &GenesisConfig) -> HashMap<String, Game> {
+        let mut games = HashMap::new();
+        games.insert(
+            DEFAULT_GAME_ID.to_string(),
+            Game::new(DEFAULT_GAME_ID.to_string(), genesis.game.clone()),
+        );
+        games
+    }
+
     /// Save the blockchain to disk if persistence is enabled
     fn save_blockchain(&self) -> Result<(), String> {
         if let Some(path) = &self.blockchain_path {
             self.blockchain.save_to_file(path)?;
+            setup::save_log(&setup::log_path(path), &self.setup)?;
         }
         Ok(())
     }
@@ -73,149 +142,390 @@ impl GameCoordinator {
         self.save_blockchain()
     }
 
+    /// Start a new game with its own grid, players, fleet spec and phase
+    pub fn create_game(&mut self, game_id: &str, config: GameConfig) -> Result<(), String> {
+        if game_id.is_empty() || game_id.contains('/') {
+            return Err("Game ID must be non-empty and must not contain '/'".to_string());
+        }
+        if self.games.contains_key(game_id) {
+            return Err(format!("Game {} already exists", game_id));
+        }
+
+        let mut genesis = self.genesis.clone();
+        genesis.game = config;
+        genesis.validate()?;
+
+        let mut game = Game::new(game_id.to_string(), genesis.game.clone());
+        game.advance_lifecycle(self.current_height());
+        self.games.insert(game_id.to_string(), game);
+        self.record(SetupAction::CreateGame { game_id: game_id.to_string(), config: genesis.game });
+
+        Ok(())
+    }
+
+    /// Look up a game by ID
+    pub fn game(&self, game_id: &str) -> Result<&Game, String> {
+        self.games.get(game_id)
+            .ok_or_else(|| format!("Game {} not found", game_id))
+    }
+
+    /// Look up a game by ID for modification
+    pub fn game_mut(&mut self, game_id: &str) -> Result<&mut Game, String> {
+        self.games.get_mut(game_id)
+            .ok_or_else(|| format!("Game {} not found", game_id))
+    }
+
+    /// IDs of all hosted games, sorted
+    pub fn game_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.games.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// UTXO owner for a player within a game, so shots cannot be carried between games
+    pub fn account_id(game_id: &str, player_id: &str) -> String {
+        format!("{}/{}", game_id, player_id)
+    }
+
+    /// UTXO owner a player mines into and spends from: their own account or their team's pool
+    pub fn shot_account(&self, game_id: &str, player_id: &str) -> String {
+        let owner = self.game(game_id).map_or(player_id.to_string(), |game| game.shot_owner(player_id));
+        Self::account_id(game_id, &owner)
+    }
+
+    /// Reward policy of a game: its own, or the chain's when it has none
+    pub fn reward_policy(&self, game_id: &str) -> Result<RewardPolicy, String> {
+        let game = self.game(game_id)?;
+        Ok(game.config.rewards.clone().unwrap_or_else(|| self.blockchain.rewards.clone()))
+    }
+
+    /// Number of unspent shots a player can spend in a game (shared with their team when pooled)
+    pub fn get_unspent_shots(&self, game_id: &str, player_id: &str) -> usize {
+        self.blockchain.get_unspent_shots(&self.shot_account(game_id, player_id))
+    }
+
+    /// Spendable shots of a player in a game and how many of them expire soon
+    pub fn shot_balance(&self, game_id: &str, player_id: &str) -> ShotBalance {
+        self.blockchain.shot_balance(&self.shot_account(game_id, player_id))
+    }
+
     /// Register a new player with their fleet
     pub fn register_player(
         &mut self,
+        game_id: &str,
         player_id: String,
         ships: Vec<Ship>,
         board_commitment: String,
         salt: String,
     ) -> Result<(), String> {
-        // Validate fleet composition: must have exactly 4 ships
-        if ships.len() != 4 {
-            return Err("Fleet must contain exactly 4 ships".to_string());
-        }
+        self.register_player_with_ship_root(game_id, player_id, ships, board_commitment, salt, None)
+    }
 
-        // Validate ship sizes and check for required ships
-        let mut ship_sizes: Vec<usize> = ships.iter()
-            .map(|ship| ship.positions.len())
-            .collect();
-        ship_sizes.sort_unstable();
+    /// Register a new player, optionally committing to per-ship cells so ships can later be declared sunk
+    pub fn register_player_with_ship_root(
+        &mut self,
+        game_id: &str,
+        player_id: String,
+        ships: Vec<Ship>,
+        board_commitment: String,
+        salt: String,
+        ship_root: Option<String>,
+    ) -> Result<(), String> {
+        self.register_player_in_team(game_id, player_id, ships, board_commitment, salt, ship_root, None)
+    }
 
-        // Required: 1 Destroyer (1 cell), 1 Submarine (2 cells), 1 Cruiser (3 cells), 1 Carrier (4 cells)
-        if ship_sizes != vec![1, 2, 3, 4] {
-            return Err("Fleet must contain: 1 Carrier (4 cells), 1 Cruiser (3 cells), 1 Submarine (2 cells), 1 Destroyer (1 cell)".to_string());
+    /// Register a new player, optionally as a member of a team
+    ///
+    /// Teammates are spared by each other's shots unless the game allows friendly
+    /// fire, and share one shot pool when the game's team rules say so.
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_player_in_team(
+        &mut self,
+        game_id: &str,
+        player_id: String,
+        ships: Vec<Ship>,
+        board_commitment: String,
+        salt: String,
+        ship_root: Option<String>,
+        team: Option<String>,
+    ) -> Result<(), String> {
+        let height = self.current_height();
+        let game = self.game_mut(game_id)?;
+        if let Some(team) = &team {
+            game.check_team(team)?;
         }
-
-        // Validate ship placement (horizontal or vertical)
-        for ship in &ships {
-            if !Self::is_valid_ship_placement(&ship.positions) {
-                return Err(format!("Ship '{}' must be placed horizontally or vertically in a continuous line", ship.id));
-            }
+        game.add_player(player_id.clone(), ships.clone(), board_commitment.clone(), salt.clone())?;
+        if let Some(player) = game.players.get_mut(&player_id) {
+            player.ship_root = ship_root.clone();
+            player.team = team.clone();
         }
+        game.advance_lifecycle(height);
 
-        // Verify the commitment matches the ships
-        let all_positions: Vec<(u8, u8)> = ships.iter()
-            .flat_map(|ship| ship.positions.clone())
-            .collect();
+        // Award the initial registration shots (UTXOs) to the player, up to the maximum balance
+        let account = self.shot_account(game_id, &player_id);
+        let policy = self.reward_policy(game_id)?;
+        let balance = self.blockchain.confirmed_balance(&account, self.blockchain.next_height(), &[]);
+        let grant = policy.registration_grant(balance);
+        self.blockchain.award_registration_shots(&account, grant, policy.expiry(self.current_height()));
 
-        if !verify_commitment(&board_commitment, &all_positions, &salt) {
-            return Err("Invalid board commitment".to_string());
-        }
+        self.record(SetupAction::Register {
+            game_id: game_id.to_string(),
+            player_id,
+            ships,
+            board_commitment,
+            salt,
+            ship_root,
+            team,
+        });
+        Ok(())
+    }
+
+    /// Allow a player to mine for shots (shots are tracked as UTXOs on-chain)
+    ///
+    /// The block's coinbase pays the player what the game's reward policy pays plus
+    /// the fees of the transactions in the block; the total is returned.
+    pub fn mine_for_shots(&mut self, game_id: &str, player_id: &str) -> Result<u32, String> {
+        let height = self.current_height() + 1;
+        let template = self.blockchain.block_template();
+        let mut coinbase = Coinbase::solo(game_id, player_id);
+        coinbase.payouts = self.coinbase_payouts(&coinbase, height, &template.transactions)?;
+        let expires_at = self.coinbase_expiry(&coinbase, height);
+        let shots_earned = self.blockchain.mine_block(template, coinbase, expires_at);
+        self.process_block(self.current_height());
+        self.advance_lifecycle();
 
-        // Place ships on the shared grid
-        for ship in &ships {
-            self.grid.place_ship(&player_id, &ship.positions)?;
+        // Auto-save blockchain after mining
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
         }
 
-        // Create player
-        let player = Player::new(player_id.clone(), ships, board_commitment, salt);
+        Ok(shots_earned)
+    }
 
-        // Award an initial registration shot (UTXO) to the player
-        self.blockchain.award_registration_shot(&player_id);
+    /// Submit a pool share worked by a player on top of the latest block
+    ///
+    /// Returns how many shares the player has in the pool for the next block.
+    pub fn submit_share(&mut self, game_id: &str, player_id: &str, nonce: u64) -> Result<usize, String> {
+        self.game(game_id)?.check_miner(player_id)?;
 
-        self.players.insert(player_id, player);
+        let worker = self.shot_account(game_id, player_id);
+        let policy = self.reward_policy(game_id)?;
+        if policy.is_full(self.blockchain.get_unspent_shots(&worker)) {
+            return Err(format!("Player {} already holds the maximum of {} shots", player_id, policy.max_balance));
+        }
+        let template = self.blockchain.get_latest_block().hash.clone();
+        let share = Share { worker: worker.clone(), nonce };
+        let pool = self.pools.entry(game_id.to_string()).or_default();
+        pool.submit(share, &template, self.blockchain.share_difficulty)?;
+        Ok(pool.shares_of(&worker))
+    }
 
-        Ok(())
+    /// Block template and shares of a game's mining pool for the next block
+    pub fn pool(&self, game_id: &str) -> Result<MiningPool, String> {
+        self.game(game_id)?;
+        let template = self.blockchain.get_latest_block().hash.clone();
+        let shares = match self.pools.get(game_id) {
+            Some(pool) if pool.template == template => pool.shares.clone(),
+            _ => Vec::new(),
+        };
+        Ok(MiningPool { template, shares })
     }
 
-    /// Validate that a ship is placed horizontally or vertically in a continuous line
-    fn is_valid_ship_placement(positions: &[(u8, u8)]) -> bool {
-        if positions.is_empty() {
-            return false;
-        }
-        if positions.len() == 1 {
-            return true; // Single cell is always valid
+    /// Mine the next block for a game's pool, splitting the game's block reward among its workers by shares
+    ///
+    /// Each worker's part of the block reward is capped by the game's maximum balance,
+    /// and the fees of the transactions in the block are split on top. The shares and
+    /// the split are recorded in the block's coinbase so every node can check them.
+    pub fn mine_for_pool(&mut self, game_id: &str) -> Result<Vec<(String, u32)>, String> {
+        let shares = self.pool(game_id)?.shares;
+        if shares.is_empty() {
+            return Err("Pool has no shares for the next block".to_string());
         }
 
-        let mut sorted_positions = positions.to_vec();
-        sorted_positions.sort_unstable();
+        let height = self.current_height() + 1;
+        let template = self.blockchain.block_template();
+        let mut coinbase = Coinbase::pool(game_id, shares);
+        coinbase.payouts = self.coinbase_payouts(&coinbase, height, &template.transactions)?;
+        self.pools.remove(game_id);
+        let payouts = coinbase.payouts.clone();
+        let expires_at = self.coinbase_expiry(&coinbase, height);
+        self.blockchain.mine_block(template, coinbase, expires_at);
+        self.process_block(self.current_height());
+        self.advance_lifecycle();
 
-        // Check if horizontal (same y, consecutive x)
-        let all_same_y = sorted_positions.iter().all(|&(_, y)| y == sorted_positions[0].1);
-        if all_same_y {
-            for i in 1..sorted_positions.len() {
-                if sorted_positions[i].0 != sorted_positions[i - 1].0 + 1 {
-                    return false; // Not consecutive
-                }
-            }
-            return true;
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
         }
 
-        // Check if vertical (same x, consecutive y)
-        let all_same_x = sorted_positions.iter().all(|&(x, _)| x == sorted_positions[0].0);
-        if all_same_x {
-            for i in 1..sorted_positions.len() {
-                if sorted_positions[i].1 != sorted_positions[i - 1].1 + 1 {
-                    return false; // Not consecutive
+        Ok(payouts)
+    }
+
+    /// Payouts a coinbase must mint in the block at `height` holding `transactions`
+    ///
+    /// A solo miner earns the mining payout of the game the coinbase names, capped by its
+    /// maximum balance. Pool workers, who must be players allowed to mine in the game,
+    /// split its block reward by their shares, each part capped the same way. Both also
+    /// collect the transactions' fees. Turn-based rounds are sealed by blocks that mint nothing.
+    pub fn coinbase_payouts(&self, coinbase: &Coinbase, height: u64, transactions: &[Transaction]) -> Result<Vec<(String, u32)>, String> {
+        let game = self.game(&coinbase.game_id)?;
+        let policy = self.reward_policy(&coinbase.game_id)?;
+        let fees = transactions.iter().fold(0u32, |fees, tx| fees.saturating_add(tx.fee));
+        if coinbase.is_pool() {
+            for share in &coinbase.shares {
+                self.check_worker(game, &share.worker)?;
+            }
+            let mut payouts: Vec<(String, u32)> = split_reward(&coinbase.shares, policy.block_reward(height))
+                .into_iter()
+                .map(|(worker, shots)| {
+                    let balance = self.blockchain.confirmed_balance(&worker, height, transactions);
+                    (worker, policy.capped(shots, balance))
+                })
+                .collect();
+            for (worker, shots) in split_reward(&coinbase.shares, fees) {
+                match payouts.iter_mut().find(|(paid, _)| *paid == worker) {
+                    Some((_, total)) => *total = total.saturating_add(shots),
+                    None => payouts.push((worker, shots)),
                 }
             }
-            return true;
+            payouts.sort();
+            payouts.retain(|(_, shots)| *shots > 0);
+            return Ok(payouts);
         }
+        let Some(miner) = &coinbase.miner else {
+            if !game.config.turns.is_turn_based() {
+                return Err(format!("Game {} is not turn-based, so its blocks must pay a miner", game.id));
+            }
+            return Ok(Vec::new());
+        };
 
-        false // Neither horizontal nor vertical
+        game.check_miner(miner)?;
+        let account = self.shot_account(&coinbase.game_id, miner);
+        let balance = self.blockchain.confirmed_balance(&account, height, transactions);
+        let hits_taken = game.players[miner].hit_cells.len();
+        let payout = policy.mining_payout(height, hits_taken, balance);
+        Ok(vec![(account, payout.saturating_add(fees))])
     }
 
-    /// Allow a player to mine for shots (shots are tracked as UTXOs on-chain)
-    pub fn mine_for_shots(&mut self, player_id: &str) -> Result<u32, String> {
-        if !self.players.contains_key(player_id) {
-            return Err("Player not found".to_string());
-        }
-
-        // Check if player is defeated
-        if self.is_player_defeated(player_id) {
-            return Err("Defeated players cannot mine".to_string());
-        }
+    /// Account a transaction's shots must come from and how many it spends besides its fee
+    ///
+    /// Shots fired pay for their weapon, ship moves the move cost, challenges their
+    /// bond, and transfers and swaps the shots they trade. Transactions in games this
+    /// node does not host cost nothing, but no shots of such games exist to spend.
+    pub fn spend_of(&self, transaction: &Transaction) -> Result<(String, u32), String> {
+        let account = self.shot_account(&transaction.game_id, &transaction.player_id);
+        let Some(game) = self.games.get(&transaction.game_id) else {
+            return Ok((account, 0));
+        };
+        let cost = match &transaction.kind {
+            TransactionKind::Shot => transaction.weapon.cost(&game.config.weapons),
+            TransactionKind::ShipMove { .. } => game.config.relocation.move_cost,
+            TransactionKind::Challenge { .. } => game.config.challenges.bond_shots,
+            TransactionKind::Transfer { amount, .. } => *amount,
+            TransactionKind::SwapOffer { give, .. } => *give,
+            TransactionKind::SwapAccept { offer_id } => game.swaps.iter()
+                .find(|offer| offer.id == *offer_id)
+                .map(|offer| offer.want)
+                .ok_or_else(|| format!("Swap offer {} not found", offer_id))?,
+            _ => 0,
+        };
+        Ok((account, cost))
+    }
 
-        // Mine pending transactions and create shot UTXOs for the miner
-        let shots_earned = self.blockchain.mine_pending_transactions(player_id);
+    /// Check a transaction about to enter the mempool spends what `spend_of` says it must
+    fn check_spends(&self, transaction: &Transaction) -> Result<(), String> {
+        let (account, cost) = self.spend_of(transaction)?;
+        self.blockchain.check_spends(transaction, &account, cost, self.blockchain.next_height())
+    }
 
-        // Auto-save blockchain after mining
-        if let Err(e) = self.save_blockchain() {
-            eprintln!("Warning: Failed to save blockchain: {}", e);
+    /// Check a pool worker's account belongs to a player who may mine in the game
+    fn check_worker(&self, game: &Game, worker: &str) -> Result<(), String> {
+        let mut result = Err(format!("Pool worker {} is not a player of game {}", worker, game.id));
+        for player_id in game.players.keys().filter(|player_id| self.shot_account(&game.id, player_id) == worker) {
+            result = game.check_miner(player_id);
+            if result.is_ok() {
+                break;
+            }
         }
+        result
+    }
 
-        Ok(shots_earned)
+    /// Block from which the shots a coinbase mints in the block at `height` can no longer be spent
+    fn coinbase_expiry(&self, coinbase: &Coinbase, height: u64) -> Option<u64> {
+        let policy = self.reward_policy(&coinbase.game_id).unwrap_or_else(|_| self.blockchain.rewards.clone());
+        policy.expiry(height)
     }
 
     /// Fire a shot (spends a shot UTXO and creates a transaction)
     pub fn fire_shot(
         &mut self,
+        game_id: &str,
+        player_id: String,
+        target_x: u16,
+        target_y: u16,
+    ) -> Result<(), String> {
+        self.fire_weapon(game_id, player_id, target_x, target_y, Weapon::Single)
+    }
+
+    /// Fire a weapon at a target cell, spending as many shot UTXOs as the weapon costs
+    pub fn fire_weapon(
+        &mut self,
+        game_id: &str,
         player_id: String,
-        target_x: u8,
-        target_y: u8,
+        target_x: u16,
+        target_y: u16,
+        weapon: Weapon,
     ) -> Result<(), String> {
+        self.fire_weapon_with_fee(game_id, player_id, target_x, target_y, weapon, 0)
+    }
+
+    /// Fire a weapon, also paying `fee` shots to the miner who includes the shot
+    pub fn fire_weapon_with_fee(
+        &mut self,
+        game_id: &str,
+        player_id: String,
+        target_x: u16,
+        target_y: u16,
+        weapon: Weapon,
+        fee: u32,
+    ) -> Result<(), String> {
+        let game = self.game(game_id)?;
+
         // Ensure player exists
-        if !self.players.contains_key(&player_id) {
+        if !game.players.contains_key(&player_id) {
             return Err("Player not found".to_string());
         }
 
         // Check if player is defeated
-        if self.is_player_defeated(&player_id) {
+        if game.is_player_defeated(&player_id) {
             return Err("Defeated players cannot fire shots".to_string());
         }
 
-        // Spend one shot UTXO on-chain
-        self.blockchain.consume_shot(&player_id)?;
+        game.check_action(GameAction::Fire)?;
+        game.check_turn(&player_id)?;
+
+        // Check the shot against the game's shot rules, counting the player's pending shots
+        game.check_shot(&player_id, (target_x, target_y), weapon, &self.pending_targets(game_id, &player_id))?;
+
+        // Spend the weapon's shot UTXOs and the fee on-chain
+        let cost = weapon.cost(&game.config.weapons);
+        let account = self.shot_account(game_id, &player_id);
+        let spent_utxos = self.blockchain.consume_shots(&account, cost.saturating_add(fee))?;
+
+        // Create transaction representing the shot
+        let mut transaction = Transaction::new(player_id.clone(), target_x, target_y, 0);
+        transaction.game_id = game_id.to_string();
+        transaction.weapon = weapon;
+        transaction.spent_utxos = spent_utxos;
+        transaction.fee = fee;
+        self.submit(transaction)?;
 
         // Record shot locally for the player (for stats / UI)
-        if let Some(player) = self.players.get_mut(&player_id) {
+        if let Some(player) = self.game_mut(game_id)?.players.get_mut(&player_id) {
             player.shots_fired.push((target_x, target_y));
         }
-
-        // Create transaction representing the shot
-        let transaction = Transaction::new(player_id, target_x, target_y, 0);
-        self.blockchain.add_transaction(transaction);
+        if self.game(game_id)?.config.turns.is_turn_based() {
+            self.end_turn(game_id)?;
+        }
 
         // Auto-save blockchain after adding transaction
         if let Err(e) = self.save_blockchain() {
@@ -225,80 +535,1095 @@ impl GameCoordinator {
         Ok(())
     }
 
-    /// Process hit reports with ZK proofs
-    #[allow(dead_code)]
+    /// Verify a hit report and add it to the mempool as a transaction
+    ///
+    /// The report must answer an open obligation. Its effect on the reporting
+    /// player's fleet is applied by every node once the transaction is mined.
     pub fn report_hit(
         &mut self,
-        report: HitReport,
+        game_id: &str,
+        mut report: HitReport,
     ) -> Result<bool, String> {
-        let player = self.players.get(&report.player_id)
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Report)?;
+
+        if !game.players.contains_key(&report.player_id) {
+            return Err("Player not found".to_string());
+        }
+
+        report.shot_id = game.find_open_obligation(&report)
+            .map(|o| o.shot_id.clone())
+            .ok_or_else(|| format!(
+                "Player {} owes no report for a shot at ({}, {})",
+                report.player_id, report.shot_x, report.shot_y
+            ))?;
+
+        let already_reported = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id && tx.player_id == report.player_id
+                && (tx.target_x, tx.target_y) == (report.shot_x, report.shot_y)
+                && matches!(&tx.kind, TransactionKind::HitReport { shot_id, .. } if *shot_id == report.shot_id)
+        });
+        if already_reported {
+            return Err("Report for this shot is already pending".to_string());
+        }
+
+        game.accept_report(&report)?;
+
+        // Record the report on-chain
+        self.submit(Transaction::hit_report(game_id, &report))?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(true)
+    }
+
+    /// Answer a sonar centred on `center` with a proof of how many ship cells it found
+    ///
+    /// The proof reveals the count without saying which cells hold the ships.
+    pub fn report_scan(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        center: (u16, u16),
+        proof: CountProof,
+    ) -> Result<bool, String> {
+        let report = HitReport::sonar(player_id.to_string(), center.0, center.1, proof.count, proof.serialize());
+        self.report_hit(game_id, report)
+    }
+
+    /// Concede the game by revealing the board behind the player's commitment
+    ///
+    /// The reveal proves the declaration comes from the player; the defeat takes
+    /// effect on every node once the declaration is mined.
+    pub fn declare_defeat(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        positions: Vec<(u16, u16)>,
+        salt: String,
+    ) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        let player = game.players.get(player_id)
             .ok_or("Player not found")?;
 
-        // Deserialize proof
-        let proof: HitProof = serde_json::from_slice(&report.proof)
-            .map_err(|_| "Invalid proof format")?;
+        if player.is_defeated() {
+            return Err(format!("Player {} is already defeated", player_id));
+        }
 
-        // Verify the proof
-        let is_valid = if report.is_hit {
-            proof.verify_hit((report.shot_x, report.shot_y), &player.board_commitment)
-        } else {
-            proof.verify_miss((report.shot_x, report.shot_y), &player.board_commitment)
+        if !verify_commitment(&player.board_commitment, &positions, &salt) {
+            return Err("Revealed board does not match the commitment".to_string());
+        }
+
+        let already_declared = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id && tx.player_id == player_id
+                && matches!(tx.kind, TransactionKind::DefeatDeclaration { .. })
+        });
+        if already_declared {
+            return Err("Defeat declaration is already pending".to_string());
+        }
+
+        self.submit(Transaction::defeat_declaration(game_id, player_id, positions, salt))?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Publish a player's full board after the game for the cheat audit
+    pub fn reveal_board(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        positions: Vec<(u16, u16)>,
+        salt: String,
+    ) -> Result<(), String> {
+        self.reveal_board_with_history(game_id, player_id, positions, salt, Vec::new())
+    }
+
+    /// Publish a player's final board and every board they held before moving ships
+    pub fn reveal_board_with_history(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        positions: Vec<(u16, u16)>,
+        salt: String,
+        earlier_boards: Vec<RevealedBoard>,
+    ) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Reveal)?;
+
+        let player = game.players.get(player_id)
+            .ok_or("Player not found")?;
+        if player.has_revealed() {
+            return Err(format!("Player {} already revealed their board", player_id));
+        }
+        if !verify_commitment(&player.board_commitment, &positions, &salt) {
+            return Err("Revealed board does not match the commitment".to_string());
+        }
+        if earlier_boards.len() != player.board_history.len() {
+            return Err(format!(
+                "Player {} moved ships {} times and must reveal every earlier board",
+                player_id, player.board_history.len()
+            ));
+        }
+        let earlier_valid = player.board_history.iter()
+            .zip(&earlier_boards)
+            .all(|(epoch, board)| verify_commitment(&epoch.commitment, &board.positions, &board.salt));
+        if !earlier_valid {
+            return Err("An earlier board does not match its commitment".to_string());
+        }
+
+        let already_revealed = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id && tx.player_id == player_id
+                && matches!(tx.kind, TransactionKind::BoardReveal { .. })
+        });
+        if already_revealed {
+            return Err("Board reveal is already pending".to_string());
+        }
+
+        self.submit(Transaction::board_reveal(game_id, player_id, positions, salt, earlier_boards))?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Slide one of the player's unhit ships and commit to the resulting board
+    pub fn move_ship(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        ship_name: &str,
+        new_positions: Vec<(u16, u16)>,
+        new_salt: String,
+    ) -> Result<String, String> {
+        self.move_ship_with_ship_root(game_id, player_id, ship_name, new_positions, new_salt, None)
+    }
+
+    /// Slide a ship, optionally committing to a new per-ship fleet root for sunk declarations
+    ///
+    /// Spends `move_cost` shot UTXOs and returns the new board commitment. The
+    /// commitment replaces the old one on every node once the move is mined;
+    /// shots mined up to then are still answered against the old board.
+    pub fn move_ship_with_ship_root(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        ship_name: &str,
+        new_positions: Vec<(u16, u16)>,
+        new_salt: String,
+        ship_root: Option<String>,
+    ) -> Result<String, String> {
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Move)?;
+
+        let player = game.players.get(player_id)
+            .ok_or("Player not found")?;
+        if player.is_defeated() {
+            return Err("Defeated players cannot move ships".to_string());
+        }
+
+        let already_moving = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id && tx.player_id == player_id
+                && matches!(tx.kind, TransactionKind::ShipMove { .. })
+        });
+        if already_moving {
+            return Err("A ship move for this player is already pending".to_string());
+        }
+
+        // A ship with unanswered shots at it must stay put until they are reported
+        let under_fire = player.ships.iter()
+            .find(|ship| ship.id == ship_name)
+            .is_some_and(|ship| game.open_obligations(player_id).iter()
+                .any(|o| ship.positions.contains(&(o.shot_x, o.shot_y))));
+        if under_fire {
+            return Err(format!("Ship {} has unreported shots at it and cannot move yet", ship_name));
+        }
+
+        if !player.knows_board() {
+            return Err("Ship positions do not match the board commitment".to_string());
+        }
+
+        let rules = game.config.relocation.clone();
+        let ships = relocate(&player.ships, ship_name, &new_positions, &player.hit_cells, rules.max_distance, &game.grid.map)?;
+        let new_board: Vec<(u16, u16)> = ships.iter()
+            .flat_map(|ship| ship.positions.clone())
+            .collect();
+        // The audit holds every revealed board to this same check
+        check_slide(&player.get_all_ship_positions(), &new_board, &player.hit_cells, rules.max_distance, &game.grid.map)?;
+        let proof = MoveProof::prove(&player.get_all_ship_positions(), &player.salt, &new_board, &new_salt, rules.max_distance);
+
+        let account = self.shot_account(game_id, player_id);
+        let spent_utxos = self.blockchain.consume_shots(&account, rules.move_cost)?;
+
+        let commitment = proof.new_commitment.clone();
+        let mut transaction = Transaction::ship_move(game_id, player_id, proof, ship_root);
+        transaction.spent_utxos = spent_utxos;
+        self.submit(transaction)?;
+        let planned = PlannedMove {
+            commitment: commitment.clone(),
+            ships,
+            salt: new_salt,
         };
+        self.plan_move(game_id, player_id, planned)?;
 
-        if !is_valid {
-            return Err("Invalid proof".to_string());
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
         }
 
-        // Update player's ship state if hit
-        if report.is_hit {
-            if let Some(player) = self.players.get_mut(&report.player_id) {
-                player.check_hit(report.shot_x, report.shot_y);
+        Ok(commitment)
+    }
+
+    /// Keep a ship move until it is mined, when the player's board becomes the planned one
+    fn plan_move(&mut self, game_id: &str, player_id: &str, planned: PlannedMove) -> Result<(), String> {
+        let player = self.game_mut(game_id)?.players.get_mut(player_id).ok_or("Player not found")?;
+        player.planned_move = Some(planned.clone());
+        self.record(SetupAction::PlanMove { game_id: game_id.to_string(), player_id: player_id.to_string(), planned });
+        Ok(())
+    }
+
+    /// Hand `amount` of a player's shots to another player in the same game
+    ///
+    /// The shots are locked at once and move to the recipient on every node once
+    /// the transfer is mined, keeping their expiry heights.
+    pub fn transfer_shots(&mut self, game_id: &str, from: &str, to: &str, amount: u32) -> Result<(), String> {
+        self.transfer_shots_with_fee(game_id, from, to, amount, 0)
+    }
+
+    /// Transfer shots, also paying `fee` shots to the miner who includes the transfer
+    pub fn transfer_shots_with_fee(&mut self, game_id: &str, from: &str, to: &str, amount: u32, fee: u32) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Trade)?;
+        game.check_trade(from, to)?;
+        if amount == 0 {
+            return Err("Transfers need a positive amount".to_string());
+        }
+
+        let account = self.shot_account(game_id, from);
+        let mut transaction = Transaction::transfer(game_id, from, to, amount);
+        transaction.fee = fee;
+        self.lock_for(&mut transaction, &account, amount)?;
+        self.submit(transaction)?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Offer to trade `give` of the maker's shots for `want` of the taker's, returning the offer ID
+    ///
+    /// The maker's shots stay in escrow until the taker accepts or the offer expires.
+    pub fn offer_swap(&mut self, game_id: &str, maker: &str, taker: &str, give: u32, want: u32) -> Result<String, String> {
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Trade)?;
+        game.check_trade(maker, taker)?;
+        if give == 0 || want == 0 {
+            return Err("Swaps need a positive amount on both sides".to_string());
+        }
+
+        let account = self.shot_account(game_id, maker);
+        let mut transaction = Transaction::swap_offer(game_id, maker, taker, give, want);
+        let offer_id = self.lock_for(&mut transaction, &account, give)?;
+        self.submit(transaction)?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(offer_id)
+    }
+
+    /// Accept a swap offer, locking the taker's side of the trade
+    ///
+    /// Both sides change hands in the block that mines the acceptance, or neither does.
+    pub fn accept_swap(&mut self, game_id: &str, taker: &str, offer_id: &str) -> Result<(), String> {
+        let height = self.current_height() + 1;
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Trade)?;
+        let want = game.check_swap_acceptance(offer_id, taker, height)?.want;
+
+        let already_accepted = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id
+                && matches!(&tx.kind, TransactionKind::SwapAccept { offer_id: pending } if pending == offer_id)
+        });
+        if already_accepted {
+            return Err("Acceptance of this swap is already pending".to_string());
+        }
+
+        let account = self.shot_account(game_id, taker);
+        let mut transaction = Transaction::swap_accept(game_id, taker, offer_id);
+        self.lock_for(&mut transaction, &account, want)?;
+        self.submit(transaction)?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Targets of a player's pending shots, which the repeat rule counts as already aimed at
+    fn pending_targets(&self, game_id: &str, player_id: &str) -> Vec<(u16, u16)> {
+        self.blockchain.mempool.iter()
+            .filter(|tx| tx.game_id == game_id && tx.player_id == player_id && tx.kind.is_shot() && !tx.weapon.is_sonar())
+            .map(|tx| (tx.target_x, tx.target_y))
+            .collect()
+    }
+
+    /// Add a transaction relayed by a peer to the mempool
+    ///
+    /// It must spend its sender's own shots, enough to cover its cost and fee, and shots
+    /// in games this node hosts must aim at a target the game allows.
+    pub fn receive_transaction(&mut self, transaction: Transaction) -> Result<String, String> {
+        self.check_spends(&transaction)?;
+        if transaction.kind.is_shot() {
+            if let Some(game) = self.games.get(&transaction.game_id) {
+                let earlier = self.pending_targets(&transaction.game_id, &transaction.player_id);
+                let target = (transaction.target_x, transaction.target_y);
+                game.check_target(&transaction.player_id, target, transaction.weapon, &earlier)?;
+            }
+        }
+        self.blockchain.add_transaction(transaction)
+    }
+
+    /// Give a transaction its sender's next nonce and add it to the mempool
+    ///
+    /// If its spends do not check out or the mempool rejects it, the shots it spent or locked are handed back.
+    fn submit(&mut self, mut transaction: Transaction) -> Result<(), String> {
+        transaction.nonce = self.blockchain.next_nonce(&transaction.game_id, &transaction.player_id);
+        let added = self.check_spends(&transaction)
+            .and_then(|()| self.blockchain.add_transaction(transaction.clone()));
+        if let Err(e) = added {
+            self.blockchain.unlock(&transaction);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Record the shots a transaction spends and lock them under its hash, returning the hash
+    ///
+    /// The transaction's fee is spent from the same account before `count` shots are locked.
+    fn lock_for(&mut self, transaction: &mut Transaction, account: &str, count: u32) -> Result<String, String> {
+        transaction.nonce = self.blockchain.next_nonce(&transaction.game_id, &transaction.player_id);
+        transaction.spent_utxos = self.blockchain.select_shots(account, count.saturating_add(transaction.fee))?;
+        let escrow_id = transaction.hash();
+        let (fee, locked) = transaction.spent_utxos.split_at(transaction.fee as usize);
+        self.blockchain.spend_utxos(fee);
+        self.blockchain.escrow_utxos(locked, &escrow_id);
+        Ok(escrow_id)
+    }
+
+    /// Every swap offer mined in a game
+    pub fn swaps(&self, game_id: &str) -> Result<Vec<SwapOffer>, String> {
+        Ok(self.game(game_id)?.swaps.clone())
+    }
+
+    /// Every team in a game with its players
+    pub fn teams(&self, game_id: &str) -> Result<BTreeMap<String, Vec<String>>, String> {
+        Ok(self.game(game_id)?.teams())
+    }
+
+    /// Audit every revealed board in a finished game against the reports on the chain
+    pub fn audit(&self, game_id: &str) -> Result<CheatAudit, String> {
+        self.game(game_id)?
+            .audit(self.current_height())
+            .ok_or_else(|| format!("Game {} is not over yet", game_id))
+    }
+
+    /// Announce that one of the player's ships sank
+    ///
+    /// The proof reveals the ship's cells against the fleet root committed at
+    /// registration, and every cell must already have a confirmed hit.
+    pub fn declare_sunk(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        ship_name: &str,
+        proof: ShipProof,
+    ) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        game.check_sunk_declaration(player_id, ship_name, &proof)?;
+
+        let already_declared = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id && tx.player_id == player_id
+                && matches!(&tx.kind, TransactionKind::SunkDeclaration { proof: pending, .. } if pending.positions == proof.positions)
+        });
+        if already_declared {
+            return Err("Sunk declaration is already pending".to_string());
+        }
+
+        self.submit(Transaction::sunk_declaration(game_id, player_id, ship_name, proof))?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Challenge another player's mined report, locking the bond in escrow
+    ///
+    /// Returns the challenge ID. The reporter must open the cell within the
+    /// response window or be slashed.
+    pub fn challenge_report(
+        &mut self,
+        game_id: &str,
+        challenger: &str,
+        reporter: &str,
+        shot_id: &str,
+    ) -> Result<String, String> {
+        self.challenge_report_at(game_id, challenger, reporter, shot_id, None)
+    }
+
+    /// Challenge the report for one cell of an area shot (None picks the shot's first cell)
+    pub fn challenge_report_at(
+        &mut self,
+        game_id: &str,
+        challenger: &str,
+        reporter: &str,
+        shot_id: &str,
+        cell: Option<(u16, u16)>,
+    ) -> Result<String, String> {
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Challenge)?;
+        let cell = game.check_challenge(challenger, reporter, shot_id, cell)
+            .map(|o| (o.shot_x, o.shot_y))?;
+        let bond = game.config.challenges.bond_shots;
+
+        let already_challenged = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id && (tx.target_x, tx.target_y) == cell
+                && matches!(&tx.kind, TransactionKind::Challenge { shot_id: pending, reporter: pending_reporter }
+                    if pending == shot_id && pending_reporter == reporter)
+        });
+        if already_challenged {
+            return Err("Challenge to this report is already pending".to_string());
+        }
+
+        let mut transaction = Transaction::challenge(game_id, challenger, reporter, shot_id, cell);
+        let account = self.shot_account(game_id, challenger);
+        let challenge_id = self.lock_for(&mut transaction, &account, bond)
+            .map_err(|_| format!("Bond needs {} unspent shot UTXOs", bond))?;
+        self.submit(transaction)?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(challenge_id)
+    }
+
+    /// Answer a challenge by revealing the board behind the reporter's commitment
+    pub fn respond_to_challenge(
+        &mut self,
+        game_id: &str,
+        player_id: &str,
+        challenge_id: &str,
+        positions: Vec<(u16, u16)>,
+        salt: String,
+    ) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        game.check_action(GameAction::Respond)?;
+        game.check_challenge_response(challenge_id, player_id, &positions, &salt)?;
+
+        let already_answered = self.blockchain.mempool.iter().any(|tx| {
+            tx.game_id == game_id
+                && matches!(&tx.kind, TransactionKind::ChallengeResponse { challenge_id: pending, .. } if pending == challenge_id)
+        });
+        if already_answered {
+            return Err("Response to this challenge is already pending".to_string());
+        }
+
+        self.submit(Transaction::challenge_response(game_id, player_id, challenge_id, positions, salt))?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Every fraud challenge mined in a game
+    pub fn challenges(&self, game_id: &str) -> Result<Vec<ReportChallenge>, String> {
+        Ok(self.game(game_id)?.challenges.clone())
+    }
+
+    /// Results of every mined shot a player fired in a game
+    pub fn shot_results(&self, game_id: &str, player_id: &str) -> Result<Vec<ShotResult>, String> {
+        let game = self.game(game_id)?;
+        if !game.players.contains_key(player_id) {
+            return Err("Player not found".to_string());
+        }
+        Ok(game.shot_results(player_id))
+    }
+
+    /// Reports a player still owes in a game
+    pub fn open_obligations(&self, game_id: &str, player_id: &str) -> Result<Vec<ReportObligation>, String> {
+        let game = self.game(game_id)?;
+        if !game.players.contains_key(player_id) {
+            return Err("Player not found".to_string());
+        }
+        Ok(game.open_obligations(player_id).into_iter().cloned().collect())
+    }
+
+    /// Apply the game effects of a block that was just added to the chain
+    ///
+    /// Every mined shot creates report obligations, every mined report fulfils one,
+    /// sunk and defeat declarations and board reveals are recorded, ship moves swap the
+    /// player's board commitment, challenges are opened and settled, shots change hands
+    /// through transfers and swaps, and reports or responses still owed past their
+    /// deadline are penalized.
+    pub fn process_block(&mut self, index: u64) {
+        let transactions = match self.blockchain.chain.get(index as usize) {
+            Some(block) => block.transactions.clone(),
+            None => return,
+        };
+
+        for tx in transactions {
+            let Some(game) = self.games.get_mut(&tx.game_id) else {
+                continue;
+            };
+            match &tx.kind {
+                TransactionKind::Shot => {
+                    game.record_target(&tx.player_id, (tx.target_x, tx.target_y), tx.weapon);
+                    game.add_weapon_obligations(&tx.hash(), &tx.player_id, (tx.target_x, tx.target_y), tx.weapon, index);
+                }
+                TransactionKind::HitReport { shot_id, is_hit, proof, count } => {
+                    let mut report = HitReport::new(tx.player_id.clone(), tx.target_x, tx.target_y, *is_hit, proof.clone());
+                    report.shot_id = shot_id.clone();
+                    report.count = *count;
+
+                    // Reports with an invalid proof leave the obligation open
+                    if game.accept_report(&report).is_err() || !game.fulfil_obligation(&report, index) {
+                        continue;
+                    }
+                    if report.is_hit && report.count.is_none() {
+                        if let Some(player) = game.players.get_mut(&report.player_id) {
+                            player.record_hit(report.shot_x, report.shot_y);
+                        }
+                    }
+                }
+                TransactionKind::SunkDeclaration { ship_name, proof } => {
+                    if game.check_sunk_declaration(&tx.player_id, ship_name, proof).is_ok() {
+                        game.record_sunk(&tx.player_id, ship_name, proof, index);
+                    }
+                }
+                TransactionKind::DefeatDeclaration { positions, salt } => {
+                    if let Some(player) = game.players.get_mut(&tx.player_id) {
+                        if verify_commitment(&player.board_commitment, positions, salt) {
+                            player.declared_defeat_at.get_or_insert(index);
+                            player.revealed_board.get_or_insert_with(|| RevealedBoard { positions: positions.clone(), salt: salt.clone() });
+                        }
+                    }
+                }
+                TransactionKind::Challenge { shot_id, reporter } => {
+                    let challenge_id = tx.hash();
+                    if !game.add_challenge(&challenge_id, &tx.player_id, reporter, shot_id, (tx.target_x, tx.target_y), index) {
+                        let challenger = Self::account_id(&tx.game_id, &game.shot_owner(&tx.player_id));
+                        self.blockchain.release_escrow(&challenge_id, &challenger);
+                    }
+                }
+                TransactionKind::ChallengeResponse { challenge_id, positions, salt } => {
+                    let Ok(revealed_hit) = game.check_challenge_response(challenge_id, &tx.player_id, positions, salt) else {
+                        continue;
+                    };
+                    if let Some(challenge) = game.resolve_challenge(challenge_id, Some(revealed_hit), index) {
+                        let slash = game.config.challenges.slash_shots;
+                        Self::settle_challenge(&mut self.blockchain, &tx.game_id, game, &challenge, slash);
+                    }
+                }
+                TransactionKind::BoardReveal { positions, salt, earlier_boards } => {
+                    if game.lifecycle.phase != GamePhase::GameOver {
+                        continue;
+                    }
+                    if let Some(player) = game.players.get_mut(&tx.player_id) {
+                        let earlier_valid = earlier_boards.len() == player.board_history.len()
+                            && player.board_history.iter()
+                                .zip(earlier_boards)
+                                .all(|(epoch, board)| verify_commitment(&epoch.commitment, &board.positions, &board.salt));
+                        if earlier_valid && verify_commitment(&player.board_commitment, positions, salt) {
+                            player.revealed_board.get_or_insert_with(|| RevealedBoard { positions: positions.clone(), salt: salt.clone() });
+                            player.revealed_history = earlier_boards.clone();
+                        }
+                    }
+                }
+                TransactionKind::Transfer { to, .. } => {
+                    let id = tx.hash();
+                    let from = Self::account_id(&tx.game_id, &game.shot_owner(&tx.player_id));
+                    let recipient = if game.check_trade(&tx.player_id, to).is_ok() {
+                        Self::account_id(&tx.game_id, &game.shot_owner(to))
+                    } else {
+                        from
+                    };
+                    self.blockchain.release_escrow(&id, &recipient);
+                }
+                TransactionKind::SwapOffer { taker, give, want } => {
+                    let id = tx.hash();
+                    let maker = Self::account_id(&tx.game_id, &game.shot_owner(&tx.player_id));
+                    if !game.add_swap(&id, &tx.player_id, taker, *give, *want, index) {
+                        self.blockchain.release_escrow(&id, &maker);
+                    }
+                }
+                TransactionKind::SwapAccept { offer_id } => {
+                    let id = tx.hash();
+                    let taker = Self::account_id(&tx.game_id, &game.shot_owner(&tx.player_id));
+                    let Some(offer) = game.complete_swap(offer_id, &tx.player_id, index) else {
+                        self.blockchain.release_escrow(&id, &taker);
+                        continue;
+                    };
+                    let maker = Self::account_id(&tx.game_id, &game.shot_owner(&offer.maker));
+                    self.blockchain.release_escrow(&offer.id, &taker);
+                    self.blockchain.release_escrow(&id, &maker);
+                }
+                // Beacon contributions are read straight from the chain
+                TransactionKind::BeaconCommit { .. } | TransactionKind::BeaconReveal { .. } => {}
+                TransactionKind::ShipMove { proof, ship_root } => {
+                    let max_distance = game.config.relocation.max_distance;
+                    if let Some(player) = game.players.get_mut(&tx.player_id) {
+                        if proof.verify(&player.board_commitment, max_distance) && !player.is_defeated() {
+                            player.apply_move(proof, index);
+                            if ship_root.is_some() {
+                                player.ship_root = ship_root.clone();
+                            }
+                        }
+                    }
+                }
             }
         }
 
-        Ok(is_valid)
+        self.enforce_report_deadlines(index);
+        self.enforce_challenge_deadlines(index);
+        self.enforce_swap_deadlines(index);
+        self.drop_illegal_shots();
+    }
+
+    /// Drop pending shots that the latest block made illegal, handing their shots back
+    fn drop_illegal_shots(&mut self) {
+        let pending = self.blockchain.mempool.transactions();
+        let illegal: Vec<Transaction> = self.illegal_shots(&pending)
+            .into_iter()
+            .map(|(tx, _)| tx.clone())
+            .collect();
+        self.blockchain.mempool.remove(&illegal.iter().map(Transaction::hash).collect::<Vec<_>>());
+        for transaction in &illegal {
+            self.blockchain.unlock(transaction);
+        }
+    }
+
+    /// Return the maker's shots for swap offers nobody accepted in time
+    fn enforce_swap_deadlines(&mut self, index: u64) {
+        for (game_id, game) in self.games.iter_mut() {
+            for offer in game.expire_swaps(index) {
+                let maker = Self::account_id(game_id, &game.shot_owner(&offer.maker));
+                self.blockchain.release_escrow(&offer.id, &maker);
+            }
+        }
+    }
+
+    /// Uphold challenges whose reporter did not respond in time
+    fn enforce_challenge_deadlines(&mut self, index: u64) {
+        for (game_id, game) in self.games.iter_mut() {
+            let slash = game.config.challenges.slash_shots;
+            for challenge in game.expire_challenges(index) {
+                Self::settle_challenge(&mut self.blockchain, game_id, game, &challenge, slash);
+            }
+        }
+    }
+
+    /// Move the escrowed bond and any slashed shots once a challenge is resolved
+    ///
+    /// An upheld challenge returns the bond to the challenger and hands them up to
+    /// `slash` of the reporter's unspent shots; a rejected one pays the bond to the reporter.
+    fn settle_challenge(blockchain: &mut Blockchain, game_id: &str, game: &Game, challenge: &ReportChallenge, slash: u32) {
+        let challenger = Self::account_id(game_id, &game.shot_owner(&challenge.challenger));
+        let reporter = Self::account_id(game_id, &game.shot_owner(&challenge.player_id));
+        match challenge.status {
+            ChallengeStatus::Upheld => {
+                blockchain.release_escrow(&challenge.id, &challenger);
+                blockchain.transfer_shots(&reporter, &challenger, slash);
+            }
+            ChallengeStatus::Rejected => blockchain.release_escrow(&challenge.id, &reporter),
+            ChallengeStatus::Open => {}
+        }
+    }
+
+    /// Penalize players whose reports were not mined by their deadline
+    ///
+    /// Reports are no longer accepted once a game is over, so its deadlines stop running too.
+    fn enforce_report_deadlines(&mut self, index: u64) {
+        for (game_id, game) in self.games.iter_mut() {
+            if game.lifecycle.phase == GamePhase::GameOver {
+                continue;
+            }
+            let penalty = game.config.reports.penalty.clone();
+            for obligation in game.expire_obligations(index) {
+                let account = Self::account_id(game_id, &game.shot_owner(&obligation.player_id));
+                let Some(player) = game.players.get_mut(&obligation.player_id) else {
+                    continue;
+                };
+                match penalty {
+                    MissedReportPenalty::TreatAsHit => {
+                        if !obligation.sonar {
+                            player.record_hit(obligation.shot_x, obligation.shot_y);
+                        }
+                    }
+                    MissedReportPenalty::LoseShots { count } => {
+                        for _ in 0..count {
+                            if self.blockchain.consume_shot(&account).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    MissedReportPenalty::Forfeit => {
+                        player.forfeited_at.get_or_insert(index);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replace the chain with a longer one from a peer and apply its new blocks
+    ///
+    /// A chain extending ours has its new blocks checked and applied as blocks from
+    /// a peer are. A chain that forked from ours rebuilds this node's games by
+    /// replaying it from genesis with the setup log, so nothing our abandoned blocks
+    /// did survives. Either way this node keeps the mempool transactions still valid.
+    pub fn adopt_chain(&mut self, blockchain: Blockchain) -> Result<(), String> {
+        if blockchain.chain.len() <= self.blockchain.chain.len() {
+            return Err("Chain is not longer than ours".to_string());
+        }
+
+        let extends_ours = self.blockchain.chain.iter()
+            .zip(&blockchain.chain)
+            .all(|(ours, theirs)| ours.hash == theirs.hash);
+        if extends_ours {
+            for block in &blockchain.chain[self.blockchain.chain.len()..] {
+                self.accept_block(block.clone())?;
+            }
+        } else {
+            let pending = self.blockchain.mempool.transactions();
+            let mut rebuilt = self.replay(&blockchain.chain, &pending)?;
+            rebuilt.blockchain_path = self.blockchain_path.take();
+            *self = rebuilt;
+        }
+        Ok(())
+    }
+
+    /// Validate a block received from a peer and append it to the chain
+    ///
+    /// The block's coinbase must mint exactly what its game pays, so blocks paying a
+    /// game this node does not host are refused.
+    pub fn accept_block(&mut self, block: Block) -> Result<(), String> {
+        if block.index != self.blockchain.chain.len() as u64 {
+            return Err("Invalid block index".to_string());
+        }
+
+        if block.previous_hash != self.blockchain.get_latest_block().hash {
+            return Err("Invalid previous hash".to_string());
+        }
+
+        if block.hash != block.calculate_hash() {
+            return Err("Invalid block hash".to_string());
+        }
+
+        if !block.hash.starts_with(&"0".repeat(self.blockchain.difficulty)) {
+            return Err("Invalid proof of work".to_string());
+        }
+
+        let coinbase = block.coinbase.as_ref().ok_or("Block has no coinbase")?;
+        coinbase.check_shares(&block.previous_hash, self.blockchain.share_difficulty)?;
+        coinbase.check_payouts(&self.coinbase_payouts(coinbase, block.index, &block.transactions)?)?;
+        self.blockchain.limits.check(&block.transactions)?;
+        self.blockchain.check_block_spends(&block, |tx| self.spend_of(tx))?;
+        self.blockchain.check_block_conflicts(&block)?;
+        self.check_block_targets(&block)?;
+
+        let expires_at = self.coinbase_expiry(coinbase, block.index);
+        self.blockchain.append_block(block, expires_at);
+        self.process_block(self.current_height());
+        self.advance_lifecycle();
+
+        Ok(())
+    }
+
+    /// Check every shot in a block follows its game's shot rules
+    pub fn check_block_targets(&self, block: &Block) -> Result<(), String> {
+        match self.illegal_shots(&block.transactions).into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Shots among `transactions` aiming at targets their game does not allow, with the reason
+    ///
+    /// Each shot counts the targets of the same player's legal shots before it. Shots
+    /// in games this node does not host are left to the nodes that do.
+    fn illegal_shots<'a>(&self, transactions: &'a [Transaction]) -> Vec<(&'a Transaction, String)> {
+        let mut earlier: HashMap<(String, String), Vec<(u16, u16)>> = HashMap::new();
+        let mut illegal = Vec::new();
+        for tx in transactions.iter().filter(|tx| tx.kind.is_shot()) {
+            let Some(game) = self.games.get(&tx.game_id) else {
+                continue;
+            };
+            let key = (tx.game_id.clone(), tx.player_id.clone());
+            let targets = earlier.entry(key).or_default();
+            match game.check_target(&tx.player_id, (tx.target_x, tx.target_y), tx.weapon, targets) {
+                Err(e) => illegal.push((tx, e)),
+                Ok(()) if !tx.weapon.is_sonar() => targets.push((tx.target_x, tx.target_y)),
+                Ok(()) => {}
+            }
+        }
+        illegal
+    }
+
+    /// Height of the latest block
+    pub fn current_height(&self) -> u64 {
+        self.blockchain.get_latest_block().index
+    }
+
+    /// Apply every phase transition due at the current height in every game
+    pub fn advance_lifecycle(&mut self) {
+        let height = self.current_height();
+        for game in self.games.values_mut() {
+            game.advance_lifecycle(height);
+        }
+    }
+
+    /// Current phase of a game
+    pub fn phase(&self, game_id: &str) -> Result<GamePhase, String> {
+        Ok(self.game(game_id)?.lifecycle.phase)
     }
 
     /// Check if a player is defeated
-    pub fn is_player_defeated(&self, player_id: &str) -> bool {
-        self.players.get(player_id)
-            .map(|p| p.is_defeated())
+    pub fn is_player_defeated(&self, game_id: &str, player_id: &str) -> bool {
+        self.games.get(game_id)
+            .map(|game| game.is_player_defeated(player_id))
             .unwrap_or(false)
     }
 
-    /// Get all active players
-    pub fn get_active_players(&self) -> Vec<String> {
-        self.players.iter()
-            .filter(|(_, p)| !p.is_defeated())
-            .map(|(id, _)| id.clone())
-            .collect()
+    /// Get all active players of a game
+    pub fn get_active_players(&self, game_id: &str) -> Vec<String> {
+        self.games.get(game_id)
+            .map(|game| game.get_active_players())
+            .unwrap_or_default()
     }
 
-    /// Advance to next round
-    #[allow(dead_code)]
-    pub fn next_round(&mut self) {
-        self.round += 1;
+    /// Advance a game to its next round
+    ///
+    /// In turn-based games this first seals the pending transactions into a block
+    /// that mints no shots, then fixes the new round's turn order from that block's
+    /// hash and, once the battle is on, grants every active player one shot. A
+    /// turn-based round only ends once every active player has fired or passed.
+    pub fn next_round(&mut self, game_id: &str) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        if game.config.turns.is_turn_based() {
+            if let Some(current) = game.current_turn() {
+                return Err(format!("Round {} is still on: it is {}'s turn", game.round, current));
+            }
+            let template = self.blockchain.block_template();
+            self.blockchain.mine_block(template, Coinbase::sealed(game_id), None);
+            self.process_block(self.current_height());
+            self.advance_lifecycle();
+        }
+        self.start_round(game_id)?;
+
+        if let Err(e) = self.save_blockchain() {
+            eprintln!("Warning: Failed to save blockchain: {}", e);
+        }
+        Ok(())
     }
 
-    /// Get game statistics
-    pub fn get_stats(&self) -> GameStats {
-        GameStats {
-            round: self.round,
-            total_players: self.players.len(),
-            active_players: self.get_active_players().len(),
-            total_shots: self.blockchain.get_transaction_count(),
-            blockchain_length: self.blockchain.chain.len(),
+    /// Start a game's next round on top of the latest block
+    fn start_round(&mut self, game_id: &str) -> Result<(), String> {
+        if !self.game(game_id)?.config.turns.is_turn_based() {
+            self.game_mut(game_id)?.round += 1;
+            self.record(SetupAction::NextRound { game_id: game_id.to_string() });
+            return Ok(());
+        }
+
+        let beacon = self.round_beacon(game_id, self.current_height())?;
+        let game = self.game_mut(game_id)?;
+        game.start_round(&beacon);
+        if game.check_action(GameAction::Fire).is_ok() {
+            let policy = self.reward_policy(game_id)?;
+            let expires_at = policy.expiry(self.current_height());
+            for player_id in self.game(game_id)?.turn_order.clone() {
+                let account = self.shot_account(game_id, &player_id);
+                let balance = self.blockchain.confirmed_balance(&account, self.blockchain.next_height(), &[]);
+                let grant = policy.capped(1, balance);
+                self.blockchain.award_registration_shots(&account, grant, expires_at);
+            }
         }
+        self.record(SetupAction::NextRound { game_id: game_id.to_string() });
+        Ok(())
+    }
+
+    /// Beacon shuffling the turn order of a game's round starting at `height`
+    fn round_beacon(&self, game_id: &str, height: u64) -> Result<Beacon, String> {
+        self.blockchain.beacon(height, &format!("{}/turns", game_id))
+            .ok_or_else(|| format!("No block at height {}", height))
+    }
+
+    /// Commit a player to a secret they will mix into the beacon for `domain`
+    ///
+    /// Returns the commitment. The secret must be revealed with `reveal_beacon_secret`
+    /// within the reveal window after the beacon's height to count.
+    pub fn commit_beacon_secret(&mut self, game_id: &str, player_id: &str, domain: &str, secret: &str) -> Result<String, String> {
+        if !self.game(game_id)?.players.contains_key(player_id) {
+            return Err("Player not found".to_string());
+        }
+        let commitment = beacon_commitment(domain, &sender_id(game_id, player_id), secret);
+        self.submit(Transaction::beacon_commit(game_id, player_id, domain, &commitment))?;
+        Ok(commitment)
+    }
+
+    /// Reveal the secret behind a player's beacon commitment
+    pub fn reveal_beacon_secret(&mut self, game_id: &str, player_id: &str, domain: &str, secret: &str) -> Result<(), String> {
+        if !self.game(game_id)?.players.contains_key(player_id) {
+            return Err("Player not found".to_string());
+        }
+        self.submit(Transaction::beacon_reveal(game_id, player_id, domain, secret))
+    }
+
+    /// Give up a player's turn in a turn-based game without firing
+    pub fn pass_turn(&mut self, game_id: &str, player_id: &str) -> Result<(), String> {
+        let game = self.game(game_id)?;
+        if !game.config.turns.is_turn_based() {
+            return Err(format!("Game {} has no turns", game_id));
+        }
+        game.check_turn(player_id)?;
+        self.end_turn(game_id)
+    }
+
+    /// Hand the turn to the next player, starting the next round once everyone has had theirs
+    fn end_turn(&mut self, game_id: &str) -> Result<(), String> {
+        self.game_mut(game_id)?.end_turn();
+        self.record(SetupAction::EndTurn { game_id: game_id.to_string() });
+        if self.game(game_id)?.current_turn().is_none() {
+            self.next_round(game_id)?;
+        }
+        Ok(())
+    }
+
+    /// Get statistics for a game
+    pub fn get_stats(&self, game_id: &str) -> Result<GameStats, String> {
+        let game = self.game(game_id)?;
+        let total_shots = self.blockchain.chain.iter()
+            .flat_map(|block| block.transactions.iter())
+            .filter(|tx| tx.game_id == game_id && tx.kind.is_shot())
+            .count();
+
+        let winner = game.audit(self.current_height()).and_then(|audit| audit.winner);
+        let winning_team = winner.as_ref()
+            .and_then(|id| game.players.get(id))
+            .and_then(|player| player.team.clone());
+
+        Ok(GameStats {
+            game_id: game_id.to_string(),
+            phase: game.lifecycle.phase,
+            winner,
+            winning_team,
+            round: game.round,
+            total_players: game.players.len(),
+            active_players: game.get_active_players().len(),
+            total_shots,
+            blockchain_length: self.blockchain.chain.len(),
+        })
     }
 
     /// Verify the entire blockchain is valid
+    ///
+    /// Besides the chain's own checks, every block is replayed against this node's
+    /// games, so coinbases must mint what their games paid and shots must be legal.
     pub fn verify_blockchain(&self) -> bool {
-        self.blockchain.is_chain_valid()
+        self.blockchain.is_chain_valid() && self.replay(&self.blockchain.chain, &[]).is_ok()
+    }
+
+    /// Log a setup action made on top of the latest block
+    fn record(&mut self, action: SetupAction) {
+        self.setup.push(SetupEvent { height: self.current_height(), action });
+        if let Some(path) = &self.blockchain_path {
+            if let Err(e) = setup::save_log(&setup::log_path(path), &self.setup) {
+                eprintln!("Warning: Failed to save setup log: {}", e);
+            }
+        }
+    }
+
+    /// Make a logged setup action again while rebuilding this node's games
+    fn apply_setup(&mut self, action: SetupAction) -> Result<(), String> {
+        match action {
+            SetupAction::CreateGame { game_id, config } => self.create_game(&game_id, config),
+            SetupAction::Register { game_id, player_id, ships, board_commitment, salt, ship_root, team } => {
+                self.register_player_in_team(&game_id, player_id, ships, board_commitment, salt, ship_root, team)
+            }
+            SetupAction::PlanMove { game_id, player_id, planned } => self.plan_move(&game_id, &player_id, planned),
+            SetupAction::NextRound { game_id } => self.start_round(&game_id),
+            SetupAction::EndTurn { game_id } => {
+                self.game_mut(&game_id)?.end_turn();
+                self.record(SetupAction::EndTurn { game_id });
+                Ok(())
+            }
+        }
+    }
+
+    /// Rebuild this node's games by replaying `chain` from genesis with its setup log
+    ///
+    /// Every block is validated as a block from a peer is, and every setup action is
+    /// made again on top of the block it was first made on; actions that no longer
+    /// apply on this chain are dropped. `pending` transactions that are still valid
+    /// stay in the mempool with their shots locked. The rebuilt coordinator saves
+    /// nothing until it is given a path.
+    fn replay(&self, chain: &[Block], pending: &[Transaction]) -> Result<Self, String> {
+        let mut rebuilt = Self {
+            blockchain: Blockchain::from_genesis(&self.genesis),
+            games: Self::default_games(&self.genesis),
+            genesis: self.genesis.clone(),
+            pools: HashMap::new(),
+            setup: Vec::new(),
+            blockchain_path: None,
+        };
+        match chain.first() {
+            Some(genesis) if genesis.hash == rebuilt.blockchain.genesis_hash() => {}
+            _ => return Err("Chain starts from a different genesis block".to_string()),
+        }
+
+        let mut events = self.setup.iter().peekable();
+        for block in &chain[1..] {
+            while let Some(event) = events.next_if(|event| event.height < block.index) {
+                let _ = rebuilt.apply_setup(event.action.clone());
+            }
+            rebuilt.accept_block(block.clone())
+                .map_err(|e| format!("Block {} is invalid: {}", block.index, e))?;
+        }
+        for event in events {
+            let _ = rebuilt.apply_setup(event.action.clone());
+        }
+
+        for transaction in pending {
+            if rebuilt.check_spends(transaction).is_ok() && rebuilt.blockchain.add_transaction(transaction.clone()).is_ok() {
+                rebuilt.blockchain.lock_transaction(transaction);
+            }
+        }
+        Ok(rebuilt)
     }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GameStats {
+    #[serde(default)]
+    pub game_id: String,
+    pub phase: GamePhase,
+    pub winner: Option<String>,
+    /// Team of the winner, in team games
+    #[serde(default)]
+    pub winning_team: Option<String>,
     pub round: u32,
     pub total_players: usize,
     pub active_players: usize,
@@ -310,7 +1635,9 @@ impl std::fmt::Display for GameStats {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Round: {} | Players: {}/{} | Shots: {} | Blocks: {}",
+            "Game: {} | Phase: {} | Round: {} | Players: {}/{} | Shots: {} | Blocks: {}",
+            self.game_id,
+            self.phase,
             self.round,
             self.active_players,
             self.total_players,
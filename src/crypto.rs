@@ -1,6 +1,6 @@
 use sha2::{Sha256, Digest};
 use rand::Rng;
-use hex;
+use crate::weapons::Region;
 
 /// Generate a random salt for commitment scheme
 pub fn generate_salt() -> String {
@@ -11,7 +11,7 @@ pub fn generate_salt() -> String {
 
 /// Create a commitment hash for a board configuration
 /// commitment = SHA256(board_positions || salt)
-pub fn create_commitment(positions: &[(u8, u8)], salt: &str) -> String {
+pub fn create_commitment(positions: &[(u16, u16)], salt: &str) -> String {
     let mut hasher = Sha256::new();
     
     // Serialize positions in a deterministic way
@@ -19,7 +19,8 @@ pub fn create_commitment(positions: &[(u8, u8)], salt: &str) -> String {
     sorted_positions.sort();
     
     for (x, y) in sorted_positions {
-        hasher.update(&[x, y]);
+        hasher.update(x.to_le_bytes());
+        hasher.update(y.to_le_bytes());
     }
     
     hasher.update(salt.as_bytes());
@@ -29,20 +30,120 @@ pub fn create_commitment(positions: &[(u8, u8)], salt: &str) -> String {
 /// Verify a commitment against revealed positions and salt
 pub fn verify_commitment(
     commitment: &str,
-    positions: &[(u8, u8)],
+    positions: &[(u16, u16)],
     salt: &str,
 ) -> bool {
     let calculated = create_commitment(positions, salt);
     calculated == commitment
 }
 
+/// One step of a Merkle path: the sibling hash and which side it sits on
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MerkleStep {
+    pub sibling: String,
+    pub sibling_on_left: bool,
+}
+
+fn hash_pair(left: &str, right: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Merkle root over the given leaf hashes (an odd node is paired with itself)
+pub fn merkle_root(leaves: &[String]) -> String {
+    if leaves.is_empty() {
+        return hash_pair("", "");
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level.chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+    }
+    level.remove(0)
+}
+
+/// Merkle path proving the leaf at `index` belongs to `merkle_root(leaves)`
+pub fn merkle_path(leaves: &[String], mut index: usize) -> Vec<MerkleStep> {
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let sibling_index = index ^ 1;
+        let sibling = level.get(sibling_index).unwrap_or(&level[index]).clone();
+        path.push(MerkleStep {
+            sibling,
+            sibling_on_left: sibling_index < index,
+        });
+        level = level.chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+        index /= 2;
+    }
+    path
+}
+
+/// Check a Merkle path from a leaf hash up to the expected root
+pub fn verify_merkle_path(leaf: &str, path: &[MerkleStep], root: &str) -> bool {
+    let computed = path.iter().fold(leaf.to_string(), |hash, step| {
+        if step.sibling_on_left {
+            hash_pair(&step.sibling, &hash)
+        } else {
+            hash_pair(&hash, &step.sibling)
+        }
+    });
+    computed == root
+}
+
+/// Root of a per-ship fleet commitment: a Merkle tree over one commitment per ship
+///
+/// Each ship is committed with its own salt, so a single ship can later be revealed
+/// without exposing the rest of the board.
+pub fn fleet_root(ships: &[(Vec<(u16, u16)>, String)]) -> String {
+    let leaves: Vec<String> = ships.iter()
+        .map(|(positions, salt)| create_commitment(positions, salt))
+        .collect();
+    merkle_root(&leaves)
+}
+
+/// Proof that one ship's cells are part of a committed fleet root
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShipProof {
+    pub positions: Vec<(u16, u16)>,
+    pub salt: String,
+    pub path: Vec<MerkleStep>,
+}
+
+impl ShipProof {
+    /// Reveal the ship at `index` of the fleet committed with `fleet_root(ships)`
+    pub fn prove(ships: &[(Vec<(u16, u16)>, String)], index: usize) -> Self {
+        let leaves: Vec<String> = ships.iter()
+            .map(|(positions, salt)| create_commitment(positions, salt))
+            .collect();
+        let (positions, salt) = ships[index].clone();
+
+        Self {
+            positions,
+            salt,
+            path: merkle_path(&leaves, index),
+        }
+    }
+
+    /// Verify the revealed ship against a fleet root
+    pub fn verify(&self, fleet_root: &str) -> bool {
+        let leaf = create_commitment(&self.positions, &self.salt);
+        verify_merkle_path(&leaf, &self.path, fleet_root)
+    }
+}
+
 /// Simple ZK proof structure for hit verification
 /// In a real implementation, this would use proper ZK-SNARKs
 /// For this prototype, we use a simplified commitment-based approach
 #[derive(Debug, Clone)]
 pub struct HitProof {
     pub commitment: String,
-    pub revealed_position: Option<(u8, u8)>,
+    pub revealed_position: Option<(u16, u16)>,
     pub position_salt: String,
 }
 
@@ -50,8 +151,8 @@ impl HitProof {
     /// Generate a proof that a position contains a ship (hit case)
     #[allow(dead_code)]
     pub fn prove_hit(
-        position: (u8, u8),
-        _all_positions: &[(u8, u8)],
+        position: (u16, u16),
+        _all_positions: &[(u16, u16)],
         _board_salt: &str,
     ) -> Self {
         // Create a commitment to the specific position
@@ -69,8 +170,8 @@ impl HitProof {
     /// This is more complex in real ZK - here we use a simplified approach
     #[allow(dead_code)]
     pub fn prove_miss(
-        _shot_position: (u8, u8),
-        all_positions: &[(u8, u8)],
+        _shot_position: (u16, u16),
+        all_positions: &[(u16, u16)],
         board_salt: &str,
     ) -> Self {
         // For a miss, we don't reveal the position
@@ -89,7 +190,7 @@ impl HitProof {
     #[allow(dead_code)]
     pub fn verify_hit(
         &self,
-        shot_position: (u8, u8),
+        shot_position: (u16, u16),
         _board_commitment: &str,
     ) -> bool {
         if let Some(revealed_pos) = self.revealed_position {
@@ -110,7 +211,7 @@ impl HitProof {
     #[allow(dead_code)]
     pub fn verify_miss(
         &self,
-        _shot_position: (u8, u8),
+        _shot_position: (u16, u16),
         board_commitment: &str,
     ) -> bool {
         // For miss, we just verify the board commitment matches
@@ -183,3 +284,166 @@ impl<'de> serde::Deserialize<'de> for HitProof {
         deserializer.deserialize_struct("HitProof", &["commitment", "revealed_position", "position_salt"], HitProofVisitor)
     }
 }
+
+/// Proof that a committed board has `count` cells inside a region, without saying which
+///
+/// Like `HitProof`, this is a simplified stand-in for a ZK-SNARK. Every cell of the
+/// region gets its own commitment to whether a ship sits there, salted from the board
+/// salt, so only the board's owner can open them. Peers can only check the proof's shape
+/// and its binding to the board commitment when the report arrives, which anyone can
+/// forge; once the board is revealed, the end-of-game audit opens every cell commitment
+/// and flags proofs that do not match, or sonar reports that carried none.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CountProof {
+    pub region: Region,
+    pub count: u8,
+    /// One commitment per cell of the region, row by row, to whether it holds a ship
+    pub cells: Vec<String>,
+    /// Hash binding the region, count and cell commitments to the board commitment
+    pub binding: String,
+}
+
+impl CountProof {
+    /// Prove how many of the board's cells lie in the region
+    pub fn prove(region: Region, all_positions: &[(u16, u16)], board_salt: &str) -> Self {
+        let cells = Self::cell_commitments(&region, all_positions, board_salt);
+        let count = region.count(all_positions);
+        let board_commitment = create_commitment(all_positions, board_salt);
+
+        Self {
+            binding: Self::bind(&board_commitment, &region, count, &cells),
+            region,
+            count,
+            cells,
+        }
+    }
+
+    /// Verify the proof claims `count` cells in `region` for the given board commitment
+    ///
+    /// This checks the shape and binding only; whether the count is true waits for `opens_to`.
+    pub fn verify(&self, region: &Region, count: u8, board_commitment: &str) -> bool {
+        self.region == *region
+            && self.count == count
+            && self.cells.len() == region.cells().len()
+            && self.binding == Self::bind(board_commitment, region, count, &self.cells)
+    }
+
+    /// Check the proof against the board and salt revealed after the game
+    ///
+    /// Every cell commitment must open to the revealed board, and the count must match it.
+    pub fn opens_to(&self, all_positions: &[(u16, u16)], board_salt: &str) -> bool {
+        let board_commitment = create_commitment(all_positions, board_salt);
+        self.verify(&self.region, self.region.count(all_positions), &board_commitment)
+            && self.cells == Self::cell_commitments(&self.region, all_positions, board_salt)
+    }
+
+    /// Commitment to whether each cell of the region holds a ship, salted from the board salt
+    fn cell_commitments(region: &Region, all_positions: &[(u16, u16)], board_salt: &str) -> Vec<String> {
+        region.cells()
+            .into_iter()
+            .map(|(x, y)| {
+                let mut hasher = Sha256::new();
+                hasher.update(board_salt.as_bytes());
+                hasher.update(x.to_le_bytes());
+                hasher.update(y.to_le_bytes());
+                hasher.update([all_positions.contains(&(x, y)) as u8]);
+                hex::encode(hasher.finalize())
+            })
+            .collect()
+    }
+
+    fn bind(board_commitment: &str, region: &Region, count: u8, cells: &[String]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(board_commitment.as_bytes());
+        for side in [region.x, region.y, region.width, region.height] {
+            hasher.update(side.to_le_bytes());
+        }
+        hasher.update([count]);
+        for cell in cells {
+            hasher.update(cell.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+}
+
+/// Proof that a new board commitment differs from the old one by one legal ship move
+///
+/// A simplified stand-in for a ZK-SNARK: the changed cells are committed under a
+/// salt derived from the new board's salt and bound to both board commitments and
+/// the allowed distance. `verify` only checks that binding when the move is mined;
+/// the mover's node checks the move is legal before proving it, and the end-of-game
+/// audit opens the proof against the revealed boards (`opens_to`) and checks they
+/// differ by one legal slide.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MoveProof {
+    pub old_commitment: String,
+    pub new_commitment: String,
+    /// Largest slide the proof claims to respect
+    pub max_distance: u8,
+    /// Commitment to the cells the ship left and entered
+    pub moved_commitment: String,
+    /// Hash binding the move to both board commitments
+    pub binding: String,
+}
+
+impl MoveProof {
+    /// Prove the move from the old board to the new one
+    pub fn prove(
+        old_positions: &[(u16, u16)],
+        old_salt: &str,
+        new_positions: &[(u16, u16)],
+        new_salt: &str,
+        max_distance: u8,
+    ) -> Self {
+        let moved_commitment = Self::commit_moved(old_positions, new_positions, new_salt);
+        let old_commitment = create_commitment(old_positions, old_salt);
+        let new_commitment = create_commitment(new_positions, new_salt);
+
+        Self {
+            binding: Self::bind(&old_commitment, &new_commitment, max_distance, &moved_commitment),
+            old_commitment,
+            new_commitment,
+            max_distance,
+            moved_commitment,
+        }
+    }
+
+    /// Verify the proof moves away from `board_commitment` within `max_distance`
+    pub fn verify(&self, board_commitment: &str, max_distance: u8) -> bool {
+        self.old_commitment == board_commitment
+            && self.new_commitment != board_commitment
+            && self.max_distance <= max_distance
+            && self.binding == Self::bind(&self.old_commitment, &self.new_commitment, self.max_distance, &self.moved_commitment)
+    }
+
+    /// Whether the proof commits to exactly the move between the two revealed boards
+    ///
+    /// `new_salt` is the salt behind the new board, which the moved cells' salt derives from.
+    pub fn opens_to(&self, old_positions: &[(u16, u16)], new_positions: &[(u16, u16)], new_salt: &str) -> bool {
+        self.new_commitment == create_commitment(new_positions, new_salt)
+            && self.moved_commitment == Self::commit_moved(old_positions, new_positions, new_salt)
+    }
+
+    /// Commitment to the cells the ship left and entered
+    fn commit_moved(old_positions: &[(u16, u16)], new_positions: &[(u16, u16)], new_salt: &str) -> String {
+        let moved: Vec<(u16, u16)> = old_positions.iter()
+            .filter(|cell| !new_positions.contains(cell))
+            .chain(new_positions.iter().filter(|cell| !old_positions.contains(cell)))
+            .copied()
+            .collect();
+        create_commitment(&moved, &format!("{}:moved", new_salt))
+    }
+
+    fn bind(old_commitment: &str, new_commitment: &str, max_distance: u8, moved_commitment: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(old_commitment.as_bytes());
+        hasher.update(new_commitment.as_bytes());
+        hasher.update([max_distance]);
+        hasher.update(moved_commitment.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
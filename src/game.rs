@@ -1,29 +1,45 @@
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::genesis::GameConfig;
+use crate::lifecycle::{GameAction, GameLifecycle, GamePhase};
+use crate::map::GameMap;
+use crate::obligations::{ReportObligation, ShotResult};
+use crate::challenges::{ChallengeStatus, ReportChallenge};
+use crate::relocation::{check_slide, BoardEpoch, PlannedMove, RevealedBoard};
+use crate::shots::SelfFire;
+use crate::teams::team_account;
+use crate::trades::{SwapOffer, SwapStatus};
+use crate::weapons::{Region, Weapon};
+use crate::audit::{adjust_standings, audit_reports, CheatAudit, IllegalMove};
+use crate::beacon::Beacon;
+use crate::crypto::{verify_commitment, CountProof, HitProof, MoveProof, ShipProof};
+
+/// ID of the game every node hosts from its genesis config
+pub const DEFAULT_GAME_ID: &str = "default";
 
 /// Represents a ship on the grid
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Ship {
     pub id: String,
-    pub positions: Vec<(u8, u8)>,
+    pub positions: Vec<(u16, u16)>,
     pub hits: Vec<bool>,
 }
 
 impl Ship {
-    pub fn new(id: String, positions: Vec<(u8, u8)>) -> Self {
+    pub fn new(id: String, positions: Vec<(u16, u16)>) -> Self {
         let hits = vec![false; positions.len()];
         Self { id, positions, hits }
     }
 
     #[allow(dead_code)]
-    pub fn is_hit_at(&self, x: u8, y: u8) -> bool {
+    pub fn is_hit_at(&self, x: u16, y: u16) -> bool {
         self.positions.iter().position(|&pos| pos == (x, y))
             .map(|idx| self.hits[idx])
             .unwrap_or(false)
     }
 
     #[allow(dead_code)]
-    pub fn register_hit(&mut self, x: u8, y: u8) -> bool {
+    pub fn register_hit(&mut self, x: u16, y: u16) -> bool {
         if let Some(idx) = self.positions.iter().position(|&pos| pos == (x, y)) {
             self.hits[idx] = true;
             true
@@ -37,32 +53,244 @@ impl Ship {
     }
 }
 
+/// A kind of ship every fleet must contain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShipClass {
+    pub name: String,
+    /// Number of cells a ship of this class occupies
+    pub size: usize,
+    /// Number of ships of this class in each fleet
+    #[serde(default = "default_class_count")]
+    pub count: usize,
+}
+
+fn default_class_count() -> usize {
+    1
+}
+
+impl ShipClass {
+    pub fn new(name: &str, size: usize, count: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            size,
+            count,
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("{} {} ({} cells)", self.count, self.name, self.size)
+    }
+}
+
+/// The fleet every player of a game must deploy, and how it may be placed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FleetSpec {
+    pub classes: Vec<ShipClass>,
+    /// Whether ships may be placed along a diagonal
+    #[serde(default)]
+    pub allow_diagonal: bool,
+    /// Whether ships of the same fleet may share cells
+    #[serde(default)]
+    pub allow_overlap: bool,
+}
+
+impl Default for FleetSpec {
+    fn default() -> Self {
+        Self::new(vec![
+            ShipClass::new("Carrier", 4, 1),
+            ShipClass::new("Cruiser", 3, 1),
+            ShipClass::new("Submarine", 2, 1),
+            ShipClass::new("Destroyer", 1, 1),
+        ])
+    }
+}
+
+impl FleetSpec {
+    pub fn new(classes: Vec<ShipClass>) -> Self {
+        Self {
+            classes,
+            allow_diagonal: false,
+            allow_overlap: false,
+        }
+    }
+
+    /// Total number of ships in a fleet
+    pub fn total_ships(&self) -> usize {
+        self.classes.iter().map(|class| class.count).sum()
+    }
+
+    /// Total number of cells occupied by a fleet
+    pub fn total_cells(&self) -> usize {
+        self.classes.iter().map(|class| class.size * class.count).sum()
+    }
+
+    /// Check that the spec itself describes a fleet that fits on the map
+    pub fn validate_spec(&self, map: &GameMap) -> Result<(), String> {
+        if self.total_ships() == 0 {
+            return Err("Fleet must contain at least one ship".to_string());
+        }
+        let longest = map.width.max(map.height) as usize;
+        for class in &self.classes {
+            if class.size == 0 || class.size > longest {
+                return Err(format!(
+                    "Ship class '{}' of size {} does not fit on a {}",
+                    class.name, class.size, map.describe()
+                ));
+            }
+        }
+        if !self.allow_overlap && self.total_cells() > map.open_cells() {
+            return Err(format!(
+                "Fleet needs {} cells but the map has {} open cells",
+                self.total_cells(), map.open_cells()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate a player's ships against this spec
+    pub fn validate_fleet(&self, ships: &[Ship], map: &GameMap) -> Result<(), String> {
+        if ships.len() != self.total_ships() {
+            return Err(format!("Fleet must contain exactly {} ships", self.total_ships()));
+        }
+
+        // Assign every ship to a class of matching size, preferring a class named like the ship
+        let mut assigned = vec![0usize; self.classes.len()];
+        let mut ship_classes = Vec::with_capacity(ships.len());
+        for ship in ships {
+            let class_idx = self.classify(ship, &assigned).ok_or_else(|| format!(
+                "Ship '{}' has {} cells, which matches no ship class (fleet must contain: {})",
+                ship.id,
+                ship.positions.len(),
+                self.describe()
+            ))?;
+            assigned[class_idx] += 1;
+            ship_classes.push(class_idx);
+        }
+
+        for (class, &count) in self.classes.iter().zip(&assigned) {
+            if count != class.count {
+                return Err(format!(
+                    "Fleet has {} {} ({} cells) but needs {} (fleet must contain: {})",
+                    count, class.name, class.size, class.count, self.describe()
+                ));
+            }
+        }
+
+        let mut occupied: HashMap<(u16, u16), &str> = HashMap::new();
+        for (ship, &class_idx) in ships.iter().zip(&ship_classes) {
+            let class = &self.classes[class_idx];
+
+            if let Some(&(x, y)) = ship.positions.iter().find(|&&cell| !map.contains(cell)) {
+                return Err(format!("{} '{}' is out of bounds at ({}, {})", class.name, ship.id, x, y));
+            }
+            if let Some(&(x, y)) = ship.positions.iter().find(|&&cell| map.is_blocked(cell)) {
+                return Err(format!("{} '{}' is on land at ({}, {})", class.name, ship.id, x, y));
+            }
+
+            if !self.is_valid_placement(&ship.positions) {
+                let directions = if self.allow_diagonal {
+                    "horizontally, vertically or diagonally"
+                } else {
+                    "horizontally or vertically"
+                };
+                return Err(format!(
+                    "{} '{}' must be placed {} in a continuous line",
+                    class.name, ship.id, directions
+                ));
+            }
+
+            for &pos in &ship.positions {
+                if let Some(other) = occupied.insert(pos, &ship.id) {
+                    if other == ship.id || !self.allow_overlap {
+                        return Err(format!(
+                            "{} '{}' overlaps '{}' at ({}, {})",
+                            class.name, ship.id, other, pos.0, pos.1
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn describe(&self) -> String {
+        self.classes.iter()
+            .map(|class| class.describe())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn classify(&self, ship: &Ship, assigned: &[usize]) -> Option<usize> {
+        let id = ship.id.to_lowercase();
+        let candidates: Vec<usize> = (0..self.classes.len())
+            .filter(|&i| self.classes[i].size == ship.positions.len())
+            .collect();
+
+        candidates.iter()
+            .copied()
+            .find(|&i| id.starts_with(&self.classes[i].name.to_lowercase()))
+            .or_else(|| candidates.iter().copied().find(|&i| assigned[i] < self.classes[i].count))
+            .or_else(|| candidates.first().copied())
+    }
+
+    /// Check that positions form a continuous line in an allowed direction
+    fn is_valid_placement(&self, positions: &[(u16, u16)]) -> bool {
+        if positions.is_empty() {
+            return false;
+        }
+        if positions.len() == 1 {
+            return true; // Single cell is always valid
+        }
+
+        let mut sorted_positions = positions.to_vec();
+        sorted_positions.sort_unstable();
+
+        let (x0, y0) = (sorted_positions[0].0 as i16, sorted_positions[0].1 as i16);
+        let (x1, y1) = (sorted_positions[1].0 as i16, sorted_positions[1].1 as i16);
+        let step = (x1 - x0, y1 - y0);
+
+        let allowed = match step {
+            (0, 1) | (1, 0) => true,
+            (1, 1) | (1, -1) => self.allow_diagonal,
+            _ => false,
+        };
+        if !allowed {
+            return false;
+        }
+
+        sorted_positions.iter()
+            .enumerate()
+            .all(|(i, &(x, y))| {
+                x as i16 == x0 + step.0 * i as i16 && y as i16 == y0 + step.1 * i as i16
+            })
+    }
+}
+
 /// Represents the game grid
 #[derive(Debug, Clone)]
 pub struct Grid {
-    pub size: u8,
+    pub map: GameMap,
     // Maps cell coordinates to list of player IDs who have ships there
-    pub cells: HashMap<(u8, u8), Vec<String>>,
+    pub cells: HashMap<(u16, u16), Vec<String>>,
 }
 
 impl Grid {
-    pub fn new(size: u8) -> Self {
+    pub fn new(map: GameMap) -> Self {
         Self {
-            size,
+            map,
             cells: HashMap::new(),
         }
     }
 
-    pub fn place_ship(&mut self, player_id: &str, positions: &[(u8, u8)]) -> Result<(), String> {
-        for &(x, y) in positions {
-            if x >= self.size || y >= self.size {
-                return Err(format!("Position ({}, {}) is out of bounds", x, y));
-            }
+    pub fn place_ship(&mut self, player_id: &str, positions: &[(u16, u16)]) -> Result<(), String> {
+        for &cell in positions {
+            self.map.check_cell(cell).map_err(|e| format!("Position {}", e))?;
         }
 
         for &pos in positions {
             self.cells.entry(pos)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(player_id.to_string());
         }
 
@@ -70,7 +298,7 @@ impl Grid {
     }
 
     #[allow(dead_code)]
-    pub fn get_players_at(&self, x: u8, y: u8) -> Vec<String> {
+    pub fn get_players_at(&self, x: u16, y: u16) -> Vec<String> {
         self.cells.get(&(x, y))
             .cloned()
             .unwrap_or_default()
@@ -88,61 +316,1007 @@ pub struct Player {
     #[allow(dead_code)]
     pub salt: String,
     /// Local log of shots fired by this player (shot availability is enforced on-chain)
-    pub shots_fired: Vec<(u8, u8)>,
+    pub shots_fired: Vec<(u16, u16)>,
+    /// Block at which the player forfeited by missing a report deadline
+    pub forfeited_at: Option<u64>,
+    /// Cells in the game's fleet spec; confirmed hits on this many distinct cells defeat the player
+    pub fleet_cells: usize,
+    /// Ships in the game's fleet spec; declaring them all sunk also defeats the player
+    pub fleet_ships: usize,
+    /// Ships the player declared sunk in mined blocks
+    pub ships_sunk: usize,
+    /// Distinct cells confirmed as hits on this player
+    pub hit_cells: Vec<(u16, u16)>,
+    /// Block at which the player's defeat declaration was mined
+    pub declared_defeat_at: Option<u64>,
+    /// Merkle root of per-ship commitments, needed to declare ships sunk
+    pub ship_root: Option<String>,
+    /// Full board revealed on-chain (after the game or with a defeat declaration)
+    pub revealed_board: Option<RevealedBoard>,
+    /// Commitments replaced by mined ship moves, oldest first
+    pub board_history: Vec<BoardEpoch>,
+    /// Boards behind `board_history`, once revealed after the game
+    pub revealed_history: Vec<RevealedBoard>,
+    /// Ship move created on this node and waiting to be mined
+    pub planned_move: Option<PlannedMove>,
+    /// Team declared at registration (None for players fighting alone)
+    pub team: Option<String>,
 }
 
 impl Player {
     pub fn new(id: String, ships: Vec<Ship>, board_commitment: String, salt: String) -> Self {
+        let fleet_cells = ships.iter().map(|ship| ship.positions.len()).sum();
+        let fleet_ships = ships.len();
         Self {
             id,
             ships,
             board_commitment,
             salt,
             shots_fired: Vec::new(),
+            forfeited_at: None,
+            fleet_cells,
+            fleet_ships,
+            ships_sunk: 0,
+            hit_cells: Vec::new(),
+            declared_defeat_at: None,
+            ship_root: None,
+            revealed_board: None,
+            board_history: Vec::new(),
+            revealed_history: Vec::new(),
+            planned_move: None,
+            team: None,
         }
     }
 
-    #[allow(dead_code)]
-    pub fn check_hit(&mut self, x: u8, y: u8) -> bool {
+    /// Register a hit on the local copy of the fleet, counting it if a ship was there
+    pub fn check_hit(&mut self, x: u16, y: u16) -> bool {
         for ship in &mut self.ships {
             if ship.register_hit(x, y) {
+                self.count_hit(x, y);
                 return true;
             }
         }
         false
     }
 
+    /// Count a hit confirmed on-chain, whether or not this node knows the player's ships
+    pub fn record_hit(&mut self, x: u16, y: u16) {
+        for ship in &mut self.ships {
+            ship.register_hit(x, y);
+        }
+        self.count_hit(x, y);
+    }
+
+    /// Drop a claimed hit that a challenge showed to be a miss
+    pub fn clear_hit(&mut self, x: u16, y: u16) {
+        self.hit_cells.retain(|&cell| cell != (x, y));
+    }
+
+    fn count_hit(&mut self, x: u16, y: u16) {
+        if !self.hit_cells.contains(&(x, y)) {
+            self.hit_cells.push((x, y));
+        }
+    }
+
+    /// Defeated once every fleet cell has a confirmed hit or every ship was declared sunk,
+    /// or after forfeiting or declaring defeat
+    ///
+    /// Ships sharing cells under `allow_overlap` cover fewer cells than the spec counts,
+    /// so such a fleet falls through its sunk declarations instead.
     pub fn is_defeated(&self) -> bool {
-        self.ships.iter().all(|ship| ship.is_sunk())
+        self.forfeited_at.is_some()
+            || self.declared_defeat_at.is_some()
+            || self.hit_cells.len() >= self.fleet_cells
+            || self.ships_sunk >= self.fleet_ships
     }
 
     #[allow(dead_code)]
-    pub fn get_all_ship_positions(&self) -> Vec<(u8, u8)> {
+    pub fn get_all_ship_positions(&self) -> Vec<(u16, u16)> {
         self.ships.iter()
             .flat_map(|ship| ship.positions.clone())
             .collect()
     }
+
+    /// Whether this node knows the ships behind the player's current board commitment
+    ///
+    /// After a ship move mined from another node, only that node knows the new board.
+    pub fn knows_board(&self) -> bool {
+        verify_commitment(&self.board_commitment, &self.get_all_ship_positions(), &self.salt)
+    }
+
+    /// Board commitment that answers shots mined in the given block
+    pub fn commitment_at(&self, block_index: u64) -> &str {
+        self.board_history.iter()
+            .find(|epoch| block_index <= epoch.until_block)
+            .map_or(&self.board_commitment, |epoch| &epoch.commitment)
+    }
+
+    /// Switch to the board committed by a mined ship move
+    pub fn apply_move(&mut self, proof: &MoveProof, block_index: u64) {
+        let old = std::mem::replace(&mut self.board_commitment, proof.new_commitment.clone());
+        self.board_history.push(BoardEpoch {
+            commitment: old,
+            until_block: block_index,
+            move_proof: Some(proof.clone()),
+        });
+
+        // Only the node that planned the move knows the new board
+        if let Some(planned) = self.planned_move.take() {
+            if planned.commitment == self.board_commitment {
+                self.ships = planned.ships;
+                self.salt = planned.salt;
+            }
+        }
+    }
+
+    /// Whether every board the player held has been revealed
+    pub fn has_revealed(&self) -> bool {
+        self.revealed_board.is_some() && self.revealed_history.len() == self.board_history.len()
+    }
+
+    /// Revealed board that answered shots mined in the given block
+    pub fn revealed_board_at(&self, block_index: u64) -> Option<&RevealedBoard> {
+        match self.board_history.iter().position(|epoch| block_index <= epoch.until_block) {
+            Some(i) => self.revealed_history.get(i),
+            None => self.revealed_board.as_ref(),
+        }
+    }
+}
+
+/// A ship publicly declared sunk, with its cells proven against the owner's fleet root
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SunkShip {
+    pub player_id: String,
+    /// Ship class name from the fleet spec
+    pub ship_name: String,
+    pub positions: Vec<(u16, u16)>,
+    /// Block in which the declaration was mined
+    pub declared_in_block: u64,
+    /// Shot whose confirmed hit was the last one on the ship
+    pub sunk_by_shot: Option<String>,
+}
+
+/// State of a single game hosted on the chain
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub id: String,
+    pub config: GameConfig,
+    pub grid: Grid,
+    pub players: HashMap<String, Player>,
+    pub round: u32,
+    /// Players in the order they take their turns this round (turn-based games only)
+    pub turn_order: Vec<String>,
+    /// Index into `turn_order` of the player whose turn it is
+    pub turn: usize,
+    pub lifecycle: GameLifecycle,
+    /// Hit/miss reports owed for mined shots, in the order the shots were mined
+    pub obligations: Vec<ReportObligation>,
+    /// Ships declared sunk, in the order the declarations were mined
+    pub sunk_ships: Vec<SunkShip>,
+    /// Fraud challenges to mined reports, in the order they were mined
+    pub challenges: Vec<ReportChallenge>,
+    /// Shot swap offers, in the order they were mined
+    pub swaps: Vec<SwapOffer>,
+    /// Cells each player aimed a mined (non-sonar) shot at
+    pub targeted: HashMap<String, HashSet<(u16, u16)>>,
+}
+
+impl Game {
+    pub fn new(id: String, config: GameConfig) -> Self {
+        Self {
+            id,
+            grid: Grid::new(config.map()),
+            config,
+            players: HashMap::new(),
+            round: 0,
+            turn_order: Vec::new(),
+            turn: 0,
+            lifecycle: GameLifecycle::new(),
+            obligations: Vec::new(),
+            sunk_ships: Vec::new(),
+            challenges: Vec::new(),
+            swaps: Vec::new(),
+            targeted: HashMap::new(),
+        }
+    }
+
+    /// Return an error if the current phase does not allow the given action
+    pub fn check_action(&self, action: GameAction) -> Result<(), String> {
+        self.lifecycle.check(action, &self.config.phases)
+    }
+
+    /// Check a player may mine a block paid by this game
+    ///
+    /// Defeated players may mine again once the game is over so reveals get mined.
+    pub fn check_miner(&self, player_id: &str) -> Result<(), String> {
+        if !self.players.contains_key(player_id) {
+            return Err("Player not found".to_string());
+        }
+        if self.is_player_defeated(player_id) && self.lifecycle.phase != GamePhase::GameOver {
+            return Err("Defeated players cannot mine".to_string());
+        }
+        self.check_action(GameAction::Mine)?;
+        if self.config.turns.is_turn_based() {
+            return Err(format!("Game {} is turn-based; shots are granted each round", self.id));
+        }
+        Ok(())
+    }
+
+    /// Validate a fleet and add the player to the game
+    pub fn add_player(
+        &mut self,
+        player_id: String,
+        ships: Vec<Ship>,
+        board_commitment: String,
+        salt: String,
+    ) -> Result<(), String> {
+        self.check_action(GameAction::Register)?;
+
+        let rules = &self.config.phases;
+        if self.players.contains_key(&player_id) {
+            return Err(format!("Player {} is already registered", player_id));
+        }
+        if rules.max_players != 0 && self.players.len() >= rules.max_players {
+            return Err(format!("Game is full ({} players)", rules.max_players));
+        }
+
+        // Validate fleet composition and placement against the game's fleet spec
+        self.config.fleet.validate_fleet(&ships, &self.grid.map)?;
+
+        // Verify the commitment matches the ships
+        let all_positions: Vec<(u16, u16)> = ships.iter()
+            .flat_map(|ship| ship.positions.clone())
+            .collect();
+
+        if !crate::crypto::verify_commitment(&board_commitment, &all_positions, &salt) {
+            return Err("Invalid board commitment".to_string());
+        }
+
+        // Place ships on the shared grid
+        for ship in &ships {
+            self.grid.place_ship(&player_id, &ship.positions)?;
+        }
+
+        // Defeat is counted against the spec, which every node knows, not the fleet itself
+        let mut player = Player::new(player_id.clone(), ships, board_commitment, salt);
+        player.fleet_cells = self.config.fleet.total_cells();
+        player.fleet_ships = self.config.fleet.total_ships();
+        self.players.insert(player_id, player);
+
+        Ok(())
+    }
+
+    /// Check if a player is defeated
+    pub fn is_player_defeated(&self, player_id: &str) -> bool {
+        self.players.get(player_id)
+            .map(|p| p.is_defeated())
+            .unwrap_or(false)
+    }
+
+    /// Get all active players
+    pub fn get_active_players(&self) -> Vec<String> {
+        self.players.iter()
+            .filter(|(_, p)| !p.is_defeated())
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Registered players other than the given one, sorted
+    fn other_players(&self, player_id: &str) -> Vec<String> {
+        let mut player_ids: Vec<String> = self.players.keys()
+            .filter(|id| id.as_str() != player_id)
+            .cloned()
+            .collect();
+        player_ids.sort();
+        player_ids
+    }
+
+    /// Whether both players declared the same team
+    pub fn are_teammates(&self, a: &str, b: &str) -> bool {
+        let team = |id: &str| self.players.get(id).and_then(|p| p.team.as_ref());
+        matches!((team(a), team(b)), (Some(x), Some(y)) if x == y)
+    }
+
+    /// Players a shot by `shooter` can hit: everyone else, minus teammates unless friendly fire
+    /// is on, and the shooter themselves when self-fire is allowed
+    fn targets_of(&self, shooter: &str) -> Vec<String> {
+        let mut targets: Vec<String> = self.other_players(shooter)
+            .into_iter()
+            .filter(|id| self.config.teams.friendly_fire || !self.are_teammates(shooter, id))
+            .collect();
+        if self.config.shots.self_fire == SelfFire::Allow && self.players.contains_key(shooter) {
+            targets.push(shooter.to_string());
+            targets.sort();
+        }
+        targets
+    }
+
+    /// Start the next round, fixing the turn order of the active players from a beacon
+    pub fn start_round(&mut self, beacon: &Beacon) {
+        self.round += 1;
+        self.turn_order = self.config.turns.turn_order(&self.get_active_players(), beacon);
+        self.turn = 0;
+    }
+
+    /// Player whose turn it is, skipping players defeated since the round started
+    pub fn current_turn(&self) -> Option<&str> {
+        self.turn_order.iter()
+            .skip(self.turn)
+            .find(|id| !self.is_player_defeated(id))
+            .map(String::as_str)
+    }
+
+    /// Check it is the player's turn (always true in games without turns)
+    pub fn check_turn(&self, player_id: &str) -> Result<(), String> {
+        if !self.config.turns.is_turn_based() {
+            return Ok(());
+        }
+        match self.current_turn() {
+            Some(current) if current == player_id => Ok(()),
+            Some(current) => Err(format!("It is {}'s turn", current)),
+            None => Err(format!("No turns are left in round {}", self.round)),
+        }
+    }
+
+    /// Pass the turn on to the next player in the order
+    pub fn end_turn(&mut self) {
+        if let Some(current) = self.current_turn() {
+            if let Some(idx) = self.turn_order.iter().position(|id| id == current) {
+                self.turn = idx + 1;
+            }
+        }
+    }
+
+    /// Check a player may join the given team
+    pub fn check_team(&self, team: &str) -> Result<(), String> {
+        if team.is_empty() {
+            return Err("Team name must not be empty".to_string());
+        }
+        let max = self.config.teams.max_team_size;
+        if max != 0 && self.team_members(team).len() >= max {
+            return Err(format!("Team {} is full ({} players)", team, max));
+        }
+        Ok(())
+    }
+
+    /// Players registered in a team, in ID order
+    pub fn team_members(&self, team: &str) -> Vec<String> {
+        let mut members: Vec<String> = self.players.iter()
+            .filter(|(_, p)| p.team.as_deref() == Some(team))
+            .map(|(id, _)| id.clone())
+            .collect();
+        members.sort();
+        members
+    }
+
+    /// Every team in the game with its players
+    pub fn teams(&self) -> BTreeMap<String, Vec<String>> {
+        let mut teams = BTreeMap::new();
+        for team in self.players.values().filter_map(|p| p.team.as_ref()) {
+            teams.entry(team.clone()).or_insert_with(|| self.team_members(team));
+        }
+        teams
+    }
+
+    /// Account whose shot UTXOs the player mines into and spends: their team's pool when shots are shared
+    pub fn shot_owner(&self, player_id: &str) -> String {
+        match self.players.get(player_id).and_then(|p| p.team.as_ref()) {
+            Some(team) if self.config.teams.shared_shots => team_account(team),
+            _ => player_id.to_string(),
+        }
+    }
+
+    /// Check a shot's target is open water on the map and, if repeats are banned, not aimed at before
+    ///
+    /// `earlier` holds the player's targets not yet recorded in the game: their
+    /// pending shots, or shots earlier in the block being validated.
+    pub fn check_target(&self, shooter: &str, target: (u16, u16), weapon: Weapon, earlier: &[(u16, u16)]) -> Result<(), String> {
+        self.grid.map.check_cell(target).map_err(|e| format!("Target {}", e))?;
+        if weapon.is_sonar() || !self.config.shots.no_repeat_targets {
+            return Ok(());
+        }
+        let fired = self.targeted.get(shooter).is_some_and(|cells| cells.contains(&target));
+        if fired || earlier.contains(&target) {
+            return Err(format!("{} already fired at ({}, {})", shooter, target.0, target.1));
+        }
+        Ok(())
+    }
+
+    /// Check a shot fired on this node against every shot rule of the game
+    ///
+    /// Shots relayed by peers and mined in blocks only go through `check_target`:
+    /// self-fire depends on fleets that never reach the chain, so it cannot decide
+    /// whether a block is valid.
+    pub fn check_shot(&self, shooter: &str, target: (u16, u16), weapon: Weapon, earlier: &[(u16, u16)]) -> Result<(), String> {
+        self.check_target(shooter, target, weapon, earlier)?;
+        self.check_self_fire(shooter, target, weapon)
+    }
+
+    /// Check a shot covers none of the shooter's (or their team's) ships when self-fire is forbidden
+    ///
+    /// Only boards this node knows are checked, so the shooter's own node enforces it.
+    pub fn check_self_fire(&self, shooter: &str, target: (u16, u16), weapon: Weapon) -> Result<(), String> {
+        let owners: Vec<&Player> = match self.config.shots.self_fire {
+            SelfFire::Allow => return Ok(()),
+            _ if weapon.is_sonar() => return Ok(()),
+            SelfFire::Forbid => self.players.get(shooter).into_iter().collect(),
+            SelfFire::ForbidTeam => self.players.values()
+                .filter(|p| p.id == shooter || self.are_teammates(shooter, &p.id))
+                .collect(),
+        };
+        let cells = weapon.cells(target, &self.grid.map, &self.config.weapons);
+        for player in owners.into_iter().filter(|player| player.knows_board()) {
+            let positions = player.get_all_ship_positions();
+            if let Some(cell) = cells.iter().find(|cell| positions.contains(cell)) {
+                return Err(format!("Shot at ({}, {}) would cover {}'s ship at ({}, {})", target.0, target.1, player.id, cell.0, cell.1));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remember the target of a mined shot for the repeat rule
+    pub fn record_target(&mut self, shooter: &str, target: (u16, u16), weapon: Weapon) {
+        if !weapon.is_sonar() {
+            self.targeted.entry(shooter.to_string()).or_default().insert(target);
+        }
+    }
+
+    /// Create the report obligations for a mined shot fired with the given weapon
+    pub fn add_weapon_obligations(&mut self, shot_id: &str, shooter: &str, target: (u16, u16), weapon: Weapon, block_index: u64) {
+        if weapon.is_sonar() {
+            for player_id in self.targets_of(shooter) {
+                self.obligations.push(ReportObligation::sonar(
+                    shot_id.to_string(),
+                    shooter.to_string(),
+                    player_id,
+                    target,
+                    Region::around(target, &self.grid.map),
+                    block_index,
+                ));
+            }
+            return;
+        }
+        for cell in weapon.cells(target, &self.grid.map, &self.config.weapons) {
+            self.add_shot_obligations(shot_id, shooter, cell, block_index);
+        }
+    }
+
+    /// Create a report obligation for every player a mined shot can hit
+    pub fn add_shot_obligations(&mut self, shot_id: &str, shooter: &str, shot: (u16, u16), block_index: u64) {
+        for player_id in self.targets_of(shooter) {
+            self.obligations.push(ReportObligation::new(
+                shot_id.to_string(),
+                shooter.to_string(),
+                player_id,
+                shot,
+                block_index,
+            ));
+        }
+    }
+
+    /// Reports a player still owes
+    pub fn open_obligations(&self, player_id: &str) -> Vec<&ReportObligation> {
+        self.obligations.iter()
+            .filter(|o| o.player_id == player_id && o.is_open())
+            .collect()
+    }
+
+    /// Find the open obligation a report answers
+    ///
+    /// Reports without a shot ID answer the oldest open obligation at the reported cell.
+    /// Sonar counts only answer sonar obligations, and hit/miss reports only answer shots.
+    pub fn find_open_obligation(&self, report: &HitReport) -> Option<&ReportObligation> {
+        self.obligations.iter()
+            .filter(|o| o.player_id == report.player_id && o.is_open())
+            .filter(|o| o.sonar == report.count.is_some())
+            .filter(|o| (o.shot_x, o.shot_y) == (report.shot_x, report.shot_y))
+            .find(|o| report.shot_id.is_empty() || o.shot_id == report.shot_id)
+    }
+
+    /// Mark the obligation answered by a mined report as fulfilled
+    pub fn fulfil_obligation(&mut self, report: &HitReport, block_index: u64) -> bool {
+        match self.obligations.iter_mut().find(|o| {
+            o.shot_id == report.shot_id && o.player_id == report.player_id && o.is_open()
+                && (o.shot_x, o.shot_y) == (report.shot_x, report.shot_y)
+                && o.sonar == report.count.is_some()
+        }) {
+            Some(obligation) => {
+                match report.count {
+                    Some(count) => obligation.fulfil_count(count, serde_json::from_slice(&report.proof).ok(), block_index),
+                    None => obligation.fulfil(report.is_hit, block_index),
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check a report's proof against the reporting player's board commitment
+    pub fn verify_report(&self, report: &HitReport) -> Result<(), String> {
+        let player = self.players.get(&report.player_id)
+            .ok_or("Player not found")?;
+
+        let shot = (report.shot_x, report.shot_y);
+        let board_commitment = self.commitment_for_shot(player, &report.shot_id);
+        let is_valid = if let Some(count) = report.count {
+            let proof: CountProof = serde_json::from_slice(&report.proof)
+                .map_err(|_| "Invalid proof format")?;
+            let region = self.obligations.iter()
+                .find(|o| o.shot_id == report.shot_id && o.player_id == report.player_id && o.sonar)
+                .and_then(|o| o.region)
+                .ok_or_else(|| format!("{} owes no sonar count for this shot", report.player_id))?;
+            proof.verify(&region, count, board_commitment)
+        } else {
+            let proof: HitProof = serde_json::from_slice(&report.proof)
+                .map_err(|_| "Invalid proof format")?;
+            if report.is_hit {
+                proof.verify_hit(shot, board_commitment)
+            } else {
+                proof.verify_miss(shot, board_commitment)
+            }
+        };
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err("Invalid proof".to_string())
+        }
+    }
+
+    /// Board commitment the player held when the given shot was mined
+    fn commitment_for_shot<'a>(&self, player: &'a Player, shot_id: &str) -> &'a str {
+        match self.obligations.iter().find(|o| o.shot_id == shot_id && o.player_id == player.id) {
+            Some(obligation) => player.commitment_at(obligation.shot_block),
+            None => &player.board_commitment,
+        }
+    }
+
+    /// Check a report the way the game's reporting mode requires
+    ///
+    /// Optimistic games accept reports without a proof and rely on challenges instead.
+    pub fn accept_report(&self, report: &HitReport) -> Result<(), String> {
+        if self.config.challenges.optimistic {
+            return Ok(());
+        }
+        self.verify_report(report)
+    }
+
+    /// Check that a player may challenge the mined report `reporter` gave for a shot
+    ///
+    /// `cell` picks one cell of an area shot; None challenges the report for the shot's first cell.
+    pub fn check_challenge(&self, challenger: &str, reporter: &str, shot_id: &str, cell: Option<(u16, u16)>) -> Result<&ReportObligation, String> {
+        if !self.config.challenges.optimistic {
+            return Err("Fraud challenges are only available in optimistic report mode".to_string());
+        }
+        if !self.players.contains_key(challenger) {
+            return Err("Player not found".to_string());
+        }
+        if challenger == reporter {
+            return Err("Players cannot challenge their own reports".to_string());
+        }
+        let obligation = self.obligations.iter()
+            .filter(|o| o.shot_id == shot_id && o.player_id == reporter && o.fulfilled_in_block.is_some())
+            .find(|o| cell.is_none() || cell == Some((o.shot_x, o.shot_y)))
+            .ok_or_else(|| format!("Player {} has no mined report for shot {}", reporter, shot_id))?;
+        if obligation.sonar {
+            return Err("Sonar counts cannot be challenged".to_string());
+        }
+        let cell = (obligation.shot_x, obligation.shot_y);
+        if self.challenges.iter().any(|c| c.shot_id == shot_id && c.player_id == reporter && (c.shot_x, c.shot_y) == cell) {
+            return Err("This report was already challenged".to_string());
+        }
+        Ok(obligation)
+    }
+
+    /// Record a mined challenge, opening the reporter's response window
+    pub fn add_challenge(&mut self, id: &str, challenger: &str, reporter: &str, shot_id: &str, cell: (u16, u16), block_index: u64) -> bool {
+        let Ok(obligation) = self.check_challenge(challenger, reporter, shot_id, Some(cell)) else {
+            return false;
+        };
+        let challenge = ReportChallenge {
+            id: id.to_string(),
+            challenger: challenger.to_string(),
+            player_id: reporter.to_string(),
+            shot_id: shot_id.to_string(),
+            shot_x: obligation.shot_x,
+            shot_y: obligation.shot_y,
+            reported_hit: obligation.is_hit.unwrap_or(false),
+            challenged_in_block: block_index,
+            deadline_block: block_index + self.config.challenges.response_blocks,
+            status: ChallengeStatus::Open,
+            revealed_hit: None,
+            resolved_in_block: None,
+        };
+        self.challenges.push(challenge);
+        true
+    }
+
+    /// Check a response to an open challenge, returning whether the opened cell holds a ship
+    pub fn check_challenge_response(
+        &self,
+        challenge_id: &str,
+        player_id: &str,
+        positions: &[(u16, u16)],
+        salt: &str,
+    ) -> Result<bool, String> {
+        let challenge = self.challenges.iter()
+            .find(|c| c.id == challenge_id)
+            .ok_or_else(|| format!("Challenge {} not found", challenge_id))?;
+        if challenge.player_id != player_id {
+            return Err(format!("Challenge {} is not against player {}", challenge_id, player_id));
+        }
+        if !challenge.is_open() {
+            return Err(format!("Challenge {} is already resolved", challenge_id));
+        }
+        let player = self.players.get(player_id)
+            .ok_or("Player not found")?;
+        if !verify_commitment(self.commitment_for_shot(player, &challenge.shot_id), positions, salt) {
+            return Err("Revealed board does not match the commitment".to_string());
+        }
+        Ok(positions.contains(&(challenge.shot_x, challenge.shot_y)))
+    }
+
+    /// Settle a challenge and correct the challenged report if it was overturned
+    pub fn resolve_challenge(&mut self, challenge_id: &str, revealed_hit: Option<bool>, block_index: u64) -> Option<ReportChallenge> {
+        let challenge = self.challenges.iter_mut().find(|c| c.id == challenge_id && c.is_open())?;
+        challenge.resolve(revealed_hit, block_index);
+        let challenge = challenge.clone();
+
+        if challenge.status == ChallengeStatus::Upheld {
+            let corrected = challenge.corrected_hit();
+            if let Some(obligation) = self.obligations.iter_mut().find(|o| {
+                o.shot_id == challenge.shot_id && o.player_id == challenge.player_id
+                    && (o.shot_x, o.shot_y) == (challenge.shot_x, challenge.shot_y)
+            }) {
+                obligation.is_hit = Some(corrected);
+            }
+            if let Some(player) = self.players.get_mut(&challenge.player_id) {
+                if corrected {
+                    player.record_hit(challenge.shot_x, challenge.shot_y);
+                } else {
+                    player.clear_hit(challenge.shot_x, challenge.shot_y);
+                }
+            }
+        }
+        Some(challenge)
+    }
+
+    /// Uphold every open challenge whose response window closed before the given block
+    pub fn expire_challenges(&mut self, block_index: u64) -> Vec<ReportChallenge> {
+        let expired: Vec<String> = self.challenges.iter()
+            .filter(|c| c.is_open() && block_index > c.deadline_block)
+            .map(|c| c.id.clone())
+            .collect();
+        expired.iter()
+            .filter_map(|id| self.resolve_challenge(id, None, block_index))
+            .collect()
+    }
+
+    /// Check that `from` may hand shots to `to`
+    pub fn check_trade(&self, from: &str, to: &str) -> Result<(), String> {
+        if !self.config.trades.enabled {
+            return Err("Shot trading is disabled in this game".to_string());
+        }
+        if !self.players.contains_key(from) {
+            return Err("Player not found".to_string());
+        }
+        if !self.players.contains_key(to) {
+            return Err(format!("Player {} not found", to));
+        }
+        if from == to {
+            return Err("Players cannot trade shots with themselves".to_string());
+        }
+        if self.shot_owner(from) == self.shot_owner(to) {
+            return Err(format!("Players {} and {} already share a shot pool", from, to));
+        }
+        if let Some(defeated) = [from, to].into_iter().find(|id| self.is_player_defeated(id)) {
+            return Err(format!("Player {} is defeated and cannot trade shots", defeated));
+        }
+        Ok(())
+    }
+
+    /// Record a mined swap offer, opening the taker's acceptance window
+    pub fn add_swap(&mut self, id: &str, maker: &str, taker: &str, give: u32, want: u32, block_index: u64) -> bool {
+        if give == 0 || want == 0 || self.check_trade(maker, taker).is_err() {
+            return false;
+        }
+        self.swaps.push(SwapOffer {
+            id: id.to_string(),
+            maker: maker.to_string(),
+            taker: taker.to_string(),
+            give,
+            want,
+            offered_in_block: block_index,
+            deadline_block: block_index + self.config.trades.swap_blocks,
+            status: SwapStatus::Open,
+            resolved_in_block: None,
+        });
+        true
+    }
+
+    /// Check that `taker` may accept a swap offer in the block at `block_index`
+    pub fn check_swap_acceptance(&self, offer_id: &str, taker: &str, block_index: u64) -> Result<&SwapOffer, String> {
+        let offer = self.swaps.iter()
+            .find(|s| s.id == offer_id)
+            .ok_or_else(|| format!("Swap offer {} not found", offer_id))?;
+        if offer.taker != taker {
+            return Err(format!("Swap offer {} is not made to player {}", offer_id, taker));
+        }
+        if !offer.is_open() || block_index > offer.deadline_block {
+            return Err(format!("Swap offer {} is no longer open", offer_id));
+        }
+        self.check_trade(taker, &offer.maker)?;
+        Ok(offer)
+    }
+
+    /// Complete a swap accepted in the block at `block_index`
+    pub fn complete_swap(&mut self, offer_id: &str, taker: &str, block_index: u64) -> Option<SwapOffer> {
+        self.check_swap_acceptance(offer_id, taker, block_index).ok()?;
+        let offer = self.swaps.iter_mut().find(|s| s.id == offer_id)?;
+        offer.resolve(SwapStatus::Completed, block_index);
+        Some(offer.clone())
+    }
+
+    /// Expire every open swap offer whose acceptance window closed before the given block
+    pub fn expire_swaps(&mut self, block_index: u64) -> Vec<SwapOffer> {
+        self.swaps.iter_mut()
+            .filter(|s| s.is_open() && block_index > s.deadline_block)
+            .map(|s| {
+                s.resolve(SwapStatus::Expired, block_index);
+                s.clone()
+            })
+            .collect()
+    }
+
+    /// Check that a sunk declaration reveals a committed ship whose every cell has a confirmed hit
+    pub fn check_sunk_declaration(&self, player_id: &str, ship_name: &str, proof: &ShipProof) -> Result<(), String> {
+        let player = self.players.get(player_id)
+            .ok_or("Player not found")?;
+        let ship_root = player.ship_root.as_ref()
+            .ok_or_else(|| format!("Player {} did not commit to per-ship cells at registration", player_id))?;
+
+        let class = self.config.fleet.classes.iter()
+            .find(|c| c.name.eq_ignore_ascii_case(ship_name))
+            .ok_or_else(|| format!("Fleet has no {} class", ship_name))?;
+        if proof.positions.len() != class.size {
+            return Err(format!(
+                "{} has {} cells but {} were revealed",
+                class.name, class.size, proof.positions.len()
+            ));
+        }
+
+        if !proof.verify(ship_root) {
+            return Err("Ship proof does not match the committed fleet".to_string());
+        }
+
+        if let Some((x, y)) = proof.positions.iter().find(|pos| !player.hit_cells.contains(pos)) {
+            return Err(format!("{} is not sunk: ({}, {}) has no confirmed hit", class.name, x, y));
+        }
+
+        let mut revealed = proof.positions.clone();
+        revealed.sort();
+        let already_declared = self.sunk_ships.iter().any(|sunk| {
+            let mut positions = sunk.positions.clone();
+            positions.sort();
+            sunk.player_id == player_id && positions == revealed
+        });
+        if already_declared {
+            return Err(format!("{} was already declared sunk", class.name));
+        }
+
+        Ok(())
+    }
+
+    /// Record a verified sunk declaration mined in the given block
+    pub fn record_sunk(&mut self, player_id: &str, ship_name: &str, proof: &ShipProof, block_index: u64) {
+        let ship_name = self.config.fleet.classes.iter()
+            .find(|c| c.name.eq_ignore_ascii_case(ship_name))
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| ship_name.to_string());
+
+        // The last confirmed hit on any of the ship's cells sank it
+        let sunk_by_shot = self.obligations.iter()
+            .filter(|o| o.player_id == player_id && o.is_hit == Some(true))
+            .filter(|o| proof.positions.contains(&(o.shot_x, o.shot_y)))
+            .max_by_key(|o| o.fulfilled_in_block.or(o.penalized_in_block))
+            .map(|o| o.shot_id.clone());
+
+        if let Some(player) = self.players.get_mut(player_id) {
+            player.ships_sunk += 1;
+        }
+        self.sunk_ships.push(SunkShip {
+            player_id: player_id.to_string(),
+            ship_name,
+            positions: proof.positions.clone(),
+            declared_in_block: block_index,
+            sunk_by_shot,
+        });
+    }
+
+    /// Check every revealed board against the player's mined reports once the game is over
+    ///
+    /// Returns None while the game is still running.
+    pub fn audit(&self, height: u64) -> Option<CheatAudit> {
+        let outcome = self.lifecycle.outcome.as_ref()?;
+
+        let mut discrepancies = Vec::new();
+        let mut illegal_moves = Vec::new();
+        let mut cheaters = Vec::new();
+        let mut unrevealed = Vec::new();
+        for player_id in &outcome.standings {
+            match self.players.get(player_id).filter(|p| p.has_revealed()) {
+                Some(player) => {
+                    // Each report is checked against the board the player held when the shot was mined
+                    let found: Vec<_> = self.obligations.iter()
+                        .filter(|o| o.player_id == *player_id)
+                        .flat_map(|o| {
+                            match player.revealed_board_at(o.shot_block) {
+                                Some(board) => audit_reports(player_id, board, std::slice::from_ref(o)),
+                                None => Vec::new(),
+                            }
+                        })
+                        .collect();
+                    let moves = self.audit_moves(player);
+                    if !found.is_empty() || !moves.is_empty() {
+                        cheaters.push(player_id.clone());
+                    }
+                    discrepancies.extend(found);
+                    illegal_moves.extend(moves);
+                }
+                None => unrevealed.push(player_id.clone()),
+            }
+        }
+
+        let finalized = height >= outcome.ended_at_block + self.config.phases.reveal_blocks;
+        let flagged: &[String] = if finalized { &unrevealed } else { &[] };
+        let standings = adjust_standings(&outcome.standings, &cheaters, flagged);
+        let winner = outcome.winner.as_ref()
+            .and_then(|_| standings.first())
+            .filter(|id| !cheaters.contains(id) && !flagged.contains(id))
+            .cloned();
+
+        Some(CheatAudit {
+            discrepancies,
+            illegal_moves,
+            cheaters,
+            unrevealed,
+            finalized,
+            standings,
+            winner,
+        })
+    }
+
+    /// Check every board a player revealed follows from the one before by a legal ship move
+    ///
+    /// The move proof mined between them must open to the two boards as well.
+    fn audit_moves(&self, player: &Player) -> Vec<IllegalMove> {
+        let boards: Vec<&RevealedBoard> = player.revealed_history.iter()
+            .chain(player.revealed_board.as_ref())
+            .collect();
+        boards.windows(2)
+            .zip(&player.board_history)
+            .filter_map(|(pair, epoch)| {
+                let (old, new) = (pair[0], pair[1]);
+                let opened = epoch.move_proof.as_ref()
+                    .is_some_and(|proof| proof.opens_to(&old.positions, &new.positions, &new.salt));
+                let hits = self.hits_on(&player.id, epoch.until_block);
+                let max_distance = self.config.relocation.max_distance;
+                let reason = if opened {
+                    check_slide(&old.positions, &new.positions, &hits, max_distance, &self.grid.map).err()?
+                } else {
+                    "The move proof does not open to the revealed boards".to_string()
+                };
+                Some(IllegalMove {
+                    player_id: player.id.clone(),
+                    moved_in_block: epoch.until_block,
+                    reason,
+                })
+            })
+            .collect()
+    }
+
+    /// Cells of a player's fleet hit by shots mined up to the given block
+    ///
+    /// Hit ships never move, so a cell stays hit on every later board.
+    fn hits_on(&self, player_id: &str, block_index: u64) -> Vec<(u16, u16)> {
+        self.obligations.iter()
+            .filter(|o| o.player_id == player_id && !o.sonar && o.shot_block <= block_index && o.is_hit == Some(true))
+            .map(|o| (o.shot_x, o.shot_y))
+            .collect()
+    }
+
+    /// Results of every cell covered by a player's mined shots, in the order they were mined
+    pub fn shot_results(&self, shooter: &str) -> Vec<ShotResult> {
+        let mut results: Vec<ShotResult> = Vec::new();
+        for obligation in self.obligations.iter().filter(|o| o.shooter == shooter) {
+            let same_cell = |r: &ShotResult| r.shot_id == obligation.shot_id && (r.shot_x, r.shot_y) == (obligation.shot_x, obligation.shot_y);
+            match results.iter_mut().rev().find(|r| same_cell(r)) {
+                Some(result) => result.record(obligation),
+                None => {
+                    let mut result = ShotResult::new(obligation);
+                    result.record(obligation);
+                    results.push(result);
+                }
+            }
+        }
+        // A ship sunk by an area shot is listed once, on the first of its cells the shot covered
+        for sunk in &self.sunk_ships {
+            if let Some(result) = results.iter_mut().find(|r| {
+                sunk.sunk_by_shot.as_deref() == Some(r.shot_id.as_str()) && sunk.positions.contains(&(r.shot_x, r.shot_y))
+            }) {
+                result.sunk.push(sunk.clone());
+            }
+        }
+        results
+    }
+
+    /// Close every open obligation whose report deadline passed before the given block
+    ///
+    /// Returns the expired obligations so the caller can apply the penalty.
+    pub fn expire_obligations(&mut self, block_index: u64) -> Vec<ReportObligation> {
+        let rules = &self.config.reports;
+        let mut expired = Vec::new();
+        for obligation in self.obligations.iter_mut().filter(|o| o.is_open()) {
+            match rules.deadline(obligation.shot_block) {
+                Some(deadline) if block_index > deadline => {
+                    obligation.expire(&rules.penalty, block_index);
+                    expired.push(obligation.clone());
+                }
+                _ => {}
+            }
+        }
+        expired
+    }
+
+    /// Apply every phase transition due at the given height
+    pub fn advance_lifecycle(&mut self, height: u64) {
+        self.lifecycle.advance(&self.config.phases, height, self.players.len());
+
+        let mut all_players: Vec<String> = self.players.keys().cloned().collect();
+        all_players.sort();
+        let mut active_players = self.get_active_players();
+        active_players.sort();
+        let teams: HashMap<String, String> = self.players.iter()
+            .filter_map(|(id, p)| p.team.clone().map(|team| (id.clone(), team)))
+            .collect();
+        self.lifecycle.update_standings(&active_players, &all_players, &teams, height);
+    }
 }
 
 /// Hit report with proof
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HitReport {
     pub player_id: String,
-    pub shot_x: u8,
-    pub shot_y: u8,
+    /// Shot being answered (empty to answer the oldest open shot at the reported cell)
+    #[serde(default)]
+    pub shot_id: String,
+    pub shot_x: u16,
+    pub shot_y: u16,
     pub is_hit: bool,
     pub proof: Vec<u8>, // ZK proof data
+    /// Ship cells in the scanned area when answering a sonar (None for hit/miss reports)
+    #[serde(default)]
+    pub count: Option<u8>,
 }
 
 impl HitReport {
     #[allow(dead_code)]
-    pub fn new(player_id: String, shot_x: u8, shot_y: u8, is_hit: bool, proof: Vec<u8>) -> Self {
+    pub fn new(player_id: String, shot_x: u16, shot_y: u16, is_hit: bool, proof: Vec<u8>) -> Self {
         Self {
             player_id,
+            shot_id: String::new(),
             shot_x,
             shot_y,
             is_hit,
             proof,
+            count: None,
         }
     }
+
+    /// Report how many ship cells lie in the area scanned by a sonar centred on the given cell
+    pub fn sonar(player_id: String, center_x: u16, center_y: u16, count: u8, proof: Vec<u8>) -> Self {
+        let mut report = Self::new(player_id, center_x, center_y, false, proof);
+        report.count = Some(count);
+        report
+    }
 }
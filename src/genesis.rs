@@ -0,0 +1,239 @@
+use sha2::{Sha256, Digest};
+use serde::{Serialize, Deserialize};
+use std::fs;
+use std::path::Path;
+use crate::beacon::BeaconRules;
+use crate::challenges::ChallengeRules;
+use crate::game::FleetSpec;
+use crate::lifecycle::PhaseRules;
+use crate::map::GameMap;
+use crate::obligations::ReportRules;
+use crate::relocation::RelocationRules;
+use crate::rewards::RewardPolicy;
+use crate::shots::ShotRules;
+use crate::mempool::MempoolLimits;
+use crate::template::BlockLimits;
+use crate::teams::TeamRules;
+use crate::trades::TradeRules;
+use crate::turns::TurnRules;
+use crate::weapons::WeaponRules;
+
+/// Consensus parameters every node on the chain must agree on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsensusParams {
+    /// Mining difficulty (number of leading zeros in a block hash)
+    pub difficulty: usize,
+    /// Leading zeros a mining pool share needs (at most `difficulty`)
+    #[serde(default = "default_share_difficulty")]
+    pub share_difficulty: usize,
+    /// Caps on the transactions a block may hold
+    #[serde(default)]
+    pub limits: BlockLimits,
+    /// Size and time-to-live limits of every node's mempool
+    #[serde(default)]
+    pub mempool: MempoolLimits,
+    /// Reveal window of commit-reveal randomness beacons
+    #[serde(default)]
+    pub beacon: BeaconRules,
+}
+
+fn default_share_difficulty() -> usize {
+    1
+}
+
+/// Rules of the game played on this chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameConfig {
+    /// Width and height of the shared grid when no map is given
+    pub grid_size: u16,
+    /// Board with its own width, height and land cells, replacing the `grid_size` square
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<GameMap>,
+    /// File `map` is loaded from, relative to the genesis file
+    ///
+    /// Only the loaded map is hashed, so nodes may keep the file at different paths.
+    #[serde(default, skip_serializing)]
+    pub map_file: Option<String>,
+    /// Ships every fleet must contain and how they may be placed
+    pub fleet: FleetSpec,
+    /// Rules for moving between lobby, placement, battle and game over
+    #[serde(default)]
+    pub phases: PhaseRules,
+    /// Report deadline and the penalty for missing it
+    #[serde(default)]
+    pub reports: ReportRules,
+    /// Optimistic reporting and the bonds behind fraud challenges
+    #[serde(default)]
+    pub challenges: ChallengeRules,
+    /// Costs and reach of the area weapons
+    #[serde(default)]
+    pub weapons: WeaponRules,
+    /// Which cells shots may aim at
+    #[serde(default)]
+    pub shots: ShotRules,
+    /// Cost and reach of ship moves
+    #[serde(default)]
+    pub relocation: RelocationRules,
+    /// Friendly fire and shared shot pools for teams
+    #[serde(default)]
+    pub teams: TeamRules,
+    /// Shot transfers and swaps between players
+    #[serde(default)]
+    pub trades: TradeRules,
+    /// Mining races or alternating turns
+    #[serde(default)]
+    pub turns: TurnRules,
+    /// Reward policy for this game (None uses the chain's reward policy)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewards: Option<RewardPolicy>,
+}
+
+impl GameConfig {
+    /// Board the game is played on
+    pub fn map(&self) -> GameMap {
+        self.map.clone().unwrap_or_else(|| GameMap::square(self.grid_size))
+    }
+}
+
+/// Genesis specification hashed into block 0
+///
+/// Two nodes can only exchange blocks when their genesis hashes match, which
+/// guarantees they play by the same rules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GenesisConfig {
+    /// Identifier distinguishing independent networks with otherwise equal settings
+    pub chain_id: String,
+    /// Timestamp recorded in the genesis block
+    pub timestamp: i64,
+    pub consensus: ConsensusParams,
+    /// Chain-wide reward policy, also used by pool blocks and games without their own
+    pub rewards: RewardPolicy,
+    pub game: GameConfig,
+}
+
+impl Default for GenesisConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: "fleetchain".to_string(),
+            timestamp: 0,
+            consensus: ConsensusParams {
+                difficulty: 2,
+                share_difficulty: default_share_difficulty(),
+                limits: BlockLimits::default(),
+                mempool: MempoolLimits::default(),
+                beacon: BeaconRules::default(),
+            },
+            rewards: RewardPolicy::default(),
+            game: GameConfig {
+                grid_size: 10,
+                map: None,
+                map_file: None,
+                fleet: FleetSpec::default(),
+                phases: PhaseRules::default(),
+                reports: ReportRules::default(),
+                challenges: ChallengeRules::default(),
+                weapons: WeaponRules::default(),
+                shots: ShotRules::default(),
+                relocation: RelocationRules::default(),
+                teams: TeamRules::default(),
+                trades: TradeRules::default(),
+                turns: TurnRules::default(),
+                rewards: None,
+            },
+        }
+    }
+}
+
+impl GenesisConfig {
+    /// Create a genesis config with the given grid size and difficulty and default everything else
+    pub fn new(grid_size: u16, difficulty: usize) -> Self {
+        let mut config = Self::default();
+        config.game.grid_size = grid_size;
+        config.consensus.difficulty = difficulty;
+        config
+    }
+
+    /// Hash of the canonical JSON encoding of this config
+    pub fn hash(&self) -> String {
+        let data = serde_json::to_string(self).unwrap();
+        let mut hasher = Sha256::new();
+        hasher.update(data.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Check that the config describes a playable game
+    pub fn validate(&self) -> Result<(), String> {
+        if self.chain_id.is_empty() {
+            return Err("Genesis chain_id must not be empty".to_string());
+        }
+        if self.consensus.share_difficulty > self.consensus.difficulty {
+            return Err("Genesis share_difficulty must not exceed difficulty".to_string());
+        }
+        if self.consensus.beacon.reveal_blocks == 0 {
+            return Err("Genesis beacon reveal_blocks must be positive".to_string());
+        }
+        self.rewards.validate()?;
+        if let Some(rewards) = &self.game.rewards {
+            rewards.validate()?;
+        }
+        if self.game.grid_size == 0 {
+            return Err("Genesis grid_size must be positive".to_string());
+        }
+        match (&self.game.map, &self.game.map_file) {
+            (Some(map), _) => map.validate()?,
+            (None, Some(file)) => return Err(format!("Genesis map file {} has not been loaded", file)),
+            (None, None) => {}
+        }
+        let phases = &self.game.phases;
+        if phases.max_players != 0 && phases.max_players < phases.min_players {
+            return Err("Genesis max_players must not be below min_players".to_string());
+        }
+        let challenges = &self.game.challenges;
+        if challenges.optimistic && (challenges.bond_shots == 0 || challenges.response_blocks == 0) {
+            return Err("Genesis optimistic reporting needs a positive bond_shots and response_blocks".to_string());
+        }
+        let weapons = &self.game.weapons;
+        if weapons.bomb_cost == 0 || weapons.torpedo_cost == 0 || weapons.sonar_cost == 0 {
+            return Err("Genesis weapon costs must be positive".to_string());
+        }
+        if weapons.torpedo_length == 0 {
+            return Err("Genesis torpedo_length must be positive".to_string());
+        }
+        if self.game.relocation.move_cost == 0 || self.game.relocation.max_distance == 0 {
+            return Err("Genesis ship moves need a positive move_cost and max_distance".to_string());
+        }
+        if self.game.trades.enabled && self.game.trades.swap_blocks == 0 {
+            return Err("Genesis trades need a positive swap_blocks".to_string());
+        }
+        self.game.fleet.validate_spec(&self.game.map())
+    }
+
+    /// Load a genesis config from a JSON file, along with the map file it references
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read genesis file: {}", e))?;
+
+        let mut config: GenesisConfig = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse genesis file: {}", e))?;
+
+        if let Some(file) = &config.game.map_file {
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            config.game.map = Some(GameMap::load_from_file(dir.join(file))?);
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Save the genesis config to a JSON file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize genesis config: {}", e))?;
+
+        fs::write(path, json)
+            .map_err(|e| format!("Failed to write genesis file: {}", e))?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,25 @@
 pub mod blockchain;
+pub mod genesis;
 pub mod game;
+pub mod map;
+pub mod lifecycle;
+pub mod obligations;
+pub mod challenges;
+pub mod weapons;
+pub mod shots;
+pub mod turns;
+pub mod beacon;
+pub mod relocation;
+pub mod teams;
+pub mod pool;
+pub mod coinbase;
+pub mod rewards;
+pub mod trades;
+pub mod template;
+pub mod mempool;
+pub mod audit;
 pub mod crypto;
+pub mod setup;
 pub mod coordinator;
 pub mod network;
 pub mod api;
@@ -0,0 +1,221 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::fmt;
+use crate::teams::team_account;
+
+/// Phases a game moves through, in order
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GamePhase {
+    /// Waiting for enough players to register
+    Lobby,
+    /// Enough players joined; fleets can still be placed while blocks are mined
+    Placement,
+    /// Shots can be fired
+    Battle,
+    /// At most one player is left standing; boards are revealed and audited
+    GameOver,
+}
+
+impl fmt::Display for GamePhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            GamePhase::Lobby => "Lobby",
+            GamePhase::Placement => "Placement",
+            GamePhase::Battle => "Battle",
+            GamePhase::GameOver => "GameOver",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Player actions that are only allowed in some phases
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameAction {
+    Register,
+    Mine,
+    Fire,
+    Report,
+    Reveal,
+    Challenge,
+    Respond,
+    Move,
+    Trade,
+}
+
+impl fmt::Display for GameAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            GameAction::Register => "register players",
+            GameAction::Mine => "mine",
+            GameAction::Fire => "fire shots",
+            GameAction::Report => "report hits",
+            GameAction::Reveal => "reveal boards",
+            GameAction::Challenge => "challenge reports",
+            GameAction::Respond => "answer challenges",
+            GameAction::Move => "move ships",
+            GameAction::Trade => "trade shots",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Rules driving the phase transitions of a game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PhaseRules {
+    /// Players needed before the lobby closes
+    pub min_players: usize,
+    /// Maximum number of players (0 means unlimited)
+    pub max_players: usize,
+    /// Blocks the placement window stays open before battle starts
+    pub placement_blocks: u64,
+    /// Whether players may still register once the battle has started
+    pub allow_late_join: bool,
+    /// Blocks after the game ends in which players must reveal their boards for the audit
+    #[serde(default = "default_reveal_blocks")]
+    pub reveal_blocks: u64,
+}
+
+fn default_reveal_blocks() -> u64 {
+    10
+}
+
+impl Default for PhaseRules {
+    fn default() -> Self {
+        Self {
+            min_players: 1,
+            max_players: 0,
+            placement_blocks: 0,
+            allow_late_join: true,
+            reveal_blocks: default_reveal_blocks(),
+        }
+    }
+}
+
+/// Final result of a finished game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameOutcome {
+    /// Last player standing (None if everyone was eliminated at once)
+    pub winner: Option<String>,
+    /// Team whose players are the last left standing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub winning_team: Option<String>,
+    /// Player IDs from first to last place
+    pub standings: Vec<String>,
+    /// Block height at which the game ended
+    pub ended_at_block: u64,
+}
+
+/// Current phase of a game and how it got there
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameLifecycle {
+    pub phase: GamePhase,
+    /// Block height at which the current phase started
+    pub phase_started_at: u64,
+    /// Players in the order they were eliminated, with the block height
+    pub eliminations: Vec<(String, u64)>,
+    pub outcome: Option<GameOutcome>,
+}
+
+impl Default for GameLifecycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameLifecycle {
+    pub fn new() -> Self {
+        Self {
+            phase: GamePhase::Lobby,
+            phase_started_at: 0,
+            eliminations: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// Whether the current phase allows the given action
+    pub fn allows(&self, action: GameAction, rules: &PhaseRules) -> bool {
+        match (self.phase, action) {
+            (GamePhase::Lobby, GameAction::Register) => true,
+            (GamePhase::Placement, GameAction::Register | GameAction::Mine | GameAction::Trade) => true,
+            (GamePhase::Battle, GameAction::Register) => rules.allow_late_join,
+            (GamePhase::Battle, GameAction::Mine | GameAction::Fire | GameAction::Report) => true,
+            (GamePhase::Battle, GameAction::Challenge | GameAction::Respond | GameAction::Move | GameAction::Trade) => true,
+            // Blocks are still mined after the game so board reveals and late responses reach the chain
+            (GamePhase::GameOver, GameAction::Mine | GameAction::Reveal | GameAction::Respond) => true,
+            _ => false,
+        }
+    }
+
+    /// Return an error if the current phase does not allow the given action
+    pub fn check(&self, action: GameAction, rules: &PhaseRules) -> Result<(), String> {
+        if self.allows(action, rules) {
+            Ok(())
+        } else {
+            Err(format!("Cannot {} during the {} phase", action, self.phase))
+        }
+    }
+
+    /// Advance through every transition whose condition holds at the given height and player count
+    pub fn advance(&mut self, rules: &PhaseRules, height: u64, player_count: usize) {
+        if self.phase == GamePhase::Lobby && player_count >= rules.min_players.max(1) {
+            self.enter(GamePhase::Placement, height);
+        }
+        if self.phase == GamePhase::Placement && height >= self.phase_started_at + rules.placement_blocks {
+            self.enter(GamePhase::Battle, height);
+        }
+    }
+
+    /// Record newly eliminated players and end the game once one side remains
+    ///
+    /// `teams` maps players to their team; players without one fight alone. The
+    /// surviving players of the last side share first place, in ID order.
+    pub fn update_standings(
+        &mut self,
+        active_players: &[String],
+        all_players: &[String],
+        teams: &HashMap<String, String>,
+        height: u64,
+    ) {
+        if self.phase != GamePhase::Battle {
+            return;
+        }
+
+        let mut newly_eliminated: Vec<&String> = all_players.iter()
+            .filter(|id| !active_players.contains(id))
+            .filter(|id| !self.eliminations.iter().any(|(e, _)| e == *id))
+            .collect();
+        newly_eliminated.sort();
+        for id in newly_eliminated {
+            self.eliminations.push((id.clone(), height));
+        }
+
+        let side = |id: &String| teams.get(id).map_or(id.clone(), |team| team_account(team));
+        let count_sides = |players: &[String]| {
+            let mut sides: Vec<String> = players.iter().map(side).collect();
+            sides.sort();
+            sides.dedup();
+            sides.len()
+        };
+        if count_sides(all_players) < 2 || count_sides(active_players) > 1 {
+            return;
+        }
+
+        let winner = active_players.first().cloned();
+        let winning_team = winner.as_ref().and_then(|id| teams.get(id)).cloned();
+        let mut standings: Vec<String> = active_players.to_vec();
+        standings.extend(self.eliminations.iter().rev().map(|(id, _)| id.clone()));
+
+        self.outcome = Some(GameOutcome {
+            winner,
+            winning_team,
+            standings,
+            ended_at_block: height,
+        });
+        self.enter(GamePhase::GameOver, height);
+    }
+
+    fn enter(&mut self, phase: GamePhase, height: u64) {
+        self.phase = phase;
+        self.phase_started_at = height;
+    }
+}
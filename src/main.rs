@@ -1,11 +1,7 @@
-mod blockchain;
-mod game;
-mod crypto;
-mod coordinator;
-mod network;
-mod api;
+use fleetchain::{api, coordinator, crypto, game, genesis, network};
 
 use clap::Parser;
+use genesis::GenesisConfig;
 use network::{NetworkNode, Peer};
 use std::sync::Arc;
 use std::path::PathBuf;
@@ -24,7 +20,7 @@ struct Args {
 
     /// Grid size for the battleship game
     #[arg(short, long, default_value_t = 10)]
-    grid_size: u8,
+    grid_size: u16,
 
     /// Mining difficulty (number of leading zeros)
     #[arg(short, long, default_value_t = 2)]
@@ -41,17 +37,31 @@ struct Args {
     /// Path to blockchain data file (default: ./data/{node_id}_blockchain.json)
     #[arg(long)]
     blockchain_path: Option<String>,
+
+    /// Path to a genesis spec file (overrides --grid-size and --difficulty)
+    #[arg(long)]
+    genesis: Option<String>,
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
+    let genesis = match &args.genesis {
+        Some(path) => GenesisConfig::load_from_file(path).unwrap_or_else(|e| {
+            eprintln!("✗ {}", e);
+            std::process::exit(1);
+        }),
+        None => GenesisConfig::new(args.grid_size, args.difficulty),
+    };
+
     println!("=== FleetChain: Blockchain Battleship ===");
     println!("Node ID: {}", args.node_id);
     println!("Port: {}", args.port);
-    println!("Grid Size: {}x{}", args.grid_size, args.grid_size);
-    println!("Mining Difficulty: {}\n", args.difficulty);
+    println!("Chain ID: {}", genesis.chain_id);
+    let map = genesis.game.map();
+    println!("Grid Size: {}x{} ({} land cells)", map.width, map.height, map.blocked.len());
+    println!("Mining Difficulty: {}\n", genesis.consensus.difficulty);
 
     // Determine blockchain path
     let blockchain_path = if let Some(path) = args.blockchain_path {
@@ -69,13 +79,13 @@ async fn main() {
     println!("Blockchain file: {:?}\n", blockchain_path);
 
     // Create network node with persistence
-    let node = Arc::new(NetworkNode::with_persistence(
+    let node = Arc::new(NetworkNode::from_genesis_with_persistence(
         args.node_id.clone(),
         args.port,
-        args.grid_size,
-        args.difficulty,
+        genesis,
         blockchain_path,
     ));
+    println!("Genesis Hash: {}\n", node.genesis_hash().await);
 
     // Connect to peers
     if !args.peers.is_empty() {
@@ -84,6 +94,13 @@ async fn main() {
             if let Some((host, port_str)) = peer_addr.split_once(':') {
                 if let Ok(port) = port_str.parse::<u16>() {
                     let peer = Peer::new(host.to_string(), port);
+
+                    // Refuse peers that were started from a different genesis
+                    if let Err(e) = node.verify_peer_genesis(&peer).await {
+                        eprintln!("✗ Skipping peer {}: {}", peer.url(), e);
+                        continue;
+                    }
+
                     node.add_peer(peer.clone()).await;
                     println!("✓ Added peer: {}", peer.url());
                     
@@ -116,12 +133,17 @@ async fn main() {
     println!("\nAvailable endpoints:");
     println!("  GET  /api/info           - Node information");
     println!("  GET  /api/stats          - Game statistics");
+    println!("  GET  /api/game           - Game phase and outcome");
     println!("  GET  /api/blockchain     - Full blockchain");
+    println!("  GET  /api/genesis        - Genesis config and hash");
     println!("  GET  /api/peers          - Connected peers");
+    println!("  GET  /api/games          - List games");
+    println!("  POST /api/games          - Create game");
     println!("  POST /api/register       - Register player");
     println!("  POST /api/fire           - Fire shot");
     println!("  POST /api/mine           - Mine for shots");
     println!("  POST /api/shots          - Get shot balance");
+    println!("  (game-specific variants live under /api/games/:game_id/...)");
     println!("  POST /api/peers          - Add peer");
     println!("  POST /api/sync           - Sync blockchain\n");
 
@@ -138,18 +160,28 @@ async fn run_demo(node: Arc<NetworkNode>) {
     demo_game(&mut coordinator);
 }
 
+/// Lay out a fleet matching the spec as vertical ships in every other column
+fn demo_fleet(spec: &game::FleetSpec, first_column: u16) -> Vec<game::Ship> {
+    let mut ships = Vec::new();
+    let mut column = first_column;
+    for class in &spec.classes {
+        for i in 0..class.count {
+            let positions = (0..class.size as u16).map(|y| (column, y)).collect();
+            ships.push(game::Ship::new(format!("{}{}", class.name.to_lowercase(), i), positions));
+            column += 2;
+        }
+    }
+    ships
+}
+
 fn demo_game(game: &mut coordinator::GameCoordinator) {
-    use game::Ship;
     use crypto::{generate_salt, create_commitment};
+    use game::DEFAULT_GAME_ID as GAME;
     // Player 1 setup
     println!("Registering Player 1...");
-    let player1_ships = vec![
-        Ship::new("carrier".to_string(), vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)]),
-        Ship::new("battleship".to_string(), vec![(2, 0), (2, 1), (2, 2), (2, 3)]),
-        Ship::new("destroyer".to_string(), vec![(4, 0), (4, 1), (4, 2)]),
-    ];
+    let player1_ships = demo_fleet(&game.genesis.game.fleet, 0);
 
-    let player1_positions: Vec<(u8, u8)> = player1_ships.iter()
+    let player1_positions: Vec<(u16, u16)> = player1_ships.iter()
         .flat_map(|ship| ship.positions.clone())
         .collect();
 
@@ -157,6 +189,7 @@ fn demo_game(game: &mut coordinator::GameCoordinator) {
     let player1_commitment = create_commitment(&player1_positions, &player1_salt);
 
     match game.register_player(
+        GAME,
         "player1".to_string(),
         player1_ships,
         player1_commitment.clone(),
@@ -168,13 +201,9 @@ fn demo_game(game: &mut coordinator::GameCoordinator) {
 
     // Player 2 setup
     println!("\nRegistering Player 2...");
-    let player2_ships = vec![
-        Ship::new("carrier".to_string(), vec![(5, 5), (5, 6), (5, 7), (5, 8), (5, 9)]),
-        Ship::new("battleship".to_string(), vec![(7, 5), (7, 6), (7, 7), (7, 8)]),
-        Ship::new("destroyer".to_string(), vec![(9, 5), (9, 6), (9, 7)]),
-    ];
+    let player2_ships = demo_fleet(&game.genesis.game.fleet, 1);
 
-    let player2_positions: Vec<(u8, u8)> = player2_ships.iter()
+    let player2_positions: Vec<(u16, u16)> = player2_ships.iter()
         .flat_map(|ship| ship.positions.clone())
         .collect();
 
@@ -182,6 +211,7 @@ fn demo_game(game: &mut coordinator::GameCoordinator) {
     let player2_commitment = create_commitment(&player2_positions, &player2_salt);
 
     match game.register_player(
+        GAME,
         "player2".to_string(),
         player2_ships,
         player2_commitment.clone(),
@@ -192,17 +222,17 @@ fn demo_game(game: &mut coordinator::GameCoordinator) {
     }
 
     // Display initial stats
-    println!("\n{}", game.get_stats());
+    println!("\n{}", game.get_stats(GAME).unwrap());
 
     println!("\n--- Mining Phase ---");
     println!("Player 1 mining for shots...");
-    match game.mine_for_shots("player1") {
+    match game.mine_for_shots(GAME, "player1") {
         Ok(shots) => println!("✓ Player 1 earned {} shot(s)", shots),
         Err(e) => println!("✗ Mining failed: {}", e),
     }
 
     println!("Player 2 mining for shots...");
-    match game.mine_for_shots("player2") {
+    match game.mine_for_shots(GAME, "player2") {
         Ok(shots) => println!("✓ Player 2 earned {} shot(s)", shots),
         Err(e) => println!("✗ Mining failed: {}", e),
     }
@@ -210,24 +240,24 @@ fn demo_game(game: &mut coordinator::GameCoordinator) {
     // Shooting demonstration
     println!("\n--- Combat Phase ---");
     println!("Player 1 fires at (5, 5)...");
-    match game.fire_shot("player1".to_string(), 5, 5) {
+    match game.fire_shot(GAME, "player1".to_string(), 5, 5) {
         Ok(_) => println!("✓ Shot fired! Transaction added to blockchain"),
         Err(e) => println!("✗ Shot failed: {}", e),
     }
 
     println!("Player 2 fires at (0, 0)...");
-    match game.fire_shot("player2".to_string(), 0, 0) {
+    match game.fire_shot(GAME, "player2".to_string(), 0, 0) {
         Ok(_) => println!("✓ Shot fired! Transaction added to blockchain"),
         Err(e) => println!("✗ Shot failed: {}", e),
     }
 
     // Mine the transactions
     println!("\nMining combat transactions...");
-    game.mine_for_shots("player1").ok();
+    game.mine_for_shots(GAME, "player1").ok();
 
     // Display final stats
     println!("\n--- Final Stats ---");
-    println!("{}", game.get_stats());
+    println!("{}", game.get_stats(GAME).unwrap());
     println!("Blockchain valid: {}", game.verify_blockchain());
     
     // Display blockchain
@@ -0,0 +1,120 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Board a game is played on: `width × height` cells, some of which may be land
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GameMap {
+    pub width: u16,
+    pub height: u16,
+    /// Land cells: ships can't be placed on them and shots can't aim at them
+    #[serde(default)]
+    pub blocked: Vec<(u16, u16)>,
+}
+
+impl GameMap {
+    /// Open rectangular board without land
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height, blocked: Vec::new() }
+    }
+
+    /// Open `size × size` board, the map of games configured by `grid_size` alone
+    pub fn square(size: u16) -> Self {
+        Self::new(size, size)
+    }
+
+    /// Parse a map drawn as text: one line per row, `.` for water and `#` for land
+    ///
+    /// Blank lines are skipped and every row must have the same width.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let rows: Vec<&str> = text.lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let mut blocked = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!("Map row {} has {} cells but the first row has {}", y, row.chars().count(), width));
+            }
+            for (x, c) in row.chars().enumerate() {
+                match c {
+                    '.' => {}
+                    '#' => blocked.push((x as u16, y as u16)),
+                    _ => return Err(format!("Unknown map cell '{}' at ({}, {})", c, x, y)),
+                }
+            }
+        }
+        let width = u16::try_from(width).map_err(|_| "Map is too wide".to_string())?;
+        let height = u16::try_from(rows.len()).map_err(|_| "Map is too tall".to_string())?;
+        let map = Self { width, height, blocked };
+        map.validate()?;
+        Ok(map)
+    }
+
+    /// Load a map from a JSON file (`.json`) or a text drawing (any other extension)
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read map file {}: {}", path.display(), e))?;
+        let map = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&text)
+                .map_err(|e| format!("Failed to parse map file {}: {}", path.display(), e))?
+        } else {
+            Self::parse(&text)?
+        };
+        map.validate()?;
+        Ok(map)
+    }
+
+    /// Check the map has water to play on and its land lies on the board
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err("Map width and height must be positive".to_string());
+        }
+        if let Some(&(x, y)) = self.blocked.iter().find(|&&cell| !self.contains(cell)) {
+            return Err(format!("Blocked cell ({}, {}) is outside the {}", x, y, self.describe()));
+        }
+        if self.open_cells() == 0 {
+            return Err("Map has no open cells".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the cell lies on the board
+    pub fn contains(&self, cell: (u16, u16)) -> bool {
+        cell.0 < self.width && cell.1 < self.height
+    }
+
+    pub fn is_blocked(&self, cell: (u16, u16)) -> bool {
+        self.blocked.contains(&cell)
+    }
+
+    /// Whether the cell lies on the board and is water
+    pub fn is_open(&self, cell: (u16, u16)) -> bool {
+        self.contains(cell) && !self.is_blocked(cell)
+    }
+
+    /// Number of water cells on the board
+    pub fn open_cells(&self) -> usize {
+        let land: HashSet<&(u16, u16)> = self.blocked.iter().collect();
+        self.width as usize * self.height as usize - land.len()
+    }
+
+    /// Error for a ship or shot cell the map does not allow, if any
+    pub fn check_cell(&self, cell: (u16, u16)) -> Result<(), String> {
+        if !self.contains(cell) {
+            return Err(format!("({}, {}) is outside the {}", cell.0, cell.1, self.describe()));
+        }
+        if self.is_blocked(cell) {
+            return Err(format!("({}, {}) is land", cell.0, cell.1));
+        }
+        Ok(())
+    }
+
+    /// Size of the board, e.g. "12x8 grid"
+    pub fn describe(&self) -> String {
+        format!("{}x{} grid", self.width, self.height)
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use crate::blockchain::Transaction;
+
+/// Size and lifetime limits of the pending transaction pool
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MempoolLimits {
+    /// Most pending transactions; a full pool evicts its lowest-fee transaction for a higher-fee one (0 means unlimited)
+    pub max_transactions: usize,
+    /// Blocks a transaction may wait before it is dropped (0 means it never expires)
+    pub ttl_blocks: u64,
+}
+
+impl Default for MempoolLimits {
+    fn default() -> Self {
+        Self {
+            max_transactions: 1000,
+            ttl_blocks: 100,
+        }
+    }
+}
+
+/// A transaction waiting to be mined
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub transaction: Transaction,
+    /// Height of the latest block when the transaction arrived
+    pub received_at: u64,
+}
+
+/// Transactions waiting to be mined, keyed by hash and kept in arrival order
+///
+/// The pool only checks transactions against each other; checks against the
+/// chain are left to `Blockchain::add_transaction`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mempool {
+    #[serde(default)]
+    pub limits: MempoolLimits,
+    #[serde(default)]
+    entries: Vec<PendingTransaction>,
+}
+
+impl Mempool {
+    pub fn new(limits: MempoolLimits) -> Self {
+        Self { limits, entries: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Pending transactions in arrival order
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        self.entries.iter().map(|entry| &entry.transaction)
+    }
+
+    /// Pending transactions with their hashes and arrival heights
+    pub fn entries(&self) -> &[PendingTransaction] {
+        &self.entries
+    }
+
+    /// Copies of the pending transactions in arrival order
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.iter().cloned().collect()
+    }
+
+    /// The transaction that arrived last
+    pub fn last(&self) -> Option<&Transaction> {
+        self.entries.last().map(|entry| &entry.transaction)
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.entries.iter().any(|entry| entry.hash == hash)
+    }
+
+    /// Nonce following the highest pending nonce of a sender
+    pub fn next_nonce(&self, sender: &str) -> Option<u64> {
+        self.iter()
+            .filter(|tx| tx.sender() == sender)
+            .map(|tx| tx.nonce + 1)
+            .max()
+    }
+
+    /// Check a transaction is not already pending and conflicts with no pending transaction
+    pub fn check(&self, transaction: &Transaction, hash: &str) -> Result<(), String> {
+        if self.contains(hash) {
+            return Err(format!("Transaction {} is already pending", hash));
+        }
+        let sender = transaction.sender();
+        for pending in self.iter() {
+            if pending.nonce == transaction.nonce && pending.sender() == sender {
+                return Err(format!("Nonce {} of {} is already used by a pending transaction", transaction.nonce, sender));
+            }
+            if let Some(id) = transaction.spent_utxos.iter().find(|id| pending.spent_utxos.contains(id)) {
+                return Err(format!("Shot UTXO {} is already spent by a pending transaction", id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a transaction that arrived at `height`, returning its hash and any transaction evicted for it
+    ///
+    /// A full pool evicts its lowest-fee transaction (the newest among equal fees),
+    /// but only for a transaction paying a higher fee.
+    pub fn insert(&mut self, transaction: Transaction, height: u64) -> Result<(String, Option<Transaction>), String> {
+        let hash = transaction.hash();
+        self.check(&transaction, &hash)?;
+
+        let mut evicted = None;
+        if self.limits.max_transactions != 0 && self.entries.len() >= self.limits.max_transactions {
+            let lowest = self.entries.iter()
+                .enumerate()
+                .min_by_key(|(i, entry)| (entry.transaction.fee, std::cmp::Reverse(*i)))
+                .map(|(i, entry)| (i, entry.transaction.fee));
+            match lowest {
+                Some((i, fee)) if fee < transaction.fee => {
+                    evicted = Some(self.entries.remove(i).transaction);
+                }
+                _ => return Err("Mempool is full and the transaction's fee is too low to replace a pending one".to_string()),
+            }
+        }
+
+        self.entries.push(PendingTransaction {
+            hash: hash.clone(),
+            transaction,
+            received_at: height,
+        });
+        Ok((hash, evicted))
+    }
+
+    /// Remove the transactions with the given hashes
+    pub fn remove(&mut self, hashes: &[String]) {
+        self.entries.retain(|entry| !hashes.contains(&entry.hash));
+    }
+
+    /// Remove exactly the transactions a block confirmed, and drop those it made invalid
+    ///
+    /// Returns the dropped transactions: ones reusing a confirmed nonce or spending a UTXO the block spent.
+    pub fn remove_confirmed(&mut self, confirmed: &[Transaction]) -> Vec<Transaction> {
+        let hashes: HashSet<String> = confirmed.iter().map(Transaction::hash).collect();
+        let nonces: HashSet<(String, u64)> = confirmed.iter().map(|tx| (tx.sender(), tx.nonce)).collect();
+        let spent: HashSet<&String> = confirmed.iter().flat_map(|tx| &tx.spent_utxos).collect();
+
+        let mut dropped = Vec::new();
+        let mut kept = Vec::new();
+        for entry in std::mem::take(&mut self.entries) {
+            if hashes.contains(&entry.hash) {
+                continue;
+            }
+            let tx = &entry.transaction;
+            if nonces.contains(&(tx.sender(), tx.nonce)) || tx.spent_utxos.iter().any(|id| spent.contains(id)) {
+                dropped.push(entry.transaction);
+            } else {
+                kept.push(entry);
+            }
+        }
+        self.entries = kept;
+        dropped
+    }
+
+    /// Drop transactions that have waited `ttl_blocks` blocks by `height`, returning them
+    pub fn expire(&mut self, height: u64) -> Vec<Transaction> {
+        if self.limits.ttl_blocks == 0 {
+            return Vec::new();
+        }
+        let ttl = self.limits.ttl_blocks;
+        let (expired, kept) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|entry| height >= entry.received_at.saturating_add(ttl));
+        self.entries = kept;
+        expired.into_iter().map(|entry: PendingTransaction| entry.transaction).collect()
+    }
+}
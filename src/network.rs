@@ -5,6 +5,7 @@ use serde::{Serialize, Deserialize};
 use std::path::PathBuf;
 use crate::blockchain::{Block, Transaction, Blockchain};
 use crate::coordinator::GameCoordinator;
+use crate::genesis::GenesisConfig;
 
 /// Represents a peer node in the network
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -32,7 +33,7 @@ pub struct NetworkNode {
 }
 
 impl NetworkNode {
-    pub fn new(node_id: String, port: u16, grid_size: u8, difficulty: usize) -> Self {
+    pub fn new(node_id: String, port: u16, grid_size: u16, difficulty: usize) -> Self {
         Self {
             peers: Arc::new(RwLock::new(HashSet::new())),
             coordinator: Arc::new(RwLock::new(GameCoordinator::new(grid_size, difficulty))),
@@ -41,24 +42,55 @@ impl NetworkNode {
         }
     }
 
+    /// Create a new NetworkNode whose rules come from a genesis config
+    pub fn from_genesis(node_id: String, port: u16, genesis: GenesisConfig) -> Self {
+        Self {
+            peers: Arc::new(RwLock::new(HashSet::new())),
+            coordinator: Arc::new(RwLock::new(GameCoordinator::from_genesis(genesis))),
+            node_id,
+            port,
+        }
+    }
+
     /// Create a new NetworkNode with blockchain persistence
     pub fn with_persistence(
         node_id: String,
         port: u16,
-        grid_size: u8,
+        grid_size: u16,
         difficulty: usize,
         blockchain_path: PathBuf,
+    ) -> Self {
+        Self::from_genesis_with_persistence(
+            node_id,
+            port,
+            GenesisConfig::new(grid_size, difficulty),
+            blockchain_path,
+        )
+    }
+
+    /// Create a new NetworkNode from a genesis config with blockchain persistence
+    pub fn from_genesis_with_persistence(
+        node_id: String,
+        port: u16,
+        genesis: GenesisConfig,
+        blockchain_path: PathBuf,
     ) -> Self {
         Self {
             peers: Arc::new(RwLock::new(HashSet::new())),
             coordinator: Arc::new(RwLock::new(
-                GameCoordinator::with_persistence(grid_size, difficulty, blockchain_path)
+                GameCoordinator::from_genesis_with_persistence(genesis, blockchain_path)
             )),
             node_id,
             port,
         }
     }
 
+    /// Hash of this node's genesis block
+    pub async fn genesis_hash(&self) -> String {
+        let coordinator = self.coordinator.read().await;
+        coordinator.blockchain.genesis_hash().to_string()
+    }
+
     /// Add a peer to the network
     pub async fn add_peer(&self, peer: Peer) {
         let mut peers = self.peers.write().await;
@@ -130,11 +162,17 @@ impl NetworkNode {
             .await
             .map_err(|e| format!("Failed to parse blockchain: {}", e))?;
 
-        // Replace our chain if peer's chain is longer and valid
+        // Refuse chains that started from a different genesis config
         let mut coordinator = self.coordinator.write().await;
+        if peer_blockchain.genesis_hash() != coordinator.blockchain.genesis_hash() {
+            return Err(format!("Peer {} uses a different genesis", peer.url()));
+        }
+
+        // Replace our chain if peer's chain is longer and valid
         if peer_blockchain.chain.len() > coordinator.blockchain.chain.len() 
             && peer_blockchain.is_chain_valid() {
-            coordinator.blockchain = peer_blockchain;
+            coordinator.adopt_chain(peer_blockchain)
+                .map_err(|e| format!("Refused chain from peer {}: {}", peer.url(), e))?;
             println!("✓ Synchronized blockchain from peer {}", peer.url());
             
             // Save the synchronized blockchain
@@ -159,24 +197,62 @@ impl NetworkNode {
         Ok(())
     }
 
+    /// Check that a peer was started from the same genesis config as this node
+    pub async fn verify_peer_genesis(&self, peer: &Peer) -> Result<(), String> {
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/genesis", peer.url());
+
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch genesis: {}", e))?;
+
+        let peer_genesis: GenesisInfo = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse genesis: {}", e))?;
+
+        if peer_genesis.hash != self.genesis_hash().await {
+            return Err(format!("Peer {} uses a different genesis", peer.url()));
+        }
+
+        Ok(())
+    }
+
     /// Announce this node to a peer
     pub async fn announce_to_peer(&self, peer: &Peer) -> Result<(), String> {
         let client = reqwest::Client::new();
         let url = format!("{}/api/peers", peer.url());
 
-        let self_peer = Peer::new("localhost".to_string(), self.port);
+        let announcement = PeerAnnouncement {
+            peer: Peer::new("localhost".to_string(), self.port),
+            genesis_hash: self.genesis_hash().await,
+        };
 
-        client
+        let response = client
             .post(&url)
-            .json(&self_peer)
+            .json(&announcement)
             .send()
             .await
             .map_err(|e| format!("Failed to announce to peer: {}", e))?;
 
+        if !response.status().is_success() {
+            return Err(format!("Peer refused announcement: {}", response.status()));
+        }
+
         Ok(())
     }
 }
 
+/// A peer introducing itself with the genesis hash it was started from
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeerAnnouncement {
+    #[serde(flatten)]
+    pub peer: Peer,
+    pub genesis_hash: String,
+}
+
 /// Request/Response types for API
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RegisterPlayerRequest {
@@ -184,13 +260,33 @@ pub struct RegisterPlayerRequest {
     pub ships: Vec<crate::game::Ship>,
     pub board_commitment: String,
     pub salt: String,
+    /// Merkle root of per-ship commitments (see `crypto::fleet_root`), needed to declare ships sunk
+    #[serde(default)]
+    pub ship_root: Option<String>,
+    /// Team to join (players without one fight alone)
+    #[serde(default)]
+    pub team: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateGameRequest {
+    pub game_id: String,
+    /// Rules for the new game (defaults to the genesis game rules)
+    #[serde(default)]
+    pub config: Option<crate::genesis::GameConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FireShotRequest {
     pub player_id: String,
-    pub target_x: u8,
-    pub target_y: u8,
+    pub target_x: u16,
+    pub target_y: u16,
+    /// Weapon to fire (a single-cell shot when omitted)
+    #[serde(default)]
+    pub weapon: crate::weapons::Weapon,
+    /// Shots paid to the miner who includes the shot
+    #[serde(default)]
+    pub fee: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -198,11 +294,148 @@ pub struct MineRequest {
     pub player_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TurnRequest {
+    pub player_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BeaconQuery {
+    pub height: u64,
+    /// Domain tag separating independent draws at the same height
+    pub domain: String,
+    /// Mix in the secrets revealed for the height (only available once the reveal window closed)
+    #[serde(default)]
+    pub committed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BeaconSecretRequest {
+    pub player_id: String,
+    pub domain: String,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferRequest {
+    /// Player handing over shots
+    pub player_id: String,
+    pub to: String,
+    pub amount: u32,
+    /// Shots paid to the miner who includes the transfer
+    #[serde(default)]
+    pub fee: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapOfferRequest {
+    /// Player making the offer
+    pub player_id: String,
+    pub taker: String,
+    /// Shots the maker hands over
+    pub give: u32,
+    /// Shots the maker asks for in return
+    pub want: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapAcceptRequest {
+    /// Player the offer was made to
+    pub player_id: String,
+    pub offer_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareRequest {
+    pub player_id: String,
+    /// Nonce whose share hash over the latest block meets the share difficulty
+    pub nonce: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ShotBalanceRequest {
     pub player_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObligationsRequest {
+    pub player_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShotResultsRequest {
+    pub player_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanReportRequest {
+    pub player_id: String,
+    /// Centre of the sonar being answered
+    pub x: u16,
+    pub y: u16,
+    pub proof: crate::crypto::CountProof,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DefeatDeclarationRequest {
+    pub player_id: String,
+    /// Every cell of the player's fleet
+    pub positions: Vec<(u16, u16)>,
+    pub salt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SunkDeclarationRequest {
+    pub player_id: String,
+    pub ship_name: String,
+    pub proof: crate::crypto::ShipProof,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChallengeRequest {
+    /// Player posting the bond
+    pub player_id: String,
+    /// Player whose report is challenged
+    pub reporter: String,
+    pub shot_id: String,
+    /// Cell of an area shot whose report is challenged (defaults to the shot's first cell)
+    #[serde(default)]
+    pub cell: Option<(u16, u16)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChallengeResponseRequest {
+    pub player_id: String,
+    pub challenge_id: String,
+    /// Every cell of the player's fleet
+    pub positions: Vec<(u16, u16)>,
+    pub salt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevealBoardRequest {
+    pub player_id: String,
+    /// Every cell of the player's fleet
+    pub positions: Vec<(u16, u16)>,
+    pub salt: String,
+    /// Boards held before each ship move, oldest first
+    #[serde(default)]
+    pub earlier_boards: Vec<crate::relocation::RevealedBoard>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveShipRequest {
+    pub player_id: String,
+    pub ship_name: String,
+    /// The ship's cells after the move
+    pub positions: Vec<(u16, u16)>,
+    /// Salt for the new board commitment
+    pub salt: String,
+    /// Per-ship fleet root for the new board
+    #[serde(default)]
+    pub ship_root: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
@@ -236,6 +469,12 @@ pub struct BlockchainInfo {
     pub is_valid: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenesisInfo {
+    pub hash: String,
+    pub config: GenesisConfig,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NodeInfo {
     pub node_id: String,
@@ -0,0 +1,199 @@
+use serde::{Serialize, Deserialize};
+use crate::crypto::CountProof;
+use crate::game::SunkShip;
+use crate::weapons::Region;
+
+/// What happens to a player who misses a report deadline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum MissedReportPenalty {
+    /// The shot counts as a hit on the silent player
+    TreatAsHit,
+    /// The silent player loses this many unspent shot UTXOs
+    LoseShots { count: u32 },
+    /// The silent player forfeits the game
+    Forfeit,
+}
+
+/// How long players have to report and what a missed report costs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportRules {
+    /// Blocks after the shot's block in which the report must be mined (0 means no deadline)
+    pub deadline_blocks: u64,
+    pub penalty: MissedReportPenalty,
+}
+
+impl Default for ReportRules {
+    fn default() -> Self {
+        Self {
+            deadline_blocks: 10,
+            penalty: MissedReportPenalty::TreatAsHit,
+        }
+    }
+}
+
+impl ReportRules {
+    /// Last block in which a report for a shot mined at `shot_block` is accepted
+    pub fn deadline(&self, shot_block: u64) -> Option<u64> {
+        if self.deadline_blocks == 0 {
+            None
+        } else {
+            Some(shot_block + self.deadline_blocks)
+        }
+    }
+}
+
+/// A player's duty to report whether a mined shot hit their fleet
+///
+/// Every shot on the shared grid can land on any player's ships, so each mined
+/// shot creates one obligation per affected cell for every registered player
+/// other than the shooter. A sonar creates a single obligation per player,
+/// answered with a ship-cell count for the area around its centre.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportObligation {
+    /// Hash of the shot transaction
+    pub shot_id: String,
+    /// Player who fired the shot
+    pub shooter: String,
+    /// Player who owes the report
+    pub player_id: String,
+    pub shot_x: u16,
+    pub shot_y: u16,
+    /// Block in which the shot was mined
+    pub shot_block: u64,
+    /// Block in which the report was mined (None while the report is still owed)
+    pub fulfilled_in_block: Option<u64>,
+    /// Whether the mined report claimed a hit (or the shot was treated as a hit after the deadline)
+    pub is_hit: Option<bool>,
+    /// Block in which the deadline passed without a report
+    #[serde(default)]
+    pub penalized_in_block: Option<u64>,
+    /// Whether this answers a sonar centred on the shot cell
+    #[serde(default)]
+    pub sonar: bool,
+    /// Ship cells the mined sonar report claimed
+    #[serde(default)]
+    pub count: Option<u8>,
+    /// Area the sonar scanned, which the count must cover
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+    /// Proof behind the mined sonar count, opened by the audit once the board is revealed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count_proof: Option<CountProof>,
+}
+
+impl ReportObligation {
+    pub fn new(shot_id: String, shooter: String, player_id: String, shot: (u16, u16), shot_block: u64) -> Self {
+        Self {
+            shot_id,
+            shooter,
+            player_id,
+            shot_x: shot.0,
+            shot_y: shot.1,
+            shot_block,
+            fulfilled_in_block: None,
+            is_hit: None,
+            penalized_in_block: None,
+            sonar: false,
+            count: None,
+            region: None,
+            count_proof: None,
+        }
+    }
+
+    /// Obligation to report a ship-cell count for a sonar centred on `center` that scanned `region`
+    pub fn sonar(shot_id: String, shooter: String, player_id: String, center: (u16, u16), region: Region, shot_block: u64) -> Self {
+        let mut obligation = Self::new(shot_id, shooter, player_id, center, shot_block);
+        obligation.sonar = true;
+        obligation.region = Some(region);
+        obligation
+    }
+
+    /// Whether the report is still owed
+    pub fn is_open(&self) -> bool {
+        self.fulfilled_in_block.is_none() && self.penalized_in_block.is_none()
+    }
+
+    /// Record the block in which the report was mined
+    pub fn fulfil(&mut self, is_hit: bool, block_index: u64) {
+        self.fulfilled_in_block = Some(block_index);
+        self.is_hit = Some(is_hit);
+    }
+
+    /// Record the block in which a sonar count was mined, with its proof if it carried one
+    ///
+    /// A count without a proof is flagged by the audit once the board is revealed.
+    pub fn fulfil_count(&mut self, count: u8, proof: Option<CountProof>, block_index: u64) {
+        self.fulfilled_in_block = Some(block_index);
+        self.count = Some(count);
+        self.count_proof = proof;
+    }
+
+    /// Close the obligation because its deadline passed at the given block
+    pub fn expire(&mut self, penalty: &MissedReportPenalty, block_index: u64) {
+        self.penalized_in_block = Some(block_index);
+        if *penalty == MissedReportPenalty::TreatAsHit && !self.sonar {
+            self.is_hit = Some(true);
+        }
+    }
+}
+
+/// What the players reported about one cell of a shooter's mined shots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShotResult {
+    pub shot_id: String,
+    pub shot_x: u16,
+    pub shot_y: u16,
+    pub shot_block: u64,
+    /// Players whose fleet was hit (including shots treated as hits after a missed deadline)
+    pub hits: Vec<String>,
+    /// Players who reported a miss
+    pub misses: Vec<String>,
+    /// Players whose report is still owed
+    pub pending: Vec<String>,
+    /// Players who missed the deadline without the shot counting as a hit
+    pub unreported: Vec<String>,
+    /// Ships declared sunk with this shot as the final hit
+    #[serde(default)]
+    pub sunk: Vec<SunkShip>,
+    /// Ship-cell counts reported for a sonar centred on this cell
+    #[serde(default)]
+    pub counts: Vec<(String, u8)>,
+}
+
+impl ShotResult {
+    pub fn new(obligation: &ReportObligation) -> Self {
+        Self {
+            shot_id: obligation.shot_id.clone(),
+            shot_x: obligation.shot_x,
+            shot_y: obligation.shot_y,
+            shot_block: obligation.shot_block,
+            hits: Vec::new(),
+            misses: Vec::new(),
+            pending: Vec::new(),
+            unreported: Vec::new(),
+            sunk: Vec::new(),
+            counts: Vec::new(),
+        }
+    }
+
+    /// Add one player's obligation for this shot
+    pub fn record(&mut self, obligation: &ReportObligation) {
+        let player_id = obligation.player_id.clone();
+        if let Some(count) = obligation.count {
+            self.counts.push((player_id, count));
+            return;
+        }
+        match obligation.is_hit {
+            Some(true) => self.hits.push(player_id),
+            Some(false) => self.misses.push(player_id),
+            None if obligation.is_open() => self.pending.push(player_id),
+            None => self.unreported.push(player_id),
+        }
+    }
+
+    /// Whether the shot hit at least one fleet
+    pub fn is_hit(&self) -> bool {
+        !self.hits.is_empty()
+    }
+}
@@ -0,0 +1,104 @@
+use sha2::{Sha256, Digest};
+use serde::{Serialize, Deserialize};
+
+/// Proof of work by a pool worker at the lower share difficulty
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Share {
+    /// Account the worker is paid to
+    pub worker: String,
+    pub nonce: u64,
+}
+
+impl Share {
+    /// Hash of the share over the block it helps mine
+    pub fn hash(&self, template: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(template.as_bytes());
+        hasher.update(self.worker.as_bytes());
+        hasher.update(self.nonce.to_le_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Whether the share's hash has `difficulty` leading zeros
+    pub fn meets(&self, template: &str, difficulty: usize) -> bool {
+        self.hash(template).starts_with(&"0".repeat(difficulty))
+    }
+
+    /// Search nonces from `start` until one meets the share difficulty
+    pub fn find(worker: &str, template: &str, difficulty: usize, start: u64) -> Self {
+        let mut share = Self { worker: worker.to_string(), nonce: start };
+        while !share.meets(template, difficulty) {
+            share.nonce += 1;
+        }
+        share
+    }
+}
+
+/// Split `reward` shots among workers in proportion to their shares
+///
+/// Whole shots go out first; leftovers go to the largest remainders, ties broken
+/// by worker ID so every node computes the same split. Workers paid nothing are left out.
+pub fn split_reward(shares: &[Share], reward: u32) -> Vec<(String, u32)> {
+    let mut counts: Vec<(String, u64)> = Vec::new();
+    for share in shares {
+        match counts.iter_mut().find(|(worker, _)| *worker == share.worker) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((share.worker.clone(), 1)),
+        }
+    }
+    counts.sort();
+
+    let total = shares.len() as u64;
+    if total == 0 {
+        return Vec::new();
+    }
+    let mut payouts: Vec<(String, u32, u64)> = counts.into_iter()
+        .map(|(worker, count)| {
+            let owed = count * reward as u64;
+            (worker, (owed / total) as u32, owed % total)
+        })
+        .collect();
+
+    let leftover = reward - payouts.iter().map(|(_, shots, _)| shots).sum::<u32>();
+    let mut by_remainder: Vec<usize> = (0..payouts.len()).collect();
+    by_remainder.sort_by(|&a, &b| payouts[b].2.cmp(&payouts[a].2).then(payouts[a].0.cmp(&payouts[b].0)));
+    for &i in by_remainder.iter().take(leftover as usize) {
+        payouts[i].1 += 1;
+    }
+
+    payouts.into_iter()
+        .filter(|(_, shots, _)| *shots > 0)
+        .map(|(worker, shots, _)| (worker, shots))
+        .collect()
+}
+
+/// Shares a pool coordinator node collects for the next block
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MiningPool {
+    /// Block hash the current shares build on
+    pub template: String,
+    pub shares: Vec<Share>,
+}
+
+impl MiningPool {
+    /// Accept a share for the block on top of `template`, dropping shares for older blocks
+    pub fn submit(&mut self, share: Share, template: &str, difficulty: usize) -> Result<(), String> {
+        if self.template != template {
+            self.template = template.to_string();
+            self.shares.clear();
+        }
+        if !share.meets(template, difficulty) {
+            return Err("Share does not meet the share difficulty".to_string());
+        }
+        if self.shares.contains(&share) {
+            return Err("Share was already submitted".to_string());
+        }
+        self.shares.push(share);
+        Ok(())
+    }
+
+    /// Shares submitted by a worker for the current template
+    pub fn shares_of(&self, worker: &str) -> usize {
+        self.shares.iter().filter(|share| share.worker == worker).count()
+    }
+}
@@ -0,0 +1,207 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use crate::crypto::MoveProof;
+use crate::game::Ship;
+use crate::map::GameMap;
+
+/// Cost and reach of moving a ship during the battle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelocationRules {
+    /// Shot UTXOs spent to move one ship
+    pub move_cost: u32,
+    /// Most cells (horizontal plus vertical) a ship may slide in one move
+    pub max_distance: u8,
+}
+
+impl Default for RelocationRules {
+    fn default() -> Self {
+        Self {
+            move_cost: 2,
+            max_distance: 2,
+        }
+    }
+}
+
+/// A board commitment a player held before moving a ship
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoardEpoch {
+    pub commitment: String,
+    /// Last block whose shots are answered against this board
+    pub until_block: u64,
+    /// Proof carried by the move that replaced this board, opened by the audit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_proof: Option<MoveProof>,
+}
+
+/// A board opened after the game, with the salt behind its commitment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RevealedBoard {
+    pub positions: Vec<(u16, u16)>,
+    pub salt: String,
+}
+
+/// A move this node created and will apply once it is mined (never leaves the node)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedMove {
+    pub commitment: String,
+    pub ships: Vec<Ship>,
+    pub salt: String,
+}
+
+/// Slide the ship `ship_name` onto `new_positions` and return the resulting fleet
+///
+/// The ship must be unhit, keep its shape, move between 1 and `max_distance`
+/// cells, stay on the map's open water and not overlap another ship.
+pub fn relocate(
+    ships: &[Ship],
+    ship_name: &str,
+    new_positions: &[(u16, u16)],
+    hit_cells: &[(u16, u16)],
+    max_distance: u8,
+    map: &GameMap,
+) -> Result<Vec<Ship>, String> {
+    let index = ships.iter()
+        .position(|ship| ship.id == ship_name)
+        .ok_or_else(|| format!("Ship {} not found", ship_name))?;
+    let ship = &ships[index];
+
+    if ship.hits.contains(&true) || ship.positions.iter().any(|cell| hit_cells.contains(cell)) {
+        return Err(format!("Ship {} has been hit and cannot move", ship_name));
+    }
+
+    // Sliding apart overlapping ships would change the number of cells the fleet covers
+    let shares_cells = ships.iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .any(|(_, other)| other.positions.iter().any(|cell| ship.positions.contains(cell)));
+    if shares_cells {
+        return Err(format!("Ship {} shares cells with another ship and cannot move", ship_name));
+    }
+
+    let mut old = ship.positions.clone();
+    let mut new = new_positions.to_vec();
+    old.sort();
+    new.sort();
+    if old.is_empty() || old.len() != new.len() {
+        return Err("A moved ship must keep its shape".to_string());
+    }
+    let dx = new[0].0 as i32 - old[0].0 as i32;
+    let dy = new[0].1 as i32 - old[0].1 as i32;
+    let slid = old.iter()
+        .zip(&new)
+        .all(|(a, b)| b.0 as i32 - a.0 as i32 == dx && b.1 as i32 - a.1 as i32 == dy);
+    if !slid {
+        return Err("A moved ship must keep its shape".to_string());
+    }
+
+    let distance = dx.unsigned_abs() + dy.unsigned_abs();
+    if distance == 0 || distance > max_distance as u32 {
+        return Err(format!("Ships move between 1 and {} cells", max_distance));
+    }
+    if new.iter().any(|&cell| !map.contains(cell)) {
+        return Err("A moved ship must stay on the grid".to_string());
+    }
+    if new.iter().any(|&cell| map.is_blocked(cell)) {
+        return Err("A moved ship cannot move onto land".to_string());
+    }
+
+    let overlaps = ships.iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .any(|(_, other)| other.positions.iter().any(|cell| new.contains(cell)));
+    if overlaps {
+        return Err("A moved ship cannot overlap another ship".to_string());
+    }
+
+    let mut fleet = ships.to_vec();
+    fleet[index] = Ship::new(ship.id.clone(), new_positions.to_vec());
+    Ok(fleet)
+}
+
+/// Check `new_board` follows from `old_board` by one slide of one unhit ship
+///
+/// Boards are flat cell lists, as revealed after the game: the cells that changed must
+/// belong to one straight ship that slid between 1 and `max_distance` cells onto open
+/// water, and none of its cells may be in `hit_cells`.
+pub fn check_slide(
+    old_board: &[(u16, u16)],
+    new_board: &[(u16, u16)],
+    hit_cells: &[(u16, u16)],
+    max_distance: u8,
+    map: &GameMap,
+) -> Result<(), String> {
+    let old: HashSet<(u16, u16)> = old_board.iter().copied().collect();
+    let new: HashSet<(u16, u16)> = new_board.iter().copied().collect();
+    let left: Vec<(u16, u16)> = old.difference(&new).copied().collect();
+    if left.is_empty() {
+        return Err("The board did not change".to_string());
+    }
+
+    // Some boards read as more than one slide; any slide of an unhit ship will do
+    let max = max_distance as i32;
+    let mut hit_ship = None;
+    for dx in -max..=max {
+        for dy in -max..=max {
+            let distance = dx.abs() + dy.abs();
+            if distance == 0 || distance > max {
+                continue;
+            }
+            let Some(ship) = slid_ship(&old, &new, &left, (dx, dy), map) else {
+                continue;
+            };
+            match ship.iter().find(|cell| hit_cells.contains(cell)) {
+                Some(&cell) => hit_ship = Some(cell),
+                None => return Ok(()),
+            }
+        }
+    }
+    match hit_ship {
+        Some((x, y)) => Err(format!("A ship hit at ({}, {}) moved", x, y)),
+        None => Err(format!("The board does not follow from the one before by one ship sliding at most {} cells", max_distance)),
+    }
+}
+
+/// Cells of a straight ship that slid by `offset` from `old` to `new`, if one did
+///
+/// The ship holds every cell the board left and, since a moved ship may not land on
+/// another ship, every old cell one of its cells slid onto.
+fn slid_ship(
+    old: &HashSet<(u16, u16)>,
+    new: &HashSet<(u16, u16)>,
+    left: &[(u16, u16)],
+    offset: (i32, i32),
+    map: &GameMap,
+) -> Option<Vec<(u16, u16)>> {
+    let shift = |cell: (u16, u16)| -> Option<(u16, u16)> {
+        let x = u16::try_from(cell.0 as i32 + offset.0).ok()?;
+        let y = u16::try_from(cell.1 as i32 + offset.1).ok()?;
+        Some((x, y)).filter(|&moved| map.is_open(moved))
+    };
+
+    let mut ship = left.to_vec();
+    let mut i = 0;
+    while i < ship.len() {
+        let moved = shift(ship[i])?;
+        if old.contains(&moved) && !ship.contains(&moved) {
+            ship.push(moved);
+        }
+        i += 1;
+    }
+
+    let moved: HashSet<(u16, u16)> = ship.iter().map(|&cell| shift(cell)).collect::<Option<_>>()?;
+    let expected: HashSet<(u16, u16)> = old.iter()
+        .filter(|cell| !ship.contains(cell))
+        .copied()
+        .chain(moved)
+        .collect();
+    (expected == *new && is_straight(&ship)).then_some(ship)
+}
+
+/// Whether the cells form one unbroken horizontal or vertical line
+fn is_straight(cells: &[(u16, u16)]) -> bool {
+    let mut sorted = cells.to_vec();
+    sorted.sort();
+    let horizontal = sorted.windows(2).all(|pair| pair[1] == (pair[0].0 + 1, pair[0].1));
+    let vertical = sorted.windows(2).all(|pair| pair[1] == (pair[0].0, pair[0].1 + 1));
+    horizontal || vertical
+}
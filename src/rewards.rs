@@ -0,0 +1,131 @@
+use serde::{Serialize, Deserialize};
+
+/// How the block reward shrinks as the chain grows
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum RewardDecay {
+    /// Every block pays the full mining reward
+    #[default]
+    None,
+    /// The reward halves every `interval` blocks
+    Halving { interval: u64 },
+    /// The reward drops by `step` shots every `interval` blocks
+    Linear { interval: u64, step: u32 },
+}
+
+/// How many shot UTXOs a game hands out and when
+///
+/// Every amount is a pure function of the block height, the hits a player has
+/// taken and their unspent shots, so every node computes the same rewards.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RewardPolicy {
+    /// Shots awarded to the miner of each block before decay
+    pub mining_reward: u32,
+    /// Shots awarded to a player when they register
+    pub registration_shots: u32,
+    #[serde(default)]
+    pub decay: RewardDecay,
+    /// Block reward the decay never goes below
+    #[serde(default)]
+    pub min_reward: u32,
+    /// Extra shots per ship cell hit, added when a player mines a block
+    #[serde(default)]
+    pub catch_up_per_hit: u32,
+    /// Most catch-up shots per block (0 means unlimited)
+    #[serde(default)]
+    pub catch_up_max: u32,
+    /// Most unspent shots an account may hold; rewards beyond it are not minted (0 means unlimited)
+    #[serde(default)]
+    pub max_balance: u32,
+    /// Blocks a shot UTXO stays spendable after the block that created it (0 means shots never expire)
+    #[serde(default)]
+    pub shot_lifetime: u64,
+}
+
+impl Default for RewardPolicy {
+    fn default() -> Self {
+        Self {
+            mining_reward: 1,
+            registration_shots: 1,
+            decay: RewardDecay::None,
+            min_reward: 0,
+            catch_up_per_hit: 0,
+            catch_up_max: 0,
+            max_balance: 0,
+            shot_lifetime: 0,
+        }
+    }
+}
+
+impl RewardPolicy {
+    /// Check the policy can be applied
+    pub fn validate(&self) -> Result<(), String> {
+        match self.decay {
+            RewardDecay::Halving { interval: 0 } | RewardDecay::Linear { interval: 0, .. } => {
+                return Err("Genesis reward decay needs a positive interval".to_string());
+            }
+            _ => {}
+        }
+        if self.min_reward > self.mining_reward {
+            return Err("Genesis min_reward must not exceed mining_reward".to_string());
+        }
+        Ok(())
+    }
+
+    /// Block reward for the block at `height` after decay
+    pub fn block_reward(&self, height: u64) -> u32 {
+        let reward = match self.decay {
+            RewardDecay::None => self.mining_reward,
+            RewardDecay::Halving { interval } => {
+                let halvings = height / interval.max(1);
+                if halvings >= u32::BITS as u64 { 0 } else { self.mining_reward >> halvings }
+            }
+            RewardDecay::Linear { interval, step } => {
+                let drops = (height / interval.max(1)).min(u32::MAX as u64) as u32;
+                self.mining_reward.saturating_sub(drops.saturating_mul(step))
+            }
+        };
+        reward.max(self.min_reward)
+    }
+
+    /// Extra shots for a miner whose fleet has taken `hits_taken` hits
+    pub fn catch_up_bonus(&self, hits_taken: usize) -> u32 {
+        let hits = hits_taken.min(u32::MAX as usize) as u32;
+        let bonus = hits.saturating_mul(self.catch_up_per_hit);
+        if self.catch_up_max == 0 { bonus } else { bonus.min(self.catch_up_max) }
+    }
+
+    /// Cut `shots` down so an account holding `balance` stays within the maximum balance
+    pub fn capped(&self, shots: u32, balance: usize) -> u32 {
+        if self.max_balance == 0 {
+            return shots;
+        }
+        let room = (self.max_balance as usize).saturating_sub(balance);
+        shots.min(room as u32)
+    }
+
+    /// Whether an account holding `balance` can receive no more shots
+    pub fn is_full(&self, balance: usize) -> bool {
+        self.max_balance != 0 && balance >= self.max_balance as usize
+    }
+
+    /// Shots minted to a player who mines the block at `height`
+    pub fn mining_payout(&self, height: u64, hits_taken: usize, balance: usize) -> u32 {
+        let earned = self.block_reward(height).saturating_add(self.catch_up_bonus(hits_taken));
+        self.capped(earned, balance)
+    }
+
+    /// Block from which shots created in the block at `created_in_block` can no longer be spent
+    pub fn expiry(&self, created_in_block: u64) -> Option<u64> {
+        if self.shot_lifetime == 0 {
+            None
+        } else {
+            Some(created_in_block.saturating_add(self.shot_lifetime))
+        }
+    }
+
+    /// Shots granted to a player who registers with `balance` unspent shots
+    pub fn registration_grant(&self, balance: usize) -> u32 {
+        self.capped(self.registration_shots, balance)
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Serialize, Deserialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::game::Ship;
+use crate::genesis::GameConfig;
+use crate::relocation::PlannedMove;
+
+/// Off-chain change a node made to its games
+///
+/// Games, registrations, rounds, turns and planned ship moves are local to a node;
+/// only the transactions they lead to reach the chain. Nodes that want to follow
+/// the same game register the same players themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SetupAction {
+    CreateGame {
+        game_id: String,
+        config: GameConfig,
+    },
+    Register {
+        game_id: String,
+        player_id: String,
+        ships: Vec<Ship>,
+        board_commitment: String,
+        salt: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ship_root: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        team: Option<String>,
+    },
+    /// A ship move this node created, applied once its transaction is mined
+    PlanMove {
+        game_id: String,
+        player_id: String,
+        planned: PlannedMove,
+    },
+    /// The start of a game's next round
+    NextRound { game_id: String },
+    /// The end of the current player's turn in a turn-based game
+    EndTurn { game_id: String },
+}
+
+/// A setup action and the height of the latest block when it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupEvent {
+    pub height: u64,
+    pub action: SetupAction,
+}
+
+/// File a node keeps its setup log in, next to its blockchain file
+pub fn log_path(blockchain_path: &Path) -> PathBuf {
+    blockchain_path.with_extension("setup.json")
+}
+
+/// Save a setup log to a JSON file
+pub fn save_log(path: &Path, events: &[SetupEvent]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(events)
+        .map_err(|e| format!("Failed to serialize setup log: {}", e))?;
+
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write setup log: {}", e))
+}
+
+/// Load a setup log from a JSON file; a node that never saved one has an empty log
+pub fn load_log(path: &Path) -> Result<Vec<SetupEvent>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read setup log: {}", e))?;
+
+    serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to deserialize setup log: {}", e))
+}
@@ -0,0 +1,30 @@
+use serde::{Serialize, Deserialize};
+
+/// What a player may do with a shot whose cells hold their own ships
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SelfFire {
+    /// Shots may cover the shooter's own cells, so the shooter reports on their own shots too
+    #[default]
+    Allow,
+    /// Shots may not cover a cell holding one of the shooter's ships
+    Forbid,
+    /// Shots may not cover a cell holding a ship of the shooter or a teammate
+    ForbidTeam,
+}
+
+/// Which cells a shot may aim at
+///
+/// Targets must always lie on the grid. Sonar scans never hit anything, so
+/// they are exempt from the repeat and self-fire rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ShotRules {
+    /// Reject a shot at a cell the same player already aimed a mined or pending shot at
+    #[serde(default)]
+    pub no_repeat_targets: bool,
+    /// Whether shots may cover cells holding the shooter's (or their team's) ships
+    ///
+    /// Nodes check this against the boards they know: every node that registered
+    /// the players knows their fleets until one of them moves a ship.
+    #[serde(default)]
+    pub self_fire: SelfFire,
+}
@@ -0,0 +1,27 @@
+use serde::{Serialize, Deserialize};
+
+/// How players declared in the same team fight together
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamRules {
+    /// Whether shots also create report obligations for (and can sink) the shooter's teammates
+    pub friendly_fire: bool,
+    /// Whether team members mine into and spend from one team-owned shot pool
+    pub shared_shots: bool,
+    /// Most players per team (0 means unlimited)
+    pub max_team_size: usize,
+}
+
+impl Default for TeamRules {
+    fn default() -> Self {
+        Self {
+            friendly_fire: false,
+            shared_shots: true,
+            max_team_size: 0,
+        }
+    }
+}
+
+/// Owner of a team's shared shot UTXOs within a game
+pub fn team_account(team: &str) -> String {
+    format!("team:{}", team)
+}
@@ -0,0 +1,80 @@
+use serde::{Serialize, Deserialize};
+use crate::blockchain::Transaction;
+
+/// Most a single block may hold (0 means unlimited)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlockLimits {
+    /// Most transactions in a block
+    #[serde(default)]
+    pub max_transactions: usize,
+    /// Most bytes of JSON-encoded transactions in a block
+    #[serde(default)]
+    pub max_bytes: usize,
+}
+
+impl BlockLimits {
+    /// Check a block's transactions stay within the limits
+    pub fn check(&self, transactions: &[Transaction]) -> Result<(), String> {
+        if self.max_transactions != 0 && transactions.len() > self.max_transactions {
+            return Err(format!(
+                "Block holds {} transactions, more than the limit of {}",
+                transactions.len(), self.max_transactions
+            ));
+        }
+        let bytes: usize = transactions.iter().map(transaction_size).sum();
+        if self.max_bytes != 0 && bytes > self.max_bytes {
+            return Err(format!("Block holds {} bytes of transactions, more than the limit of {}", bytes, self.max_bytes));
+        }
+        Ok(())
+    }
+}
+
+/// Size of a transaction as counted against `max_bytes`
+pub fn transaction_size(transaction: &Transaction) -> usize {
+    serde_json::to_string(transaction).unwrap().len()
+}
+
+/// Transactions picked for the next block, with the fees they pay its miner
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockTemplate {
+    pub transactions: Vec<Transaction>,
+    /// Shot fees of the picked transactions, added to the miner's coinbase
+    pub fees: u32,
+    /// Bytes of JSON-encoded transactions
+    pub bytes: usize,
+}
+
+impl BlockTemplate {
+    /// Fill a block from `pending` by priority, returning the template and the transactions left over
+    ///
+    /// Higher fees go first and equal fees go oldest first, ties kept in arrival
+    /// order. A transaction that would break a limit is skipped so smaller ones
+    /// behind it can still fit; leftovers keep their arrival order for later blocks.
+    pub fn build(pending: Vec<Transaction>, limits: &BlockLimits) -> (Self, Vec<Transaction>) {
+        let mut order: Vec<usize> = (0..pending.len()).collect();
+        order.sort_by(|&a, &b| {
+            pending[b].fee.cmp(&pending[a].fee)
+                .then(pending[a].timestamp.cmp(&pending[b].timestamp))
+        });
+
+        let mut template = Self::default();
+        let mut chosen = Vec::new();
+        for i in order {
+            if limits.max_transactions != 0 && chosen.len() == limits.max_transactions {
+                break;
+            }
+            let size = transaction_size(&pending[i]);
+            if limits.max_bytes != 0 && template.bytes + size > limits.max_bytes {
+                continue;
+            }
+            chosen.push(i);
+            template.bytes += size;
+            template.fees = template.fees.saturating_add(pending[i].fee);
+        }
+
+        let mut slots: Vec<Option<Transaction>> = pending.into_iter().map(Some).collect();
+        template.transactions = chosen.into_iter().filter_map(|i| slots[i].take()).collect();
+        let leftover = slots.into_iter().flatten().collect();
+        (template, leftover)
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Serialize, Deserialize};
+
+/// Whether players may hand shots to each other and how long swap offers stay open
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TradeRules {
+    /// Allow shot transfers and swaps between players
+    pub enabled: bool,
+    /// Blocks after the offer's block in which the taker may accept a swap
+    pub swap_blocks: u64,
+}
+
+impl Default for TradeRules {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            swap_blocks: 10,
+        }
+    }
+}
+
+/// How a swap offer ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SwapStatus {
+    /// Waiting for the taker to accept
+    Open,
+    /// Both sides' shots changed hands in the same block
+    Completed,
+    /// Nobody accepted in time and the maker got their shots back
+    Expired,
+}
+
+/// An offer to trade `give` of the maker's shots for `want` of the taker's
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwapOffer {
+    /// Hash of the offer transaction (also the ID of the maker's escrow)
+    pub id: String,
+    pub maker: String,
+    pub taker: String,
+    /// Shots the maker locked in escrow
+    pub give: u32,
+    /// Shots the taker must lock to accept
+    pub want: u32,
+    /// Block in which the offer was mined
+    pub offered_in_block: u64,
+    /// Last block in which an acceptance is mined
+    pub deadline_block: u64,
+    pub status: SwapStatus,
+    pub resolved_in_block: Option<u64>,
+}
+
+impl SwapOffer {
+    /// Whether the offer can still be accepted
+    pub fn is_open(&self) -> bool {
+        self.status == SwapStatus::Open
+    }
+
+    /// Close the offer as completed or expired
+    pub fn resolve(&mut self, status: SwapStatus, block_index: u64) {
+        self.status = status;
+        self.resolved_in_block = Some(block_index);
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Serialize, Deserialize};
+use crate::beacon::Beacon;
+
+/// How players of a game get their shots
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TurnMode {
+    /// Players race to mine blocks for shot UTXOs and fire whenever they hold one
+    #[default]
+    Mining,
+    /// Every active player is granted one shot per round and fires it on their turn
+    TurnBased,
+}
+
+/// Order in which players take their turns within a round
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TurnOrder {
+    /// Player ID order, the same every round
+    #[default]
+    Fixed,
+    /// Shuffled every round by a beacon from the block the round starts on
+    Random,
+}
+
+/// Whether a game is played in mining races or alternating turns
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TurnRules {
+    #[serde(default)]
+    pub mode: TurnMode,
+    #[serde(default)]
+    pub order: TurnOrder,
+}
+
+impl TurnRules {
+    pub fn is_turn_based(&self) -> bool {
+        self.mode == TurnMode::TurnBased
+    }
+
+    /// Order in which `players` take their turns in the round `beacon` was drawn for
+    pub fn turn_order(&self, players: &[String], beacon: &Beacon) -> Vec<String> {
+        let mut order = players.to_vec();
+        order.sort();
+        if self.order == TurnOrder::Random {
+            beacon.shuffle(&mut order);
+        }
+        order
+    }
+}
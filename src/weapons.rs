@@ -0,0 +1,136 @@
+use serde::{Serialize, Deserialize};
+use crate::map::GameMap;
+
+/// Direction a torpedo travels from its target cell
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Direction {
+    /// Towards higher x
+    Horizontal,
+    /// Towards higher y
+    Vertical,
+}
+
+/// What a shot transaction fires
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum Weapon {
+    /// One cell for one shot UTXO
+    #[default]
+    Single,
+    /// The 3×3 square centred on the target
+    Bomb,
+    /// A straight line of `torpedo_length` cells starting at the target
+    Torpedo { direction: Direction },
+    /// Scouts the 3×3 square centred on the target; players only report how many ship cells it holds
+    Sonar,
+}
+
+impl Weapon {
+    pub fn is_single(&self) -> bool {
+        matches!(self, Weapon::Single)
+    }
+
+    /// Whether players answer with a ship-cell count instead of hit/miss reports
+    pub fn is_sonar(&self) -> bool {
+        matches!(self, Weapon::Sonar)
+    }
+
+    /// Shot UTXOs spent to fire this weapon
+    pub fn cost(&self, rules: &WeaponRules) -> u32 {
+        match self {
+            Weapon::Single => 1,
+            Weapon::Bomb => rules.bomb_cost,
+            Weapon::Torpedo { .. } => rules.torpedo_cost,
+            Weapon::Sonar => rules.sonar_cost,
+        }
+    }
+
+    /// Open cells on the map affected when fired at `target`
+    pub fn cells(&self, target: (u16, u16), map: &GameMap, rules: &WeaponRules) -> Vec<(u16, u16)> {
+        let (x, y) = (target.0 as i32, target.1 as i32);
+        let cells: Vec<(i32, i32)> = match self {
+            Weapon::Single => vec![(x, y)],
+            Weapon::Bomb | Weapon::Sonar => (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                .collect(),
+            Weapon::Torpedo { direction } => (0..rules.torpedo_length as i32)
+                .map(|i| match direction {
+                    Direction::Horizontal => (x + i, y),
+                    Direction::Vertical => (x, y + i),
+                })
+                .collect(),
+        };
+        cells.into_iter()
+            .filter_map(|(cx, cy)| Some((u16::try_from(cx).ok()?, u16::try_from(cy).ok()?)))
+            .filter(|&cell| map.is_open(cell))
+            .collect()
+    }
+}
+
+/// Shot UTXO costs and reach of the area weapons (single shots always cost one UTXO)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeaponRules {
+    pub bomb_cost: u32,
+    pub torpedo_cost: u32,
+    /// Cells a torpedo covers, starting at its target
+    pub torpedo_length: u8,
+    pub sonar_cost: u32,
+}
+
+impl Default for WeaponRules {
+    fn default() -> Self {
+        Self {
+            bomb_cost: 4,
+            torpedo_cost: 3,
+            torpedo_length: 4,
+            sonar_cost: 2,
+        }
+    }
+}
+
+/// Rectangle of cells, from its top-left corner `(x, y)`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Region {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Region {
+    /// The 3×3 square a sonar scans around `center`, clipped to the map
+    pub fn around(center: (u16, u16), map: &GameMap) -> Self {
+        let x = center.0.saturating_sub(1);
+        let y = center.1.saturating_sub(1);
+        let right = center.0.saturating_add(1).min(map.width.saturating_sub(1));
+        let bottom = center.1.saturating_add(1).min(map.height.saturating_sub(1));
+        Self {
+            x,
+            y,
+            width: right.saturating_sub(x) + 1,
+            height: bottom.saturating_sub(y) + 1,
+        }
+    }
+
+    pub fn contains(&self, cell: (u16, u16)) -> bool {
+        (self.x as u32..self.x as u32 + self.width as u32).contains(&(cell.0 as u32))
+            && (self.y as u32..self.y as u32 + self.height as u32).contains(&(cell.1 as u32))
+    }
+
+    /// Every cell of the region, row by row
+    pub fn cells(&self) -> Vec<(u16, u16)> {
+        (self.y..self.y.saturating_add(self.height))
+            .flat_map(|y| (self.x..self.x.saturating_add(self.width)).map(move |x| (x, y)))
+            .collect()
+    }
+
+    /// Number of cells of `board` inside the region
+    pub fn count(&self, board: &[(u16, u16)]) -> u8 {
+        board.iter().filter(|&&cell| self.contains(cell)).count() as u8
+    }
+}
+
+/// Number of cells of `board` inside the sonar area centred on `center`
+pub fn sonar_count(board: &[(u16, u16)], center: (u16, u16)) -> u8 {
+    Region::around(center, &GameMap::square(u16::MAX)).count(board)
+}
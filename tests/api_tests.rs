@@ -5,10 +5,18 @@ use axum::{
 use tower::util::ServiceExt;
 use std::sync::Arc;
 use fleetchain::api::create_router;
-use fleetchain::network::{NetworkNode, RegisterPlayerRequest, FireShotRequest, MineRequest, Peer};
-use fleetchain::game::Ship;
-use fleetchain::crypto::{generate_salt, create_commitment};
-use fleetchain::blockchain::{Block, Transaction};
+use fleetchain::network::{NetworkNode, RegisterPlayerRequest, FireShotRequest, MineRequest, Peer, PeerAnnouncement, GenesisInfo, CreateGameRequest, ObligationsRequest, ShotResultsRequest, TurnRequest, ApiResponse};
+use fleetchain::obligations::{ReportObligation, ShotResult};
+use fleetchain::game::{Ship, HitReport, DEFAULT_GAME_ID};
+use fleetchain::crypto::{generate_salt, create_commitment, HitProof};
+use fleetchain::blockchain::{Block, Blockchain, Transaction};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::beacon::Beacon;
+use fleetchain::turns::TurnMode;
+use fleetchain::weapons::Weapon;
+
+mod common;
+use common::{mined_block, register};
 
 // Helper function to create a valid 4-ship fleet
 fn create_valid_fleet() -> Vec<Ship> {
@@ -45,7 +53,7 @@ async fn test_register_player() {
 
     let ships = create_valid_fleet();
 
-    let all_positions: Vec<(u8, u8)> = ships.iter()
+    let all_positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|ship| ship.positions.clone())
         .collect();
 
@@ -57,6 +65,8 @@ async fn test_register_player() {
         ships,
         board_commitment: commitment,
         salt,
+        ship_root: None,
+        team: None,
     };
 
     let response = app
@@ -91,6 +101,8 @@ async fn test_register_player_invalid_commitment() {
         ships,
         board_commitment: commitment,
         salt,
+        ship_root: None,
+        team: None,
     };
 
     let response = app
@@ -116,12 +128,12 @@ async fn test_mine_for_shots() {
     {
         let mut coordinator = node.coordinator.write().await;
         let ships = create_valid_fleet();
-        let all_positions: Vec<(u8, u8)> = ships.iter()
+        let all_positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|ship| ship.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&all_positions, &salt);
-        coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+        coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     }
 
     let app = create_router(node.clone());
@@ -153,13 +165,13 @@ async fn test_fire_shot() {
     {
         let mut coordinator = node.coordinator.write().await;
         let ships = create_valid_fleet();
-        let all_positions: Vec<(u8, u8)> = ships.iter()
+        let all_positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|ship| ship.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&all_positions, &salt);
-        coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
-        coordinator.mine_for_shots("player1").unwrap();
+        coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     }
 
     let app = create_router(node.clone());
@@ -168,6 +180,8 @@ async fn test_fire_shot() {
         player_id: "player1".to_string(),
         target_x: 5,
         target_y: 5,
+        weapon: Weapon::Single,
+        fee: 0,
     };
 
     let response = app
@@ -193,12 +207,12 @@ async fn test_fire_shot_without_shots() {
     {
         let mut coordinator = node.coordinator.write().await;
         let ships = create_valid_fleet();
-        let all_positions: Vec<(u8, u8)> = ships.iter()
+        let all_positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|ship| ship.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&all_positions, &salt);
-        coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+        coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     }
 
     let app = create_router(node.clone());
@@ -208,6 +222,8 @@ async fn test_fire_shot_without_shots() {
         player_id: "player1".to_string(),
         target_x: 5,
         target_y: 5,
+        weapon: Weapon::Single,
+        fee: 0,
     };
 
     let response1 = app
@@ -230,6 +246,8 @@ async fn test_fire_shot_without_shots() {
         player_id: "player1".to_string(),
         target_x: 6,
         target_y: 6,
+        weapon: Weapon::Single,
+        fee: 0,
     };
 
     let response2 = app
@@ -275,7 +293,10 @@ async fn test_add_peer() {
     let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
     let app = create_router(node.clone());
 
-    let peer = Peer::new("localhost".to_string(), 8081);
+    let announcement = PeerAnnouncement {
+        peer: Peer::new("localhost".to_string(), 8081),
+        genesis_hash: node.genesis_hash().await,
+    };
 
     let response = app
         .oneshot(
@@ -283,7 +304,7 @@ async fn test_add_peer() {
                 .method("POST")
                 .uri("/api/peers")
                 .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_string(&peer).unwrap()))
+                .body(Body::from(serde_json::to_string(&announcement).unwrap()))
                 .unwrap(),
         )
         .await
@@ -296,6 +317,79 @@ async fn test_add_peer() {
     assert_eq!(peers.len(), 1);
 }
 
+#[tokio::test]
+async fn test_add_peer_without_genesis_refused() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
+    let app = create_router(node.clone());
+
+    let peer = Peer::new("localhost".to_string(), 8081);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/peers")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&peer).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(response.status().is_client_error());
+    assert!(node.get_peers().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_add_peer_with_different_genesis_refused() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
+    let app = create_router(node.clone());
+
+    let other_genesis = GenesisConfig::new(12, 2);
+    let announcement = PeerAnnouncement {
+        peer: Peer::new("localhost".to_string(), 8081),
+        genesis_hash: Blockchain::from_genesis(&other_genesis).genesis_hash().to_string(),
+    };
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/peers")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&announcement).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(node.get_peers().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_genesis() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
+    let app = create_router(node.clone());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/genesis")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let info: GenesisInfo = serde_json::from_slice(&body).unwrap();
+    assert_eq!(info.hash, node.genesis_hash().await);
+    assert_eq!(info.config.game.grid_size, 10);
+}
+
 #[tokio::test]
 async fn test_get_node_info() {
     let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
@@ -337,7 +431,14 @@ async fn test_receive_transaction() {
     let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
     let app = create_router(node.clone());
 
-    let tx = Transaction::new("player1".to_string(), 5, 5, 0);
+    // A relayed shot spends one of the shooter's shots
+    let mut tx = Transaction::new("player1".to_string(), 5, 5, 0);
+    {
+        let mut coordinator = node.coordinator.write().await;
+        register(&mut coordinator, "player1", 0);
+        let account = coordinator.shot_account(DEFAULT_GAME_ID, "player1");
+        tx.spent_utxos = coordinator.blockchain.select_shots(&account, 1).unwrap();
+    }
 
     let response = app
         .oneshot(
@@ -355,20 +456,19 @@ async fn test_receive_transaction() {
     
     // Verify transaction was added
     let coordinator = node.coordinator.read().await;
-    assert_eq!(coordinator.blockchain.pending_transactions.len(), 1);
+    assert_eq!(coordinator.blockchain.mempool.len(), 1);
 }
 
 #[tokio::test]
 async fn test_receive_valid_block() {
     let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
     
-    // Create a valid block
-    let mut block = {
-        let coordinator = node.coordinator.read().await;
-        let latest = coordinator.blockchain.get_latest_block();
-        Block::new(1, vec![], latest.hash.clone())
+    // Create a valid block mined by a registered player
+    let block = {
+        let mut coordinator = node.coordinator.write().await;
+        register(&mut coordinator, "player1", 0);
+        mined_block(&coordinator, "player1", vec![])
     };
-    block.mine(2);
 
     let app = create_router(node.clone());
 
@@ -456,3 +556,308 @@ async fn test_sync_blockchain() {
     // Should succeed even with no peers
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn test_create_game_and_register_in_it() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
+    let app = create_router(node.clone());
+
+    let create_req = CreateGameRequest {
+        game_id: "arena".to_string(),
+        config: None,
+    };
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/games")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&create_req).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let ships = create_valid_fleet();
+    let all_positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|ship| ship.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let register_req = RegisterPlayerRequest {
+        player_id: "player1".to_string(),
+        board_commitment: create_commitment(&all_positions, &salt),
+        ships,
+        salt,
+        ship_root: None,
+        team: None,
+    };
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/games/arena/register")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&register_req).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let coordinator = node.coordinator.read().await;
+    assert_eq!(coordinator.game("arena").unwrap().players.len(), 1);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 0);
+}
+
+#[tokio::test]
+async fn test_unknown_game_stats_not_found() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
+    let app = create_router(node.clone());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/games/missing/stats")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_obligations() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 2));
+    {
+        let mut coordinator = node.coordinator.write().await;
+        for player_id in ["player1", "player2"] {
+            let ships = create_valid_fleet();
+            let positions: Vec<(u16, u16)> = ships.iter()
+                .flat_map(|s| s.positions.clone())
+                .collect();
+            let salt = generate_salt();
+            let commitment = create_commitment(&positions, &salt);
+            coordinator.register_player(DEFAULT_GAME_ID, player_id.to_string(), ships, commitment, salt).unwrap();
+        }
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    }
+    let app = create_router(node.clone());
+
+    let req = ObligationsRequest {
+        player_id: "player2".to_string(),
+    };
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/obligations")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&req).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let parsed: ApiResponse<Vec<ReportObligation>> = serde_json::from_slice(&body).unwrap();
+    let obligations = parsed.data.unwrap();
+    assert_eq!(obligations.len(), 1);
+    assert_eq!((obligations[0].shot_x, obligations[0].shot_y), (5, 5));
+}
+
+#[tokio::test]
+async fn test_report_hit_and_shot_results() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 1));
+    {
+        let mut coordinator = node.coordinator.write().await;
+        for (player_id, fleet) in [("player1", create_valid_fleet()), ("player2", create_valid_fleet())] {
+            let positions: Vec<(u16, u16)> = fleet.iter()
+                .flat_map(|s| s.positions.clone())
+                .collect();
+            let salt = generate_salt();
+            let commitment = create_commitment(&positions, &salt);
+            coordinator.register_player(DEFAULT_GAME_ID, player_id.to_string(), fleet, commitment, salt).unwrap();
+        }
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 0, 0).unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    }
+    let app = create_router(node.clone());
+
+    let proof = HitProof::prove_hit((0, 0), &[], "");
+    let report = HitReport::new("player2".to_string(), 0, 0, true, proof.serialize());
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/report")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&report).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    node.coordinator.write().await.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let req = ShotResultsRequest {
+        player_id: "player1".to_string(),
+    };
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/results")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&req).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let parsed: ApiResponse<Vec<ShotResult>> = serde_json::from_slice(&body).unwrap();
+    let results = parsed.data.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].hits, vec!["player2".to_string()]);
+}
+
+#[tokio::test]
+async fn test_report_without_obligation_rejected() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 1));
+    let app = create_router(node.clone());
+
+    let proof = HitProof::prove_hit((0, 0), &[], "");
+    let report = HitReport::new("player2".to_string(), 0, 0, true, proof.serialize());
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/report")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&report).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_turn_based_rounds_and_passing() {
+    let mut genesis = GenesisConfig::new(10, 2);
+    genesis.game.turns.mode = TurnMode::TurnBased;
+    let node = Arc::new(NetworkNode::from_genesis("test_node".to_string(), 8080, genesis));
+    let app = create_router(node.clone());
+
+    let ships = create_valid_fleet();
+    let all_positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|ship| ship.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&all_positions, &salt);
+    node.coordinator.write().await
+        .register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt)
+        .unwrap();
+
+    let response = app.clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/round")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let parsed: ApiResponse<u32> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(parsed.data, Some(1));
+
+    let pass = |player_id: &str| {
+        let req = TurnRequest { player_id: player_id.to_string() };
+        Request::builder()
+            .method("POST")
+            .uri("/api/pass")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&req).unwrap()))
+            .unwrap()
+    };
+    let response = app.clone().oneshot(pass("player2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = app.oneshot(pass("player1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Passing the only turn of round 1 started round 2
+    assert_eq!(node.coordinator.read().await.game(DEFAULT_GAME_ID).unwrap().round, 2);
+}
+
+#[tokio::test]
+async fn test_round_cannot_restart_before_everyone_has_played() {
+    let mut genesis = GenesisConfig::new(10, 2);
+    genesis.game.turns.mode = TurnMode::TurnBased;
+    genesis.rewards.registration_shots = 0;
+    let node = Arc::new(NetworkNode::from_genesis("test_node".to_string(), 8080, genesis));
+    let app = create_router(node.clone());
+
+    for player_id in ["player1", "player2"] {
+        let ships = create_valid_fleet();
+        let all_positions: Vec<(u16, u16)> = ships.iter()
+            .flat_map(|ship| ship.positions.clone())
+            .collect();
+        let salt = generate_salt();
+        let commitment = create_commitment(&all_positions, &salt);
+        node.coordinator.write().await
+            .register_player(DEFAULT_GAME_ID, player_id.to_string(), ships, commitment, salt)
+            .unwrap();
+    }
+
+    let round = || Request::builder()
+        .method("POST")
+        .uri("/api/round")
+        .body(Body::empty())
+        .unwrap();
+    let fire = |x: u16| {
+        let req = FireShotRequest { player_id: "player1".to_string(), target_x: x, target_y: 9, weapon: Weapon::Single, fee: 0 };
+        Request::builder()
+            .method("POST")
+            .uri("/api/fire")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&req).unwrap()))
+            .unwrap()
+    };
+
+    assert_eq!(app.clone().oneshot(round()).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(app.clone().oneshot(fire(5)).await.unwrap().status(), StatusCode::OK);
+
+    // player2 has not played yet, so player1 gets no second shot this round
+    assert_eq!(app.clone().oneshot(round()).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    assert_eq!(app.oneshot(fire(6)).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    let coordinator = node.coordinator.read().await;
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().round, 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 0);
+}
+
+#[tokio::test]
+async fn test_get_beacon() {
+    let node = Arc::new(NetworkNode::new("test_node".to_string(), 8080, 10, 1));
+    let app = create_router(node.clone());
+    let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app.clone().oneshot(get("/api/beacon?height=0&domain=dice")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let parsed: ApiResponse<Beacon> = serde_json::from_slice(&body).unwrap();
+    let expected = node.coordinator.read().await.blockchain.beacon(0, "dice");
+    assert_eq!(parsed.data, expected);
+
+    // The reveal window after block 0 has not closed yet
+    let response = app.oneshot(get("/api/beacon?height=0&domain=dice&committed=true")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,182 @@
+use fleetchain::audit::{adjust_standings, audit_reports};
+use fleetchain::blockchain::TransactionKind;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::crypto::{generate_salt, CountProof, HitProof};
+use fleetchain::game::{HitReport, DEFAULT_GAME_ID};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::lifecycle::GamePhase;
+use fleetchain::map::GameMap;
+use fleetchain::obligations::ReportObligation;
+use fleetchain::relocation::RevealedBoard;
+use fleetchain::weapons::Region;
+
+mod common;
+use common::{Board, register};
+
+/// Two players with no report deadline, so only the reports below decide the game
+fn two_player_game() -> (GameCoordinator, Board, Board) {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.reports.deadline_blocks = 0;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    let board1 = register(&mut coordinator, "player1", 0);
+    let board2 = register(&mut coordinator, "player2", 1);
+    (coordinator, board1, board2)
+}
+
+/// `shooter` fires at `pos`, the shot is mined and `target` answers with the given report
+fn fire_and_report(coordinator: &mut GameCoordinator, shooter: &str, pos: (u16, u16), report: HitReport) {
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, shooter).unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, shooter.to_string(), pos.0, pos.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, shooter).unwrap();
+    coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, shooter).unwrap();
+}
+
+fn honest_hit(player_id: &str, pos: (u16, u16)) -> HitReport {
+    let proof = HitProof::prove_hit(pos, &[], "");
+    HitReport::new(player_id.to_string(), pos.0, pos.1, true, proof.serialize())
+}
+
+/// player1 sinks every cell of player2's fleet in column 1
+fn sink_player2(coordinator: &mut GameCoordinator) {
+    for y in 0..10 {
+        fire_and_report(coordinator, "player1", (1, y), honest_hit("player2", (1, y)));
+    }
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::GameOver);
+}
+
+fn reveal(coordinator: &mut GameCoordinator, player_id: &str, board: &Board) {
+    coordinator.reveal_board(DEFAULT_GAME_ID, player_id, board.0.clone(), board.1.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, player_id).unwrap();
+}
+
+#[test]
+fn test_adjust_standings_orders_honest_then_silent_then_cheaters() {
+    let standings: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+    let adjusted = adjust_standings(&standings, &["a".to_string()], &["b".to_string()]);
+    assert_eq!(adjusted, vec!["c", "d", "b", "a"]);
+
+    assert_eq!(adjust_standings(&standings, &[], &[]), standings);
+}
+
+#[test]
+fn test_audit_checks_sonar_counts() {
+    let (positions, salt) = (vec![(1, 0), (1, 1), (1, 2)], generate_salt());
+    let board = RevealedBoard { positions: positions.clone(), salt: salt.clone() };
+    let map = GameMap::square(10);
+    let sonar = |shot_id: &str, center| ReportObligation::sonar(
+        shot_id.to_string(), "player1".to_string(), "player2".to_string(), center, Region::around(center, &map), 1,
+    );
+
+    let mut honest = sonar("a", (1, 1));
+    honest.fulfil_count(3, Some(CountProof::prove(Region::around((1, 1), &map), &positions, &salt)), 2);
+    let mut lie = sonar("b", (0, 0));
+    lie.fulfil_count(0, None, 2);
+
+    // A proof made without the board's salt does not open to the board, even with the right count
+    let mut forged = sonar("c", (1, 1));
+    let mut proof = CountProof::prove(Region::around((1, 1), &map), &[], &generate_salt());
+    proof.count = 3;
+    forged.fulfil_count(3, Some(proof), 2);
+
+    // A right count with no proof behind it is flagged too
+    let mut unproven = sonar("d", (1, 1));
+    unproven.fulfil_count(3, None, 2);
+
+    let found = audit_reports("player2", &board, &[honest, lie, forged, unproven]);
+    let shot_ids: Vec<&str> = found.iter().map(|d| d.shot_id.as_str()).collect();
+    assert_eq!(shot_ids, vec!["b", "c", "d"]);
+    assert_eq!(found[0].reported_count, Some(0));
+}
+
+#[test]
+fn test_reveal_rejected_before_game_over() {
+    let (mut coordinator, board1, _) = two_player_game();
+    let err = coordinator.reveal_board(DEFAULT_GAME_ID, "player1", board1.0, board1.1).unwrap_err();
+    assert!(err.contains("Battle"));
+    assert!(coordinator.audit(DEFAULT_GAME_ID).unwrap_err().contains("not over"));
+}
+
+#[test]
+fn test_reveal_must_match_commitment() {
+    let (mut coordinator, board1, board2) = two_player_game();
+    sink_player2(&mut coordinator);
+
+    let err = coordinator.reveal_board(DEFAULT_GAME_ID, "player1", board2.0, board1.1.clone()).unwrap_err();
+    assert!(err.contains("does not match the commitment"));
+
+    coordinator.reveal_board(DEFAULT_GAME_ID, "player1", board1.0.clone(), board1.1.clone()).unwrap();
+    let err = coordinator.reveal_board(DEFAULT_GAME_ID, "player1", board1.0.clone(), board1.1.clone()).unwrap_err();
+    assert!(err.contains("already pending"));
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    let err = coordinator.reveal_board(DEFAULT_GAME_ID, "player1", board1.0, board1.1).unwrap_err();
+    assert!(err.contains("already revealed"));
+}
+
+#[test]
+fn test_honest_reveals_keep_standings() {
+    let (mut coordinator, board1, board2) = two_player_game();
+    sink_player2(&mut coordinator);
+    reveal(&mut coordinator, "player1", &board1);
+    reveal(&mut coordinator, "player2", &board2);
+
+    let mined = &coordinator.blockchain.chain.last().unwrap().transactions;
+    assert!(mined.iter().any(|tx| matches!(tx.kind, TransactionKind::BoardReveal { .. })));
+
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert!(audit.discrepancies.is_empty());
+    assert!(audit.cheaters.is_empty());
+    assert!(audit.unrevealed.is_empty());
+    assert_eq!(audit.standings, vec!["player1", "player2"]);
+    assert_eq!(audit.winner, Some("player1".to_string()));
+}
+
+#[test]
+fn test_lying_report_demotes_the_cheater() {
+    let (mut coordinator, board1, board2) = two_player_game();
+
+    // player1 claims a miss on a cell that holds their Carrier
+    let lie = HitProof::prove_miss((0, 0), &board1.0, &board1.1);
+    let report = HitReport::new("player1".to_string(), 0, 0, false, lie.serialize());
+    fire_and_report(&mut coordinator, "player2", (0, 0), report);
+
+    sink_player2(&mut coordinator);
+    let outcome = coordinator.game(DEFAULT_GAME_ID).unwrap().lifecycle.outcome.clone().unwrap();
+    assert_eq!(outcome.winner, Some("player1".to_string()));
+
+    reveal(&mut coordinator, "player1", &board1);
+    reveal(&mut coordinator, "player2", &board2);
+
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(audit.discrepancies.len(), 1);
+    assert_eq!((audit.discrepancies[0].shot_x, audit.discrepancies[0].shot_y), (0, 0));
+    assert!(!audit.discrepancies[0].reported_hit);
+    assert_eq!(audit.cheaters, vec!["player1"]);
+    assert_eq!(audit.standings, vec!["player2", "player1"]);
+    assert_eq!(audit.winner, Some("player2".to_string()));
+    assert_eq!(coordinator.get_stats(DEFAULT_GAME_ID).unwrap().winner, Some("player2".to_string()));
+}
+
+#[test]
+fn test_unrevealed_player_flagged_after_reveal_window() {
+    let (mut coordinator, _, board2) = two_player_game();
+    sink_player2(&mut coordinator);
+    reveal(&mut coordinator, "player2", &board2);
+
+    // player1 is unrevealed but still wins while the window is open
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(audit.unrevealed, vec!["player1"]);
+    assert!(!audit.finalized);
+    assert_eq!(audit.winner, Some("player1".to_string()));
+
+    let reveal_blocks = coordinator.game(DEFAULT_GAME_ID).unwrap().config.phases.reveal_blocks;
+    for _ in 0..reveal_blocks {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    }
+
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert!(audit.finalized);
+    assert_eq!(audit.standings, vec!["player2", "player1"]);
+    assert_eq!(audit.winner, Some("player2".to_string()));
+}
@@ -0,0 +1,102 @@
+use fleetchain::beacon::{beacon_commitment, Beacon};
+use fleetchain::blockchain::{Blockchain, Transaction};
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+
+mod common;
+use common::register;
+
+fn two_players() -> GameCoordinator {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator
+}
+
+fn mine(coordinator: &mut GameCoordinator) -> u64 {
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    coordinator.current_height()
+}
+
+#[test]
+fn test_beacon_depends_on_height_and_domain() {
+    let mut blockchain = Blockchain::new(1);
+    blockchain.mine_pending_transactions("miner1");
+
+    let beacon = blockchain.beacon(1, "turns").unwrap();
+    assert_eq!(beacon, blockchain.beacon(1, "turns").unwrap());
+    assert_eq!(beacon, Beacon::from_block_hash(1, "turns", &blockchain.chain[1].hash));
+    assert_ne!(beacon.value, blockchain.beacon(1, "events").unwrap().value);
+    assert_ne!(beacon.value, blockchain.beacon(0, "turns").unwrap().value);
+    assert!(blockchain.beacon(2, "turns").is_none());
+}
+
+#[test]
+fn test_draws_are_in_range_and_uniform() {
+    let beacon = Beacon::from_block_hash(7, "dice", "00ab");
+    let mut counts = [0usize; 6];
+    for i in 0..6000 {
+        counts[beacon.random_below(i, 6) as usize] += 1;
+    }
+    assert!(counts.iter().all(|&c| (850..1150).contains(&c)), "{:?}", counts);
+    assert_eq!(beacon.random_below(3, 1), 0);
+    assert_eq!(beacon.random_below(3, 0), 0);
+
+    let mut items: Vec<u32> = (0..20).collect();
+    beacon.shuffle(&mut items);
+    assert_ne!(items, (0..20).collect::<Vec<u32>>());
+    items.sort();
+    assert_eq!(items, (0..20).collect::<Vec<u32>>());
+}
+
+#[test]
+fn test_committed_beacon_mixes_revealed_secrets() {
+    let mut coordinator = two_players();
+    let commitment = coordinator.commit_beacon_secret(DEFAULT_GAME_ID, "player1", "loot", "s3cret").unwrap();
+    assert_eq!(commitment, beacon_commitment("loot", "default/player1", "s3cret"));
+    coordinator.commit_beacon_secret(DEFAULT_GAME_ID, "player2", "loot", "other").unwrap();
+    let height = mine(&mut coordinator);
+
+    // player2 reveals the wrong secret, then the right one too late
+    coordinator.reveal_beacon_secret(DEFAULT_GAME_ID, "player1", "loot", "s3cret").unwrap();
+    coordinator.reveal_beacon_secret(DEFAULT_GAME_ID, "player2", "loot", "wrong").unwrap();
+    mine(&mut coordinator);
+    mine(&mut coordinator);
+    assert!(coordinator.blockchain.committed_beacon(height, "loot").is_none());
+    mine(&mut coordinator);
+    assert!(coordinator.blockchain.committed_beacon(height, "loot").is_some());
+    coordinator.reveal_beacon_secret(DEFAULT_GAME_ID, "player2", "loot", "other").unwrap();
+    mine(&mut coordinator);
+
+    let blockchain = &coordinator.blockchain;
+    let committed = blockchain.committed_beacon(height, "loot").unwrap();
+    assert_eq!(committed.reveals, 1);
+    let plain = blockchain.beacon(height, "loot").unwrap();
+    assert_eq!(committed, plain.mix(&[("default/player1".to_string(), "s3cret".to_string())]));
+    assert_ne!(committed.value, plain.value);
+    assert_eq!(blockchain.committed_beacon(height, "other").unwrap().value, blockchain.beacon(height, "other").unwrap().mix(&[]).value);
+}
+
+#[test]
+fn test_reveals_must_match_the_senders_commitment() {
+    let mut coordinator = two_players();
+    coordinator.commit_beacon_secret(DEFAULT_GAME_ID, "player1", "loot", "s3cret").unwrap();
+    let height = mine(&mut coordinator);
+
+    // player2 replays player1's secret under their own name
+    let replay = Transaction::beacon_reveal(DEFAULT_GAME_ID, "player2", "loot", "s3cret");
+    coordinator.blockchain.add_transaction(replay).unwrap();
+    for _ in 0..3 {
+        mine(&mut coordinator);
+    }
+    assert_eq!(coordinator.blockchain.committed_beacon(height, "loot").unwrap().reveals, 0);
+    assert!(coordinator.commit_beacon_secret(DEFAULT_GAME_ID, "nobody", "loot", "x").is_err());
+}
+
+#[test]
+fn test_reveal_window_must_be_positive() {
+    let mut genesis = GenesisConfig::default();
+    genesis.consensus.beacon.reveal_blocks = 0;
+    assert!(genesis.validate().unwrap_err().contains("reveal_blocks"));
+}
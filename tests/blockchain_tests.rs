@@ -22,28 +22,28 @@ fn test_add_transaction() {
     let mut blockchain = Blockchain::new(2);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
     
-    blockchain.add_transaction(tx);
-    assert_eq!(blockchain.pending_transactions.len(), 1);
+    blockchain.add_transaction(tx).unwrap();
+    assert_eq!(blockchain.mempool.len(), 1);
 }
 
 #[test]
 fn test_mining_creates_new_block() {
     let mut blockchain = Blockchain::new(2);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
-    blockchain.add_transaction(tx);
+    blockchain.add_transaction(tx).unwrap();
     
     let initial_length = blockchain.chain.len();
     blockchain.mine_pending_transactions("miner1");
     
     assert_eq!(blockchain.chain.len(), initial_length + 1);
-    assert_eq!(blockchain.pending_transactions.len(), 0);
+    assert_eq!(blockchain.mempool.len(), 0);
 }
 
 #[test]
 fn test_mined_block_has_correct_proof_of_work() {
     let mut blockchain = Blockchain::new(3);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
-    blockchain.add_transaction(tx);
+    blockchain.add_transaction(tx).unwrap();
     
     blockchain.mine_pending_transactions("miner1");
     let latest_block = blockchain.get_latest_block();
@@ -82,7 +82,7 @@ fn test_blockchain_rejects_invalid_previous_hash() {
 fn test_blockchain_rejects_tampered_block() {
     let mut blockchain = Blockchain::new(2);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
-    blockchain.add_transaction(tx);
+    blockchain.add_transaction(tx).unwrap();
     blockchain.mine_pending_transactions("miner1");
     
     // Tamper with a block
@@ -98,8 +98,8 @@ fn test_blockchain_with_multiple_blocks() {
     let mut blockchain = Blockchain::new(2);
     
     for i in 0..5 {
-        let tx = Transaction::new(format!("player{}", i), i as u8, i as u8, 0);
-        blockchain.add_transaction(tx);
+        let tx = Transaction::new(format!("player{}", i), i as u16, i as u16, 0);
+        blockchain.add_transaction(tx).unwrap();
         blockchain.mine_pending_transactions(&format!("miner{}", i));
     }
     
@@ -136,7 +136,7 @@ fn test_block_serialization() {
 fn test_blockchain_serialization() {
     let mut blockchain = Blockchain::new(2);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
-    blockchain.add_transaction(tx);
+    blockchain.add_transaction(tx).unwrap();
     blockchain.mine_pending_transactions("miner1");
     
     let json = serde_json::to_string(&blockchain).unwrap();
@@ -154,8 +154,8 @@ fn test_mining_difficulty_affects_hash() {
     let tx1 = Transaction::new("player1".to_string(), 5, 5, 0);
     let tx2 = Transaction::new("player1".to_string(), 5, 5, 0);
     
-    blockchain_easy.add_transaction(tx1);
-    blockchain_hard.add_transaction(tx2);
+    blockchain_easy.add_transaction(tx1).unwrap();
+    blockchain_hard.add_transaction(tx2).unwrap();
     
     blockchain_easy.mine_pending_transactions("miner1");
     blockchain_hard.mine_pending_transactions("miner1");
@@ -172,8 +172,8 @@ fn test_get_transaction_count() {
     let mut blockchain = Blockchain::new(2);
     
     for i in 0..3 {
-        let tx = Transaction::new(format!("player{}", i), i as u8, i as u8, 0);
-        blockchain.add_transaction(tx);
+        let tx = Transaction::new(format!("player{}", i), i as u16, i as u16, 0);
+        blockchain.add_transaction(tx).unwrap();
         blockchain.mine_pending_transactions(&format!("miner{}", i));
     }
     
@@ -203,6 +203,35 @@ fn test_consume_shot_utxo() {
     assert!(blockchain.consume_shot("player1").is_err());
 }
 
+#[test]
+fn test_escrowed_shots_cannot_be_spent() {
+    let mut blockchain = Blockchain::new(2);
+    blockchain.award_registration_shot("player1");
+    blockchain.mine_pending_transactions("player1");
+    assert_eq!(blockchain.get_unspent_shots("player1"), 2);
+
+    let bond: Vec<String> = blockchain.shot_utxos.iter().map(|u| u.id.clone()).collect();
+    blockchain.escrow_utxos(&bond, "challenge");
+    assert_eq!(blockchain.get_unspent_shots("player1"), 0);
+    assert_eq!(blockchain.escrowed_shots("challenge"), 2);
+    assert!(blockchain.consume_shot("player1").is_err());
+
+    // Releasing the escrow hands the shots to the winner of the challenge
+    blockchain.release_escrow("challenge", "player2");
+    assert_eq!(blockchain.escrowed_shots("challenge"), 0);
+    assert_eq!(blockchain.get_unspent_shots("player2"), 2);
+}
+
+#[test]
+fn test_transfer_shots_moves_at_most_unspent() {
+    let mut blockchain = Blockchain::new(2);
+    blockchain.award_registration_shot("player1");
+
+    assert_eq!(blockchain.transfer_shots("player1", "player2", 3), 1);
+    assert_eq!(blockchain.get_unspent_shots("player1"), 0);
+    assert_eq!(blockchain.get_unspent_shots("player2"), 1);
+}
+
 #[test]
 fn test_empty_block_mining() {
     let mut blockchain = Blockchain::new(2);
@@ -217,21 +246,21 @@ fn test_multiple_transactions_per_block() {
     let mut blockchain = Blockchain::new(2);
     
     for i in 0..5 {
-        let tx = Transaction::new(format!("player{}", i), i as u8, i as u8, 0);
-        blockchain.add_transaction(tx);
+        let tx = Transaction::new(format!("player{}", i), i as u16, i as u16, 0);
+        blockchain.add_transaction(tx).unwrap();
     }
     
     blockchain.mine_pending_transactions("miner1");
     
     assert_eq!(blockchain.chain[1].transactions.len(), 5);
-    assert_eq!(blockchain.pending_transactions.len(), 0);
+    assert_eq!(blockchain.mempool.len(), 0);
 }
 
 #[test]
 fn test_blockchain_immutability() {
     let mut blockchain = Blockchain::new(2);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
-    blockchain.add_transaction(tx);
+    blockchain.add_transaction(tx).unwrap();
     blockchain.mine_pending_transactions("miner1");
     
     let original_hash = blockchain.chain[1].hash.clone();
@@ -255,8 +284,8 @@ fn test_blockchain_persistence_save_and_load() {
     let mut blockchain = Blockchain::new(2);
     let tx1 = Transaction::new("player1".to_string(), 5, 5, 0);
     let tx2 = Transaction::new("player2".to_string(), 3, 7, 0);
-    blockchain.add_transaction(tx1);
-    blockchain.add_transaction(tx2);
+    blockchain.add_transaction(tx1).unwrap();
+    blockchain.add_transaction(tx2).unwrap();
     blockchain.mine_pending_transactions("miner1");
     
     let original_length = blockchain.chain.len();
@@ -308,7 +337,7 @@ fn test_blockchain_persistence_validates_on_load() {
     // Create a blockchain and save it
     let mut blockchain = Blockchain::new(2);
     let tx = Transaction::new("player1".to_string(), 5, 5, 0);
-    blockchain.add_transaction(tx);
+    blockchain.add_transaction(tx).unwrap();
     blockchain.mine_pending_transactions("miner1");
     blockchain.save_to_file(&test_path).expect("Failed to save blockchain");
     
@@ -345,8 +374,8 @@ fn test_blockchain_persistence_with_multiple_blocks() {
     // Create blockchain with multiple blocks
     let mut blockchain = Blockchain::new(2);
     for i in 0..5 {
-        let tx = Transaction::new(format!("player{}", i), i as u8, i as u8, 0);
-        blockchain.add_transaction(tx);
+        let tx = Transaction::new(format!("player{}", i), i as u16, i as u16, 0);
+        blockchain.add_transaction(tx).unwrap();
         blockchain.mine_pending_transactions(&format!("miner{}", i));
     }
     
@@ -369,3 +398,35 @@ fn test_blockchain_persistence_with_multiple_blocks() {
     // Clean up
     fs::remove_file(&test_path).ok();
 }
+
+#[test]
+fn test_expired_shots_cannot_be_spent() {
+    let mut blockchain = Blockchain::new(1);
+    blockchain.rewards.shot_lifetime = 2;
+    blockchain.award_registration_shot("player1");
+    blockchain.award_registration_shots("player1", 1, None);
+    assert_eq!(blockchain.shot_utxos[0].expires_at, Some(2));
+
+    let balance = blockchain.shot_balance("player1");
+    assert_eq!((balance.spendable, balance.expiring, balance.next_expiry), (2, 1, Some(2)));
+
+    // The shot closest to expiring is spent first
+    let expiring_id = blockchain.shot_utxos[0].id.clone();
+    assert_eq!(blockchain.consume_shot("player1").unwrap(), expiring_id);
+
+    blockchain.award_registration_shot("player2");
+    let expired_id = blockchain.shot_utxos[2].id.clone();
+    blockchain.mine_pending_transactions("player2");
+    blockchain.mine_pending_transactions("player2");
+    assert_eq!(blockchain.get_unspent_shots("player2"), 1);
+    assert_eq!(blockchain.shot_balance("player2").next_expiry, Some(4));
+
+    // A pending transaction spending an expired shot is left out of the next block
+    let mut transaction = Transaction::new("player2".to_string(), 1, 1, 0);
+    transaction.spent_utxos = vec![expired_id];
+    assert!(blockchain.check_spends(&transaction, "player2", 1, 3).unwrap_err().contains("expired"));
+    blockchain.add_transaction(transaction).unwrap();
+    blockchain.mine_pending_transactions("player2");
+    assert!(blockchain.get_latest_block().transactions.is_empty());
+    assert!(blockchain.is_chain_valid());
+}
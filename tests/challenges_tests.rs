@@ -0,0 +1,266 @@
+use fleetchain::challenges::ChallengeStatus;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::{HitReport, DEFAULT_GAME_ID};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::weapons::Weapon;
+
+mod common;
+use common::{Board, register};
+
+fn optimistic_genesis() -> GenesisConfig {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.reports.deadline_blocks = 0;
+    genesis.game.challenges.optimistic = true;
+    genesis
+}
+
+/// player1 fires at `shot`, player2 answers with an unproven report and both are mined
+///
+/// Returns the shot ID and player2's board.
+fn game_with_unproven_report(shot: (u16, u16), is_hit: bool) -> (GameCoordinator, String, Board) {
+    let mut coordinator = GameCoordinator::from_genesis(optimistic_genesis());
+    register(&mut coordinator, "player1", 0);
+    let board2 = register(&mut coordinator, "player2", 1);
+
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), shot.0, shot.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    let shot_id = coordinator.blockchain.chain[1].transactions[0].hash();
+
+    let report = HitReport::new("player2".to_string(), shot.0, shot.1, is_hit, Vec::new());
+    coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    (coordinator, shot_id, board2)
+}
+
+#[test]
+fn test_reports_need_a_proof_outside_optimistic_mode() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 1, 0).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+
+    let report = HitReport::new("player2".to_string(), 1, 0, false, Vec::new());
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap_err();
+    assert!(err.contains("Invalid proof format"));
+}
+
+#[test]
+fn test_optimistic_report_accepted_without_proof() {
+    let (coordinator, shot_id, _) = game_with_unproven_report((1, 0), false);
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    let obligation = game.obligations.iter().find(|o| o.shot_id == shot_id && o.player_id == "player2").unwrap();
+    assert_eq!(obligation.is_hit, Some(false));
+    assert!(game.players["player2"].hit_cells.is_empty());
+}
+
+#[test]
+fn test_challenge_locks_bond_in_escrow() {
+    let (mut coordinator, shot_id, _) = game_with_unproven_report((1, 0), false);
+    let unspent = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1");
+
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), unspent - 1);
+    assert_eq!(coordinator.blockchain.escrowed_shots(&challenge_id), 1);
+
+    let err = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap_err();
+    assert!(err.contains("already pending"));
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let challenges = coordinator.challenges(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(challenges.len(), 1);
+    assert_eq!(challenges[0].id, challenge_id);
+    assert_eq!(challenges[0].status, ChallengeStatus::Open);
+    assert_eq!(challenges[0].deadline_block, 3 + 5);
+    assert!(!challenges[0].reported_hit);
+
+    let err = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap_err();
+    assert!(err.contains("already challenged"));
+}
+
+#[test]
+fn test_challenge_names_its_bond_utxos() {
+    let (mut coordinator, shot_id, _) = game_with_unproven_report((1, 0), false);
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+    let challenge = coordinator.blockchain.mempool.transactions()[0].clone();
+    assert_eq!(challenge.hash(), challenge_id);
+    let escrowed: Vec<String> = coordinator.blockchain.shot_utxos.iter()
+        .filter(|u| u.escrow.as_deref() == Some(challenge_id.as_str()))
+        .map(|u| u.id.clone())
+        .collect();
+    assert_eq!(challenge.spent_utxos, escrowed);
+
+    // A peer that did not create the challenge holds the very same UTXOs once it is mined
+    let mut peer = GameCoordinator::from_genesis(optimistic_genesis());
+    register(&mut peer, "player1", 0);
+    register(&mut peer, "player2", 1);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    for block in coordinator.blockchain.chain[1..].iter().cloned() {
+        peer.accept_block(block).unwrap();
+    }
+    let held: Vec<String> = peer.blockchain.shot_utxos.iter()
+        .filter(|u| u.escrow.as_deref() == Some(challenge_id.as_str()))
+        .map(|u| u.id.clone())
+        .collect();
+    assert_eq!(held, escrowed);
+}
+
+#[test]
+fn test_invalid_challenges_rejected() {
+    let (mut coordinator, shot_id, _) = game_with_unproven_report((1, 0), false);
+
+    let err = coordinator.challenge_report(DEFAULT_GAME_ID, "player2", "player2", &shot_id).unwrap_err();
+    assert!(err.contains("own reports"));
+    let err = coordinator.challenge_report(DEFAULT_GAME_ID, "player2", "player1", &shot_id).unwrap_err();
+    assert!(err.contains("no mined report"));
+
+    // The bond must be covered by unspent shots
+    while coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1") > 0 {
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 9, 9).unwrap();
+    }
+    let err = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap_err();
+    assert!(err.contains("Bond"));
+}
+
+#[test]
+fn test_false_report_upheld_and_slashed() {
+    let (mut coordinator, shot_id, board2) = game_with_unproven_report((1, 0), false);
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let shots1 = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1");
+    let shots2 = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2");
+
+    coordinator.respond_to_challenge(DEFAULT_GAME_ID, "player2", &challenge_id, board2.0, board2.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+
+    let challenge = &coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!(challenge.status, ChallengeStatus::Upheld);
+    assert_eq!(challenge.revealed_hit, Some(true));
+
+    // The lie is corrected on every node
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert!(game.players["player2"].hit_cells.contains(&(1, 0)));
+    let obligation = game.obligations.iter().find(|o| o.shot_id == shot_id && o.player_id == "player2").unwrap();
+    assert_eq!(obligation.is_hit, Some(true));
+
+    // The bond comes back and one of player2's shots goes to player1 (player2 also mined a block)
+    assert_eq!(coordinator.blockchain.escrowed_shots(&challenge_id), 0);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), shots1 + 2);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), shots2);
+}
+
+#[test]
+fn test_honest_report_keeps_the_bond() {
+    let (mut coordinator, shot_id, board2) = game_with_unproven_report((5, 5), false);
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let shots1 = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1");
+    let shots2 = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2");
+
+    coordinator.respond_to_challenge(DEFAULT_GAME_ID, "player2", &challenge_id, board2.0, board2.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let challenge = &coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!(challenge.status, ChallengeStatus::Rejected);
+    assert_eq!(challenge.revealed_hit, Some(false));
+    assert!(coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].hit_cells.is_empty());
+
+    // player1 only gets the block reward; the bond goes to player2
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), shots1 + 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), shots2 + 1);
+}
+
+#[test]
+fn test_false_hit_claim_overturned() {
+    let (mut coordinator, shot_id, board2) = game_with_unproven_report((5, 5), true);
+    assert!(coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].hit_cells.contains(&(5, 5)));
+
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.respond_to_challenge(DEFAULT_GAME_ID, "player2", &challenge_id, board2.0, board2.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let challenge = &coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!(challenge.status, ChallengeStatus::Upheld);
+    assert!(coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].hit_cells.is_empty());
+}
+
+#[test]
+fn test_response_must_open_the_committed_board() {
+    let (mut coordinator, shot_id, board2) = game_with_unproven_report((1, 0), false);
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+
+    // The challenge has to be mined before it can be answered
+    let err = coordinator.respond_to_challenge(DEFAULT_GAME_ID, "player2", &challenge_id, board2.0.clone(), board2.1.clone()).unwrap_err();
+    assert!(err.contains("not found"));
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let err = coordinator.respond_to_challenge(DEFAULT_GAME_ID, "player2", &challenge_id, vec![(5, 5)], board2.1.clone()).unwrap_err();
+    assert!(err.contains("does not match the commitment"));
+    let err = coordinator.respond_to_challenge(DEFAULT_GAME_ID, "player1", &challenge_id, board2.0.clone(), board2.1.clone()).unwrap_err();
+    assert!(err.contains("is not against player player1"));
+}
+
+#[test]
+fn test_silent_reporter_slashed_after_response_window() {
+    let (mut coordinator, shot_id, _) = game_with_unproven_report((1, 0), false);
+    let challenge_id = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", &shot_id).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let deadline = coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0].deadline_block;
+    while coordinator.current_height() < deadline {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    }
+    assert!(coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0].is_open());
+    let shots2 = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2");
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let challenge = &coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!(challenge.status, ChallengeStatus::Upheld);
+    assert_eq!(challenge.revealed_hit, None);
+    assert_eq!(challenge.resolved_in_block, Some(deadline + 1));
+    assert_eq!(coordinator.blockchain.escrowed_shots(&challenge_id), 0);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), shots2 - 1);
+    assert!(coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].hit_cells.contains(&(1, 0)));
+}
+
+#[test]
+fn test_challenges_need_optimistic_mode() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    let err = coordinator.challenge_report(DEFAULT_GAME_ID, "player1", "player2", "shot").unwrap_err();
+    assert!(err.contains("optimistic"));
+}
+
+#[test]
+fn test_challenge_one_cell_of_a_bomb() {
+    let mut coordinator = GameCoordinator::from_genesis(optimistic_genesis());
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    for _ in 0..4 {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    }
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player1".to_string(), 2, 1, Weapon::Bomb).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    let shot_id = coordinator.blockchain.get_latest_block().transactions[0].hash();
+
+    // player2 claims every cell of the bomb missed, including its Carrier at (1, 1)
+    for obligation in coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap() {
+        let report = HitReport::new("player2".to_string(), obligation.shot_x, obligation.shot_y, false, Vec::new());
+        coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap();
+    }
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    coordinator.challenge_report_at(DEFAULT_GAME_ID, "player1", "player2", &shot_id, Some((1, 1))).unwrap();
+    let err = coordinator.challenge_report_at(DEFAULT_GAME_ID, "player1", "player2", &shot_id, Some((9, 9))).unwrap_err();
+    assert!(err.contains("no mined report"));
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let challenge = &coordinator.challenges(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!((challenge.shot_x, challenge.shot_y), (1, 1));
+
+    // Another cell of the same shot can still be challenged
+    coordinator.challenge_report_at(DEFAULT_GAME_ID, "player1", "player2", &shot_id, Some((1, 2))).unwrap();
+}
@@ -0,0 +1,85 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use fleetchain::blockchain::{Block, Transaction};
+use fleetchain::coinbase::Coinbase;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::crypto::{generate_salt, create_commitment};
+use fleetchain::game::{Ship, DEFAULT_GAME_ID};
+
+/// Ship cells and salt behind a player's board commitment
+pub type Board = (Vec<(u16, u16)>, String);
+
+/// Default fleet standing in column `x`, rows 0 to 9
+pub fn fleet_in_column(x: u16) -> Vec<Ship> {
+    vec![
+        Ship::new("Carrier".to_string(), vec![(x, 0), (x, 1), (x, 2), (x, 3)]),
+        Ship::new("Cruiser".to_string(), vec![(x, 4), (x, 5), (x, 6)]),
+        Ship::new("Submarine".to_string(), vec![(x, 7), (x, 8)]),
+        Ship::new("Destroyer".to_string(), vec![(x, 9)]),
+    ]
+}
+
+/// Register a player of a game, optionally in a team, with the fleet in column `x`
+pub fn try_register_in(
+    coordinator: &mut GameCoordinator,
+    game_id: &str,
+    player_id: &str,
+    x: u16,
+    team: Option<&str>,
+) -> Result<Board, String> {
+    let ships = fleet_in_column(x);
+    let positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|s| s.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    coordinator.register_player_in_team(
+        game_id,
+        player_id.to_string(),
+        ships,
+        commitment,
+        salt.clone(),
+        None,
+        team.map(str::to_string),
+    )?;
+    Ok((positions, salt))
+}
+
+/// Register a player of the default game with the fleet in column `x`
+pub fn try_register(coordinator: &mut GameCoordinator, player_id: &str, x: u16) -> Result<Board, String> {
+    try_register_in(coordinator, DEFAULT_GAME_ID, player_id, x, None)
+}
+
+/// Register a player of the default game with the fleet in column `x`, returning their board
+pub fn register(coordinator: &mut GameCoordinator, player_id: &str, x: u16) -> Board {
+    try_register(coordinator, player_id, x).unwrap()
+}
+
+/// Register a player of another game with the fleet in column `x`
+pub fn register_in_game(coordinator: &mut GameCoordinator, game_id: &str, player_id: &str, x: u16) -> Board {
+    try_register_in(coordinator, game_id, player_id, x, None).unwrap()
+}
+
+/// Register a player of the default game in a team with the fleet in column `x`, returning their board
+pub fn register_in_team(coordinator: &mut GameCoordinator, player_id: &str, x: u16, team: Option<&str>) -> Board {
+    try_register_in_team(coordinator, player_id, x, team).unwrap()
+}
+
+/// Register a player of the default game in a team with the fleet in column `x`
+pub fn try_register_in_team(coordinator: &mut GameCoordinator, player_id: &str, x: u16, team: Option<&str>) -> Result<Board, String> {
+    try_register_in(coordinator, DEFAULT_GAME_ID, player_id, x, team)
+}
+
+/// Block holding `transactions` on top of the coordinator's latest block, mined by
+/// a player of the default game as a peer would mine it
+pub fn mined_block(coordinator: &GameCoordinator, miner: &str, transactions: Vec<Transaction>) -> Block {
+    let latest = coordinator.blockchain.get_latest_block();
+    let mut block = Block::new(latest.index + 1, transactions, latest.hash.clone());
+    let mut coinbase = Coinbase::solo(DEFAULT_GAME_ID, miner);
+    coinbase.payouts = coordinator.coinbase_payouts(&coinbase, block.index, &block.transactions).unwrap();
+    block.coinbase = Some(coinbase);
+    block.hash = block.calculate_hash();
+    block.mine(coordinator.blockchain.difficulty);
+    block
+}
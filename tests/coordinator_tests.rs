@@ -1,6 +1,7 @@
 use fleetchain::coordinator::GameCoordinator;
-use fleetchain::game::Ship;
-use fleetchain::crypto::{generate_salt, create_commitment};
+use fleetchain::game::{Ship, HitReport, DEFAULT_GAME_ID};
+use fleetchain::crypto::{generate_salt, create_commitment, HitProof};
+use fleetchain::lifecycle::GamePhase;
 
 // Helper function to create a valid 4-ship fleet
 fn create_valid_fleet() -> Vec<Ship> {
@@ -15,10 +16,10 @@ fn create_valid_fleet() -> Vec<Ship> {
 #[test]
 fn test_coordinator_creation() {
     let coordinator = GameCoordinator::new(10, 2);
-    assert_eq!(coordinator.grid.size, 10);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().grid.map.width, 10);
     assert_eq!(coordinator.blockchain.difficulty, 2);
-    assert_eq!(coordinator.players.len(), 0);
-    assert_eq!(coordinator.round, 0);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 0);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().round, 0);
 }
 
 #[test]
@@ -26,13 +27,14 @@ fn test_register_player() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -40,7 +42,7 @@ fn test_register_player() {
     );
     
     assert!(result.is_ok());
-    assert_eq!(coordinator.players.len(), 1);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 1);
 }
 
 #[test]
@@ -52,6 +54,7 @@ fn test_register_player_invalid_commitment() {
     let wrong_commitment = "wrong_commitment_hash".to_string();
     
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         wrong_commitment,
@@ -59,7 +62,7 @@ fn test_register_player_invalid_commitment() {
     );
     
     assert!(result.is_err());
-    assert_eq!(coordinator.players.len(), 0);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 0);
 }
 
 #[test]
@@ -73,13 +76,14 @@ fn test_register_multiple_players() {
             Ship::new(format!("Submarine{}", i), vec![(i, 7), (i, 8)]),
             Ship::new(format!("Destroyer{}", i), vec![(i, 9)]),
         ];
-        let positions: Vec<(u8, u8)> = ships.iter()
+        let positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|s| s.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&positions, &salt);
         
         coordinator.register_player(
+            DEFAULT_GAME_ID,
             format!("player{}", i),
             ships,
             commitment,
@@ -87,7 +91,7 @@ fn test_register_multiple_players() {
         ).unwrap();
     }
     
-    assert_eq!(coordinator.players.len(), 3);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 3);
 }
 
 #[test]
@@ -95,30 +99,31 @@ fn test_mine_for_shots() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
         salt,
     ).unwrap();
     
-    let shots = coordinator.mine_for_shots("player1").unwrap();
+    let shots = coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     assert!(shots > 0);
 
     // Registration awards 1 shot, mining adds `shots` more
-    assert_eq!(coordinator.blockchain.get_unspent_shots("player1"), (1 + shots) as usize);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), (1 + shots) as usize);
 }
 
 #[test]
 fn test_mine_for_nonexistent_player() {
     let mut coordinator = GameCoordinator::new(10, 2);
-    let result = coordinator.mine_for_shots("nonexistent");
+    let result = coordinator.mine_for_shots(DEFAULT_GAME_ID, "nonexistent");
     assert!(result.is_err());
 }
 
@@ -127,13 +132,14 @@ fn test_fire_shot() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -141,21 +147,21 @@ fn test_fire_shot() {
     ).unwrap();
     
     // After registration, player already has 1 shot UTXO
-    assert_eq!(coordinator.blockchain.get_unspent_shots("player1"), 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
 
     // Mine to get additional shots (creates UTXOs)
-    let mined = coordinator.mine_for_shots("player1").unwrap();
+    let mined = coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     assert!(mined > 0);
 
     // Fire shot (spends one UTXO)
-    let result = coordinator.fire_shot("player1".to_string(), 5, 5);
+    let result = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5);
     assert!(result.is_ok());
 
     // Check transaction was added
-    assert_eq!(coordinator.blockchain.pending_transactions.len(), 1);
+    assert_eq!(coordinator.blockchain.mempool.len(), 1);
 
     // Player should now have registration + mined - 1 remaining
-    let remaining = coordinator.blockchain.get_unspent_shots("player1");
+    let remaining = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1");
     assert_eq!(remaining as u32, 1 + mined - 1);
 }
 
@@ -164,13 +170,14 @@ fn test_fire_shot_without_shots() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -178,10 +185,10 @@ fn test_fire_shot_without_shots() {
     ).unwrap();
 
     // Registration grants 1 shot: first fire should succeed
-    assert!(coordinator.fire_shot("player1".to_string(), 5, 5).is_ok());
+    assert!(coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).is_ok());
 
     // Second fire without mining should fail (no UTXOs left)
-    let result = coordinator.fire_shot("player1".to_string(), 6, 6);
+    let result = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 6, 6);
     assert!(result.is_err());
 }
 
@@ -196,13 +203,14 @@ fn test_get_active_players() {
             Ship::new(format!("Submarine{}", i), vec![(i, 7), (i, 8)]),
             Ship::new(format!("Destroyer{}", i), vec![(i, 9)]),
         ];
-        let positions: Vec<(u8, u8)> = ships.iter()
+        let positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|s| s.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&positions, &salt);
         
         coordinator.register_player(
+            DEFAULT_GAME_ID,
             format!("player{}", i),
             ships,
             commitment,
@@ -210,7 +218,7 @@ fn test_get_active_players() {
         ).unwrap();
     }
     
-    let active = coordinator.get_active_players();
+    let active = coordinator.get_active_players(DEFAULT_GAME_ID);
     assert_eq!(active.len(), 3);
 }
 
@@ -219,14 +227,14 @@ fn test_verify_blockchain() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
-    coordinator.mine_for_shots("player1").unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     
     assert!(coordinator.verify_blockchain());
 }
@@ -236,16 +244,16 @@ fn test_game_stats() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
-    coordinator.mine_for_shots("player1").unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     
-    let stats = coordinator.get_stats();
+    let stats = coordinator.get_stats(DEFAULT_GAME_ID).unwrap();
     assert_eq!(stats.total_players, 1);
     assert_eq!(stats.active_players, 1);
     assert!(stats.blockchain_length > 0);
@@ -256,22 +264,22 @@ fn test_multiple_shots_and_mining() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     
     // Mine and shoot multiple times
     for i in 0..5 {
-        coordinator.mine_for_shots("player1").unwrap();
-        coordinator.fire_shot("player1".to_string(), i, i).unwrap();
-        coordinator.mine_for_shots("player1").unwrap(); // Mine to include the shot
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), i, i).unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap(); // Mine to include the shot
     }
     
-    let stats = coordinator.get_stats();
+    let stats = coordinator.get_stats(DEFAULT_GAME_ID).unwrap();
     assert_eq!(stats.total_shots, 5);
 }
 
@@ -286,7 +294,7 @@ fn test_overlapping_ships_different_players() {
         Ship::new("Submarine1".to_string(), vec![(4, 0), (4, 1)]),
         Ship::new("Destroyer1".to_string(), vec![(6, 0)]),
     ];
-    let positions1: Vec<(u8, u8)> = ships1.iter()
+    let positions1: Vec<(u16, u16)> = ships1.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt1 = generate_salt();
@@ -299,16 +307,16 @@ fn test_overlapping_ships_different_players() {
         Ship::new("Submarine2".to_string(), vec![(0, 4), (1, 4)]),
         Ship::new("Destroyer2".to_string(), vec![(0, 6)]),
     ];
-    let positions2: Vec<(u8, u8)> = ships2.iter()
+    let positions2: Vec<(u16, u16)> = ships2.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt2 = generate_salt();
     let commitment2 = create_commitment(&positions2, &salt2);
     
-    coordinator.register_player("player1".to_string(), ships1, commitment1, salt1).unwrap();
-    coordinator.register_player("player2".to_string(), ships2, commitment2, salt2).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships1, commitment1, salt1).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player2".to_string(), ships2, commitment2, salt2).unwrap();
     
-    assert_eq!(coordinator.players.len(), 2);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 2);
 }
 
 #[test]
@@ -316,18 +324,18 @@ fn test_blockchain_grows_with_mining() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     
     let initial_length = coordinator.blockchain.chain.len();
     
     for _ in 0..3 {
-        coordinator.mine_for_shots("player1").unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     }
     
     assert_eq!(coordinator.blockchain.chain.len(), initial_length + 3);
@@ -338,29 +346,29 @@ fn test_pending_transactions_cleared_after_mining() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     
     // Mine to get additional shots (registration already granted 1)
-    coordinator.mine_for_shots("player1").unwrap();
-    coordinator.mine_for_shots("player1").unwrap();
-    coordinator.mine_for_shots("player1").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     
     // Fire multiple shots
     for i in 0..3 {
-        coordinator.fire_shot("player1".to_string(), i, i).unwrap();
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), i, i).unwrap();
     }
     
-    assert_eq!(coordinator.blockchain.pending_transactions.len(), 3);
+    assert_eq!(coordinator.blockchain.mempool.len(), 3);
     
     // Mine to clear pending
-    coordinator.mine_for_shots("player1").unwrap();
-    assert_eq!(coordinator.blockchain.pending_transactions.len(), 0);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.blockchain.mempool.len(), 0);
 }
 
 #[test]
@@ -368,15 +376,15 @@ fn test_stats_serialization() {
     let mut coordinator = GameCoordinator::new(10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     
-    let stats = coordinator.get_stats();
+    let stats = coordinator.get_stats(DEFAULT_GAME_ID).unwrap();
     let json = serde_json::to_string(&stats).unwrap();
     let deserialized: fleetchain::coordinator::GameStats = serde_json::from_str(&json).unwrap();
     
@@ -396,13 +404,14 @@ fn test_concurrent_players_mining() {
             Ship::new(format!("Submarine{}", i), vec![(i, 7), (i, 8)]),
             Ship::new(format!("Destroyer{}", i), vec![(i, 9)]),
         ];
-        let positions: Vec<(u8, u8)> = ships.iter()
+        let positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|s| s.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&positions, &salt);
         
         coordinator.register_player(
+            DEFAULT_GAME_ID,
             format!("player{}", i),
             ships,
             commitment,
@@ -411,25 +420,26 @@ fn test_concurrent_players_mining() {
     }
     
     // Both players mine
-    coordinator.mine_for_shots("player0").unwrap();
-    coordinator.mine_for_shots("player1").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player0").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
 
     // Both should have UTXO-backed shots
-    assert!(coordinator.blockchain.get_unspent_shots("player0") > 0);
-    assert!(coordinator.blockchain.get_unspent_shots("player1") > 0);
+    assert!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player0") > 0);
+    assert!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1") > 0);
 }
 
 #[test]
 fn test_fleet_validation_correct_fleet() {
     let mut coordinator = GameCoordinator::new(10, 2);
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -449,13 +459,14 @@ fn test_fleet_validation_wrong_number_of_ships() {
         Ship::new("Cruiser".to_string(), vec![(2, 0), (2, 1), (2, 2)]),
         Ship::new("Submarine".to_string(), vec![(4, 0), (4, 1)]),
     ];
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -477,13 +488,14 @@ fn test_fleet_validation_wrong_ship_sizes() {
         Ship::new("Ship3".to_string(), vec![(4, 0), (4, 1)]),
         Ship::new("Ship4".to_string(), vec![(6, 0), (6, 1)]), // Should be 1 cell
     ];
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -505,13 +517,14 @@ fn test_fleet_validation_diagonal_placement() {
         Ship::new("Submarine".to_string(), vec![(4, 0), (4, 1)]),
         Ship::new("Destroyer".to_string(), vec![(6, 0)]),
     ];
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -533,13 +546,14 @@ fn test_fleet_validation_non_consecutive_placement() {
         Ship::new("Submarine".to_string(), vec![(4, 0), (4, 1)]),
         Ship::new("Destroyer".to_string(), vec![(6, 0)]),
     ];
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -561,13 +575,14 @@ fn test_fleet_validation_vertical_placement() {
         Ship::new("Submarine".to_string(), vec![(0, 4), (1, 4)]),
         Ship::new("Destroyer".to_string(), vec![(0, 6)]),
     ];
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -588,13 +603,14 @@ fn test_defeated_player_cannot_mine() {
         Ship::new("Submarine".to_string(), vec![(4, 0), (4, 1)]),
         Ship::new("Destroyer".to_string(), vec![(6, 0)]),
     ];
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -602,22 +618,20 @@ fn test_defeated_player_cannot_mine() {
     ).unwrap();
 
     // Player can mine initially
-    assert!(coordinator.mine_for_shots("player1").is_ok());
-
-    // Sink all ships
-    if let Some(player) = coordinator.players.get_mut("player1") {
-        for ship in &mut player.ships {
-            for i in 0..ship.positions.len() {
-                ship.hits[i] = true;
-            }
+    assert!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").is_ok());
+
+    // Confirm a hit on every fleet cell
+    if let Some(player) = coordinator.game_mut(DEFAULT_GAME_ID).unwrap().players.get_mut("player1") {
+        for (x, y) in player.get_all_ship_positions() {
+            player.record_hit(x, y);
         }
     }
 
     // Verify player is defeated
-    assert!(coordinator.is_player_defeated("player1"));
+    assert!(coordinator.is_player_defeated(DEFAULT_GAME_ID, "player1"));
 
     // Defeated player cannot mine
-    let result = coordinator.mine_for_shots("player1");
+    let result = coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1");
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("Defeated players cannot mine"));
 }
@@ -634,13 +648,14 @@ fn test_large_scale_game() {
             Ship::new(format!("Submarine{}", i), vec![(i, 7), (i, 8)]),
             Ship::new(format!("Destroyer{}", i), vec![(i, 9)]),
         ];
-        let positions: Vec<(u8, u8)> = ships.iter()
+        let positions: Vec<(u16, u16)> = ships.iter()
             .flat_map(|s| s.positions.clone())
             .collect();
         let salt = generate_salt();
         let commitment = create_commitment(&positions, &salt);
         
         coordinator.register_player(
+            DEFAULT_GAME_ID,
             format!("player{}", i),
             ships,
             commitment,
@@ -648,16 +663,16 @@ fn test_large_scale_game() {
         ).unwrap();
     }
     
-    assert_eq!(coordinator.players.len(), 10);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 10);
     
     // Each player mines and shoots
     for i in 0..10 {
-        coordinator.mine_for_shots(&format!("player{}", i)).unwrap();
-        coordinator.fire_shot(format!("player{}", i), i, i).unwrap();
-        coordinator.mine_for_shots(&format!("player{}", i)).unwrap(); // Mine to include shot
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, &format!("player{}", i)).unwrap();
+        coordinator.fire_shot(DEFAULT_GAME_ID, format!("player{}", i), i, i).unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, &format!("player{}", i)).unwrap(); // Mine to include shot
     }
     
-    let stats = coordinator.get_stats();
+    let stats = coordinator.get_stats(DEFAULT_GAME_ID).unwrap();
     assert_eq!(stats.total_players, 10);
     assert_eq!(stats.total_shots, 10);
 }
@@ -667,20 +682,21 @@ fn test_registration_awards_initial_shot_utxo() {
     let mut coordinator = GameCoordinator::new(10, 2);
 
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
         salt,
     ).unwrap();
 
-    assert_eq!(coordinator.blockchain.get_unspent_shots("player1"), 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
 }
 
 #[test]
@@ -688,13 +704,14 @@ fn test_utxo_lifecycle_registration_mining_and_firing() {
     let mut coordinator = GameCoordinator::new(10, 2);
 
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
 
     coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -702,17 +719,153 @@ fn test_utxo_lifecycle_registration_mining_and_firing() {
     ).unwrap();
 
     // After registration
-    assert_eq!(coordinator.blockchain.get_unspent_shots("player1"), 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
 
     // Mine for additional shots
-    let mined = coordinator.mine_for_shots("player1").unwrap();
+    let mined = coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     assert!(mined > 0);
 
-    let total_after_mine = coordinator.blockchain.get_unspent_shots("player1");
+    let total_after_mine = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1");
     assert_eq!(total_after_mine as u32, 1 + mined);
 
     // Fire one shot
-    coordinator.fire_shot("player1".to_string(), 3, 3).unwrap();
-    let total_after_fire = coordinator.blockchain.get_unspent_shots("player1");
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 3, 3).unwrap();
+    let total_after_fire = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1");
     assert_eq!(total_after_fire as u32, 1 + mined - 1);
 }
+
+fn register_in(coordinator: &mut GameCoordinator, game_id: &str, player_id: &str) -> Result<(), String> {
+    let ships = create_valid_fleet();
+    let positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|s| s.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    coordinator.register_player(game_id, player_id.to_string(), ships, commitment, salt)
+}
+
+#[test]
+fn test_create_game() {
+    let mut coordinator = GameCoordinator::new(10, 2);
+    let config = coordinator.genesis.game.clone();
+
+    coordinator.create_game("arena", config.clone()).unwrap();
+    assert_eq!(coordinator.game_ids(), vec!["arena".to_string(), DEFAULT_GAME_ID.to_string()]);
+
+    assert!(coordinator.create_game("arena", config.clone()).is_err());
+    assert!(coordinator.create_game("", config.clone()).is_err());
+    assert!(coordinator.create_game("a/b", config).is_err());
+}
+
+#[test]
+fn test_unknown_game_is_rejected() {
+    let mut coordinator = GameCoordinator::new(10, 2);
+
+    let result = register_in(&mut coordinator, "missing", "player1");
+    assert_eq!(result.unwrap_err(), "Game missing not found");
+    assert!(coordinator.get_stats("missing").is_err());
+    assert!(coordinator.mine_for_shots("missing", "player1").is_err());
+}
+
+#[test]
+fn test_games_keep_players_and_shots_apart() {
+    let mut coordinator = GameCoordinator::new(10, 2);
+    let config = coordinator.genesis.game.clone();
+    coordinator.create_game("arena", config).unwrap();
+
+    register_in(&mut coordinator, DEFAULT_GAME_ID, "player1").unwrap();
+    register_in(&mut coordinator, "arena", "player1").unwrap();
+    register_in(&mut coordinator, "arena", "player2").unwrap();
+
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 1);
+    assert_eq!(coordinator.game("arena").unwrap().players.len(), 2);
+
+    coordinator.mine_for_shots("arena", "player1").unwrap();
+    let arena_shots = coordinator.get_unspent_shots("arena", "player1");
+    assert!(arena_shots > 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 0);
+    assert_eq!(coordinator.get_unspent_shots("arena", "player1"), arena_shots);
+
+    // Shots count once they are mined into a block
+    coordinator.mine_for_shots("arena", "player2").unwrap();
+    assert_eq!(coordinator.get_stats(DEFAULT_GAME_ID).unwrap().total_shots, 1);
+    assert_eq!(coordinator.get_stats("arena").unwrap().total_shots, 0);
+}
+
+#[test]
+fn test_games_use_their_own_rules() {
+    let mut coordinator = GameCoordinator::new(10, 2);
+    let mut config = coordinator.genesis.game.clone();
+    config.grid_size = 8;
+    config.phases.min_players = 2;
+    coordinator.create_game("small", config).unwrap();
+
+    register_in(&mut coordinator, "small", "player1").unwrap();
+    register_in(&mut coordinator, DEFAULT_GAME_ID, "player1").unwrap();
+
+    assert_eq!(coordinator.game("small").unwrap().grid.map.width, 8);
+    assert_eq!(coordinator.phase("small").unwrap(), GamePhase::Lobby);
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Battle);
+}
+
+fn register_with_salt(coordinator: &mut GameCoordinator, player_id: &str, salt: &str) -> Vec<(u16, u16)> {
+    let ships = create_valid_fleet();
+    let positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|s| s.positions.clone())
+        .collect();
+    let commitment = create_commitment(&positions, salt);
+    coordinator.register_player(DEFAULT_GAME_ID, player_id.to_string(), ships, commitment, salt.to_string()).unwrap();
+    positions
+}
+
+#[test]
+fn test_defeat_from_verified_reports_on_every_node() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    let mut peer = GameCoordinator::new(10, 1);
+    for node in [&mut coordinator, &mut peer] {
+        register_in(node, DEFAULT_GAME_ID, "player1").unwrap();
+    }
+    let positions = register_with_salt(&mut coordinator, "player2", "salt2");
+    register_with_salt(&mut peer, "player2", "salt2");
+
+    for (x, y) in positions {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), x, y).unwrap();
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+        let proof = HitProof::prove_hit((x, y), &[], "");
+        let report = HitReport::new("player2".to_string(), x, y, true, proof.serialize());
+        coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap();
+    }
+    assert!(!coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+
+    for block in coordinator.blockchain.chain[1..].iter().cloned() {
+        peer.accept_block(block).unwrap();
+    }
+    assert!(peer.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+    assert_eq!(peer.get_active_players(DEFAULT_GAME_ID), vec!["player1".to_string()]);
+}
+
+#[test]
+fn test_declare_defeat() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register_in(&mut coordinator, DEFAULT_GAME_ID, "player1").unwrap();
+    let positions = register_with_salt(&mut coordinator, "player2", "salt2");
+
+    let err = coordinator.declare_defeat(DEFAULT_GAME_ID, "player2", positions.clone(), "wrong".to_string()).unwrap_err();
+    assert!(err.contains("does not match"));
+
+    coordinator.declare_defeat(DEFAULT_GAME_ID, "player2", positions.clone(), "salt2".to_string()).unwrap();
+    assert!(coordinator.declare_defeat(DEFAULT_GAME_ID, "player2", positions, "salt2".to_string()).is_err());
+    assert!(!coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::GameOver);
+    assert_eq!(coordinator.get_stats(DEFAULT_GAME_ID).unwrap().winner, Some("player1".to_string()));
+}
@@ -1,4 +1,6 @@
-use fleetchain::crypto::{generate_salt, create_commitment, verify_commitment};
+use fleetchain::crypto::{generate_salt, create_commitment, verify_commitment, merkle_root, merkle_path, verify_merkle_path, fleet_root, ShipProof, CountProof, MoveProof};
+use fleetchain::map::GameMap;
+use fleetchain::weapons::Region;
 
 #[test]
 fn test_salt_generation() {
@@ -122,7 +124,7 @@ fn test_commitment_order_independence() {
 
 #[test]
 fn test_commitment_empty_positions() {
-    let positions: Vec<(u8, u8)> = vec![];
+    let positions: Vec<(u16, u16)> = vec![];
     let salt = generate_salt();
     
     let commitment = create_commitment(&positions, &salt);
@@ -140,7 +142,7 @@ fn test_commitment_single_position() {
 
 #[test]
 fn test_commitment_large_positions() {
-    let positions: Vec<(u8, u8)> = (0..100).map(|i| (i % 10, i / 10)).collect();
+    let positions: Vec<(u16, u16)> = (0..100).map(|i| (i % 10, i / 10)).collect();
     let salt = generate_salt();
     
     let commitment = create_commitment(&positions, &salt);
@@ -318,3 +320,136 @@ fn test_verify_commitment_comprehensive() {
     wrong_positions[0] = (9, 9);
     assert!(!verify_commitment(&commitment, &wrong_positions, &salt));
 }
+
+#[test]
+fn test_merkle_path_verifies_every_leaf() {
+    let leaves: Vec<String> = (0..5).map(|i| create_commitment(&[(i, i)], "salt")).collect();
+    let root = merkle_root(&leaves);
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let path = merkle_path(&leaves, i);
+        assert!(verify_merkle_path(leaf, &path, &root));
+    }
+
+    let path = merkle_path(&leaves, 0);
+    assert!(!verify_merkle_path(&leaves[1], &path, &root));
+}
+
+fn committed_fleet() -> Vec<(Vec<(u16, u16)>, String)> {
+    vec![
+        (vec![(0, 0), (0, 1), (0, 2), (0, 3)], generate_salt()),
+        (vec![(2, 0), (2, 1), (2, 2)], generate_salt()),
+        (vec![(4, 0), (4, 1)], generate_salt()),
+        (vec![(6, 0)], generate_salt()),
+    ]
+}
+
+#[test]
+fn test_ship_proof_reveals_single_ship() {
+    let fleet = committed_fleet();
+    let root = fleet_root(&fleet);
+
+    let proof = ShipProof::prove(&fleet, 1);
+    assert_eq!(proof.positions, vec![(2, 0), (2, 1), (2, 2)]);
+    assert!(proof.verify(&root));
+}
+
+#[test]
+fn test_ship_proof_rejects_tampering() {
+    let fleet = committed_fleet();
+    let root = fleet_root(&fleet);
+
+    let mut moved = ShipProof::prove(&fleet, 2);
+    moved.positions = vec![(5, 0), (5, 1)];
+    assert!(!moved.verify(&root));
+
+    let mut wrong_salt = ShipProof::prove(&fleet, 2);
+    wrong_salt.salt = generate_salt();
+    assert!(!wrong_salt.verify(&root));
+
+    let other_root = fleet_root(&committed_fleet());
+    assert!(!ShipProof::prove(&fleet, 2).verify(&other_root));
+}
+
+#[test]
+fn test_count_proof_verifies_region_count() {
+    let positions = vec![(1, 0), (1, 1), (1, 2), (5, 5)];
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    let region = Region::around((1, 1), &GameMap::square(10));
+
+    let proof = CountProof::prove(region, &positions, &salt);
+    assert_eq!(proof.count, 3);
+    assert!(proof.verify(&region, 3, &commitment));
+
+    // The proof does not name the cells it counted
+    let json = String::from_utf8(proof.serialize()).unwrap();
+    assert!(!json.contains(&salt));
+}
+
+#[test]
+fn test_count_proof_rejects_other_claims() {
+    let positions = vec![(1, 0), (1, 1), (1, 2)];
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    let region = Region::around((1, 1), &GameMap::square(10));
+    let proof = CountProof::prove(region, &positions, &salt);
+
+    assert!(!proof.verify(&region, 2, &commitment));
+    assert!(!proof.verify(&Region::around((5, 5), &GameMap::square(10)), 3, &commitment));
+    assert!(!proof.verify(&region, 3, &create_commitment(&positions, &generate_salt())));
+
+    let mut inflated = proof.clone();
+    inflated.count = 0;
+    assert!(!inflated.verify(&region, 0, &commitment));
+}
+
+#[test]
+fn test_count_proof_opens_only_to_the_proven_board() {
+    let positions = vec![(1, 0), (1, 1), (1, 2)];
+    let salt = generate_salt();
+    let region = Region::around((1, 1), &GameMap::square(10));
+    let proof = CountProof::prove(region, &positions, &salt);
+    assert_eq!(proof.cells.len(), 9);
+    assert!(proof.opens_to(&positions, &salt));
+
+    // Without the board's salt, nobody can make cell commitments that open to it
+    let forged = CountProof::prove(region, &positions, &generate_salt());
+    assert!(!forged.opens_to(&positions, &salt));
+    assert!(!proof.opens_to(&[(1, 0), (1, 1), (5, 5)], &salt));
+}
+
+#[test]
+fn test_move_proof_binds_both_commitments() {
+    let old_board = vec![(0, 0), (0, 1), (5, 5)];
+    let new_board = vec![(1, 0), (1, 1), (5, 5)];
+    let (old_salt, new_salt) = (generate_salt(), generate_salt());
+    let old_commitment = create_commitment(&old_board, &old_salt);
+
+    let proof = MoveProof::prove(&old_board, &old_salt, &new_board, &new_salt, 2);
+    assert_eq!(proof.new_commitment, create_commitment(&new_board, &new_salt));
+    assert!(proof.verify(&old_commitment, 2));
+
+    // Wrong starting board, a tighter distance limit or a swapped target all fail
+    assert!(!proof.verify(&proof.new_commitment, 2));
+    assert!(!proof.verify(&old_commitment, 1));
+    let mut swapped = proof.clone();
+    swapped.new_commitment = create_commitment(&old_board, &new_salt);
+    assert!(!swapped.verify(&old_commitment, 2));
+}
+
+#[test]
+fn test_move_proof_opens_only_to_the_moved_cells() {
+    let old_board = vec![(0, 0), (0, 1), (5, 5)];
+    let new_board = vec![(1, 0), (1, 1), (5, 5)];
+    let (old_salt, new_salt) = (generate_salt(), generate_salt());
+    let proof = MoveProof::prove(&old_board, &old_salt, &new_board, &new_salt, 2);
+    assert!(proof.opens_to(&old_board, &new_board, &new_salt));
+
+    // Another earlier board, the wrong salt or a rebound moved commitment all fail
+    assert!(!proof.opens_to(&[(0, 0), (0, 1), (6, 5)], &new_board, &new_salt));
+    assert!(!proof.opens_to(&old_board, &new_board, &old_salt));
+    let mut forged = proof.clone();
+    forged.moved_commitment = create_commitment(&[(9, 9)], &new_salt);
+    assert!(!forged.opens_to(&old_board, &new_board, &new_salt));
+}
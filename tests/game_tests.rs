@@ -1,5 +1,7 @@
-use fleetchain::game::{Ship, Grid, Player};
-use fleetchain::crypto::{generate_salt, create_commitment};
+use fleetchain::game::{Ship, Grid, Game, Player, FleetSpec, ShipClass};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::map::GameMap;
+use fleetchain::crypto::{generate_salt, create_commitment, ShipProof};
 
 #[test]
 fn test_ship_creation() {
@@ -41,14 +43,14 @@ fn test_ship_multiple_hits_same_position() {
 
 #[test]
 fn test_grid_creation() {
-    let grid = Grid::new(10);
-    assert_eq!(grid.size, 10);
+    let grid = Grid::new(GameMap::square(10));
+    assert_eq!(grid.map, GameMap::square(10));
     assert_eq!(grid.cells.len(), 0);
 }
 
 #[test]
 fn test_grid_place_ship() {
-    let mut grid = Grid::new(10);
+    let mut grid = Grid::new(GameMap::square(10));
     let positions = vec![(0, 0), (0, 1), (0, 2)];
     
     let result = grid.place_ship("player1", &positions);
@@ -57,7 +59,7 @@ fn test_grid_place_ship() {
 
 #[test]
 fn test_grid_out_of_bounds() {
-    let mut grid = Grid::new(10);
+    let mut grid = Grid::new(GameMap::square(10));
     let positions = vec![(10, 10), (11, 11)]; // Out of bounds
     
     let result = grid.place_ship("player1", &positions);
@@ -66,10 +68,10 @@ fn test_grid_out_of_bounds() {
 
 #[test]
 fn test_grid_multiple_ships_same_cell() {
-    let mut grid = Grid::new(10);
+    let mut grid = Grid::new(GameMap::square(10));
     
-    grid.place_ship("player1", &vec![(0, 0), (0, 1)]).unwrap();
-    grid.place_ship("player2", &vec![(0, 0), (1, 0)]).unwrap();
+    grid.place_ship("player1", &[(0, 0), (0, 1)]).unwrap();
+    grid.place_ship("player2", &[(0, 0), (1, 0)]).unwrap();
     
     // Both players should have ships at (0, 0)
     let players = grid.cells.get(&(0, 0)).unwrap();
@@ -80,13 +82,13 @@ fn test_grid_multiple_ships_same_cell() {
 
 #[test]
 fn test_grid_boundary_positions() {
-    let mut grid = Grid::new(10);
+    let mut grid = Grid::new(GameMap::square(10));
     
     // Test corners
-    assert!(grid.place_ship("p1", &vec![(0, 0)]).is_ok());
-    assert!(grid.place_ship("p2", &vec![(9, 9)]).is_ok());
-    assert!(grid.place_ship("p3", &vec![(0, 9)]).is_ok());
-    assert!(grid.place_ship("p4", &vec![(9, 0)]).is_ok());
+    assert!(grid.place_ship("p1", &[(0, 0)]).is_ok());
+    assert!(grid.place_ship("p2", &[(9, 9)]).is_ok());
+    assert!(grid.place_ship("p3", &[(0, 9)]).is_ok());
+    assert!(grid.place_ship("p4", &[(9, 0)]).is_ok());
 }
 
 #[test]
@@ -95,7 +97,7 @@ fn test_player_creation() {
         Ship::new("carrier".to_string(), vec![(0, 0), (0, 1), (0, 2)]),
     ];
     let salt = generate_salt();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let commitment = create_commitment(&positions, &salt);
@@ -114,7 +116,7 @@ fn test_player_check_hit() {
         Ship::new("destroyer".to_string(), vec![(5, 5), (5, 6)]),
     ];
     let salt = generate_salt();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let commitment = create_commitment(&positions, &salt);
@@ -132,7 +134,7 @@ fn test_player_defeat() {
         Ship::new("small".to_string(), vec![(0, 0), (0, 1)]),
     ];
     let salt = generate_salt();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let commitment = create_commitment(&positions, &salt);
@@ -153,7 +155,7 @@ fn test_player_get_all_positions() {
         Ship::new("ship2".to_string(), vec![(5, 5)]),
     ];
     let salt = generate_salt();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let commitment = create_commitment(&positions, &salt);
@@ -169,13 +171,13 @@ fn test_player_get_all_positions() {
 
 #[test]
 fn test_multiple_players_on_grid() {
-    let mut grid = Grid::new(10);
+    let mut grid = Grid::new(GameMap::square(10));
     
     // Player 1 ships
-    grid.place_ship("player1", &vec![(0, 0), (0, 1), (0, 2)]).unwrap();
+    grid.place_ship("player1", &[(0, 0), (0, 1), (0, 2)]).unwrap();
     
     // Player 2 ships (overlapping at (0, 1))
-    grid.place_ship("player2", &vec![(0, 1), (1, 1), (2, 1)]).unwrap();
+    grid.place_ship("player2", &[(0, 1), (1, 1), (2, 1)]).unwrap();
     
     // Check overlapping cell
     let players_at_0_1 = grid.cells.get(&(0, 1)).unwrap();
@@ -195,7 +197,7 @@ fn test_ship_serialization() {
 
 #[test]
 fn test_large_grid() {
-    let mut grid = Grid::new(100);
+    let mut grid = Grid::new(GameMap::square(100));
     
     // Place ships across large grid
     for i in 0..10 {
@@ -211,3 +213,153 @@ fn test_ship_with_single_cell() {
     assert!(!ship.is_sunk());
 }
 
+
+fn classic_spec() -> FleetSpec {
+    FleetSpec::new(vec![
+        ShipClass::new("Battleship", 4, 1),
+        ShipClass::new("Cruiser", 3, 2),
+        ShipClass::new("Destroyer", 2, 1),
+    ])
+}
+
+#[test]
+fn test_fleet_spec_totals() {
+    let spec = classic_spec();
+    assert_eq!(spec.total_ships(), 4);
+    assert_eq!(spec.total_cells(), 12);
+
+    let default_spec = FleetSpec::default();
+    assert_eq!(default_spec.total_ships(), 4);
+    assert_eq!(default_spec.total_cells(), 10);
+}
+
+#[test]
+fn test_fleet_spec_class_counts() {
+    let spec = classic_spec();
+    let ships = vec![
+        Ship::new("Battleship".to_string(), vec![(0, 0), (0, 1), (0, 2), (0, 3)]),
+        Ship::new("Cruiser1".to_string(), vec![(2, 0), (2, 1), (2, 2)]),
+        Ship::new("Cruiser2".to_string(), vec![(4, 0), (4, 1), (4, 2)]),
+        Ship::new("Destroyer".to_string(), vec![(6, 0), (6, 1)]),
+    ];
+    assert!(spec.validate_fleet(&ships, &GameMap::square(10)).is_ok());
+
+    // One cruiser swapped for a second destroyer
+    let ships = vec![
+        Ship::new("Battleship".to_string(), vec![(0, 0), (0, 1), (0, 2), (0, 3)]),
+        Ship::new("Cruiser1".to_string(), vec![(2, 0), (2, 1), (2, 2)]),
+        Ship::new("Destroyer1".to_string(), vec![(4, 0), (4, 1)]),
+        Ship::new("Destroyer2".to_string(), vec![(6, 0), (6, 1)]),
+    ];
+    let err = spec.validate_fleet(&ships, &GameMap::square(10)).unwrap_err();
+    assert!(err.contains("1 Cruiser"));
+}
+
+#[test]
+fn test_fleet_spec_error_names_class() {
+    let spec = classic_spec();
+    let ships = vec![
+        Ship::new("Battleship".to_string(), vec![(0, 0), (1, 1), (2, 2), (3, 3)]),
+        Ship::new("Cruiser1".to_string(), vec![(2, 0), (2, 1), (2, 2)]),
+        Ship::new("Cruiser2".to_string(), vec![(4, 0), (4, 1), (4, 2)]),
+        Ship::new("Destroyer".to_string(), vec![(6, 0), (6, 1)]),
+    ];
+    let err = spec.validate_fleet(&ships, &GameMap::square(10)).unwrap_err();
+    assert!(err.starts_with("Battleship"));
+    assert!(err.contains("horizontally or vertically"));
+
+    let ships = vec![
+        Ship::new("flagship".to_string(), vec![(0, 0), (0, 1), (0, 2), (0, 3)]),
+        Ship::new("c1".to_string(), vec![(2, 0), (2, 1), (2, 2)]),
+        Ship::new("c2".to_string(), vec![(4, 8), (4, 9), (4, 10)]),
+        Ship::new("d1".to_string(), vec![(6, 0), (6, 1)]),
+    ];
+    let err = spec.validate_fleet(&ships, &GameMap::square(10)).unwrap_err();
+    assert!(err.contains("Cruiser 'c2' is out of bounds"));
+}
+
+#[test]
+fn test_fleet_spec_diagonal_rule() {
+    let mut spec = FleetSpec::new(vec![ShipClass::new("Cruiser", 3, 1)]);
+    let diagonal = vec![Ship::new("Cruiser".to_string(), vec![(0, 2), (1, 1), (2, 0)])];
+    assert!(spec.validate_fleet(&diagonal, &GameMap::square(10)).is_err());
+
+    spec.allow_diagonal = true;
+    assert!(spec.validate_fleet(&diagonal, &GameMap::square(10)).is_ok());
+
+    // A bent line is never valid
+    let bent = vec![Ship::new("Cruiser".to_string(), vec![(0, 0), (1, 1), (2, 1)])];
+    assert!(spec.validate_fleet(&bent, &GameMap::square(10)).is_err());
+}
+
+#[test]
+fn test_fleet_spec_overlap_rule() {
+    let mut spec = FleetSpec::new(vec![
+        ShipClass::new("Cruiser", 3, 1),
+        ShipClass::new("Destroyer", 2, 1),
+    ]);
+    let crossing = vec![
+        Ship::new("Cruiser".to_string(), vec![(1, 0), (1, 1), (1, 2)]),
+        Ship::new("Destroyer".to_string(), vec![(0, 1), (1, 1)]),
+    ];
+    let err = spec.validate_fleet(&crossing, &GameMap::square(10)).unwrap_err();
+    assert!(err.contains("Destroyer 'Destroyer' overlaps 'Cruiser' at (1, 1)"));
+
+    spec.allow_overlap = true;
+    assert!(spec.validate_fleet(&crossing, &GameMap::square(10)).is_ok());
+}
+
+#[test]
+fn test_player_defeat_counts_confirmed_hit_cells() {
+    let ships = vec![
+        Ship::new("small".to_string(), vec![(0, 0), (0, 1)]),
+    ];
+    let salt = generate_salt();
+    let commitment = create_commitment(&ships[0].positions, &salt);
+    let mut player = Player::new("player1".to_string(), ships, commitment, salt);
+    assert_eq!(player.fleet_cells, 2);
+
+    // The same cell only counts once
+    player.record_hit(0, 0);
+    player.record_hit(0, 0);
+    assert_eq!(player.hit_cells.len(), 1);
+    assert!(!player.is_defeated());
+
+    // Confirmed hits count even where this node has no ship on record
+    player.record_hit(5, 5);
+    assert!(player.is_defeated());
+    assert!(!player.ships[0].is_sunk());
+}
+
+#[test]
+fn test_overlapping_fleet_is_defeated_by_its_sunk_declarations() {
+    let mut config = GenesisConfig::new(10, 1).game;
+    config.fleet = FleetSpec::new(vec![ShipClass::new("Cruiser", 3, 1), ShipClass::new("Destroyer", 2, 1)]);
+    config.fleet.allow_overlap = true;
+    let mut game = Game::new("overlap".to_string(), config);
+
+    let ships = vec![
+        Ship::new("Cruiser".to_string(), vec![(1, 0), (1, 1), (1, 2)]),
+        Ship::new("Destroyer".to_string(), vec![(0, 1), (1, 1)]),
+    ];
+    let committed: Vec<(Vec<(u16, u16)>, String)> = ships.iter()
+        .map(|ship| (ship.positions.clone(), generate_salt()))
+        .collect();
+    let positions: Vec<(u16, u16)> = ships.iter().flat_map(|s| s.positions.clone()).collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    game.add_player("player1".to_string(), ships, commitment, salt).unwrap();
+
+    // The threshold comes from the spec, so every node agrees on it whatever fleet it holds
+    let player = &game.players["player1"];
+    assert_eq!((player.fleet_cells, player.fleet_ships), (5, 2));
+    for cell in [(1, 0), (1, 1), (1, 2), (0, 1)] {
+        game.players.get_mut("player1").unwrap().record_hit(cell.0, cell.1);
+    }
+    assert!(!game.is_player_defeated("player1"));
+
+    game.record_sunk("player1", "Cruiser", &ShipProof::prove(&committed, 0), 3);
+    assert!(!game.is_player_defeated("player1"));
+    game.record_sunk("player1", "Destroyer", &ShipProof::prove(&committed, 1), 4);
+    assert!(game.is_player_defeated("player1"));
+}
@@ -0,0 +1,199 @@
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::blockchain::Blockchain;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::{Ship, ShipClass, FleetSpec, DEFAULT_GAME_ID};
+use fleetchain::crypto::{generate_salt, create_commitment};
+use fleetchain::rewards::{RewardDecay, RewardPolicy};
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+use common::register_in_game;
+
+#[test]
+fn test_genesis_hash_deterministic() {
+    let a = GenesisConfig::new(10, 2);
+    let b = GenesisConfig::new(10, 2);
+    assert_eq!(a.hash(), b.hash());
+    assert_eq!(
+        Blockchain::from_genesis(&a).genesis_hash(),
+        Blockchain::from_genesis(&b).genesis_hash()
+    );
+}
+
+#[test]
+fn test_genesis_hash_changes_with_settings() {
+    let base = GenesisConfig::new(10, 2);
+
+    let mut other_grid = base.clone();
+    other_grid.game.grid_size = 12;
+    assert_ne!(base.hash(), other_grid.hash());
+
+    let mut other_chain = base.clone();
+    other_chain.chain_id = "another-network".to_string();
+    assert_ne!(
+        Blockchain::from_genesis(&base).genesis_hash(),
+        Blockchain::from_genesis(&other_chain).genesis_hash()
+    );
+}
+
+#[test]
+fn test_genesis_block_commits_to_config() {
+    let genesis = GenesisConfig::new(10, 2);
+    let mut blockchain = Blockchain::from_genesis(&genesis);
+    assert_eq!(blockchain.chain[0].config_hash, Some(genesis.hash()));
+    assert!(blockchain.is_chain_valid());
+
+    // Swapping the config hash without re-mining invalidates the chain
+    blockchain.chain[0].config_hash = Some(GenesisConfig::new(12, 2).hash());
+    assert!(!blockchain.is_chain_valid());
+}
+
+#[test]
+fn test_genesis_parameters_applied() {
+    let mut genesis = GenesisConfig::new(8, 1);
+    genesis.rewards.mining_reward = 3;
+    genesis.rewards.registration_shots = 2;
+
+    let mut blockchain = Blockchain::from_genesis(&genesis);
+    assert_eq!(blockchain.difficulty, 1);
+    assert_eq!(blockchain.rewards.mining_reward, 3);
+
+    blockchain.award_registration_shot("player1");
+    assert_eq!(blockchain.get_unspent_shots("player1"), 2);
+
+    assert_eq!(blockchain.mine_pending_transactions("player1"), 3);
+    assert_eq!(blockchain.get_unspent_shots("player1"), 5);
+}
+
+#[test]
+fn test_genesis_validation() {
+    assert!(GenesisConfig::default().validate().is_ok());
+
+    let empty_chain_id = GenesisConfig {
+        chain_id: String::new(),
+        ..Default::default()
+    };
+    assert!(empty_chain_id.validate().is_err());
+
+    let mut oversized_ship = GenesisConfig::new(3, 2);
+    oversized_ship.game.fleet = FleetSpec::new(vec![ShipClass::new("Carrier", 4, 1)]);
+    let err = oversized_ship.validate().unwrap_err();
+    assert!(err.contains("Carrier"));
+
+    let mut free_challenges = GenesisConfig::default();
+    free_challenges.game.challenges.optimistic = true;
+    free_challenges.game.challenges.bond_shots = 0;
+    assert!(free_challenges.validate().unwrap_err().contains("bond_shots"));
+
+    let mut frozen_ships = GenesisConfig::default();
+    frozen_ships.game.relocation.max_distance = 0;
+    assert!(frozen_ships.validate().unwrap_err().contains("max_distance"));
+
+    let mut hard_shares = GenesisConfig::new(10, 1);
+    hard_shares.consensus.share_difficulty = 2;
+    assert!(hard_shares.validate().unwrap_err().contains("share_difficulty"));
+
+    let mut endless_halving = GenesisConfig::default();
+    endless_halving.rewards.decay = RewardDecay::Halving { interval: 0 };
+    assert!(endless_halving.validate().unwrap_err().contains("interval"));
+
+    let mut high_floor = GenesisConfig::default();
+    high_floor.game.rewards = Some(RewardPolicy { min_reward: 2, ..RewardPolicy::default() });
+    assert!(high_floor.validate().unwrap_err().contains("min_reward"));
+
+    let mut instant_swaps = GenesisConfig::default();
+    instant_swaps.game.trades.swap_blocks = 0;
+    assert!(instant_swaps.validate().unwrap_err().contains("swap_blocks"));
+}
+
+#[test]
+fn test_genesis_save_and_load() {
+    let test_path = PathBuf::from("test_genesis_save.json");
+    let _ = fs::remove_file(&test_path);
+
+    let mut genesis = GenesisConfig::new(12, 3);
+    genesis.chain_id = "saved-network".to_string();
+    genesis.save_to_file(&test_path).expect("Failed to save genesis");
+
+    let loaded = GenesisConfig::load_from_file(&test_path).expect("Failed to load genesis");
+    assert_eq!(loaded, genesis);
+    assert_eq!(loaded.hash(), genesis.hash());
+
+    fs::remove_file(&test_path).ok();
+}
+
+#[test]
+fn test_coordinator_uses_genesis_fleet() {
+    let mut genesis = GenesisConfig::new(10, 2);
+    genesis.game.fleet = FleetSpec::new(vec![
+        ShipClass::new("Battleship", 5, 1),
+        ShipClass::new("Frigate", 2, 1),
+    ]);
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+
+    let ships = vec![
+        Ship::new("Battleship".to_string(), vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)]),
+        Ship::new("Frigate".to_string(), vec![(2, 0), (3, 0)]),
+    ];
+    let positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|s| s.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+
+    assert!(coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).is_ok());
+}
+
+#[test]
+fn test_persistence_discards_chain_from_other_genesis() {
+    let test_path = PathBuf::from("test_genesis_mismatch_chain.json");
+    let _ = fs::remove_file(&test_path);
+
+    let mut old = Blockchain::from_genesis(&GenesisConfig::new(10, 1));
+    old.mine_pending_transactions("miner1");
+    old.save_to_file(&test_path).expect("Failed to save blockchain");
+
+    let coordinator = GameCoordinator::from_genesis_with_persistence(
+        GenesisConfig::new(12, 1),
+        test_path.clone(),
+    );
+    assert_eq!(coordinator.blockchain.chain.len(), 1);
+    assert_eq!(coordinator.blockchain.genesis_hash(), Blockchain::from_genesis(&GenesisConfig::new(12, 1)).genesis_hash());
+
+    fs::remove_file(&test_path).ok();
+    fs::remove_file(test_path.with_extension("setup.json")).ok();
+}
+
+#[test]
+fn test_restart_replays_games_from_the_setup_log() {
+    let test_path = PathBuf::from("test_restart_replays_games.json");
+    let setup_path = test_path.with_extension("setup.json");
+    let _ = fs::remove_file(&test_path);
+    let _ = fs::remove_file(&setup_path);
+
+    let genesis = GenesisConfig::new(10, 1);
+    let mut coordinator = GameCoordinator::from_genesis_with_persistence(genesis.clone(), test_path.clone());
+    coordinator.create_game("second", genesis.game.clone()).unwrap();
+    register_in_game(&mut coordinator, "second", "player1", 0);
+    register_in_game(&mut coordinator, "second", "player2", 1);
+    coordinator.fire_shot("second", "player1".to_string(), 1, 0).unwrap();
+    coordinator.mine_for_shots("second", "player2").unwrap();
+    coordinator.fire_shot("second", "player2".to_string(), 0, 0).unwrap();
+
+    let restarted = GameCoordinator::from_genesis_with_persistence(genesis, test_path.clone());
+    let game = restarted.game("second").unwrap();
+    assert_eq!(game.players.len(), 2);
+    assert_eq!(restarted.open_obligations("second", "player2").unwrap().len(), 1);
+    assert_eq!(restarted.blockchain.mempool.len(), 1);
+    for player_id in ["player1", "player2"] {
+        assert_eq!(
+            restarted.get_unspent_shots("second", player_id),
+            coordinator.get_unspent_shots("second", player_id)
+        );
+    }
+    assert!(restarted.verify_blockchain());
+
+    fs::remove_file(&test_path).ok();
+    fs::remove_file(&setup_path).ok();
+}
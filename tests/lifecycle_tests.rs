@@ -0,0 +1,178 @@
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::{HitReport, DEFAULT_GAME_ID};
+use fleetchain::crypto::HitProof;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::lifecycle::{GamePhase, PhaseRules};
+use fleetchain::shots::SelfFire;
+
+mod common;
+use common::try_register;
+
+fn coordinator_with_rules(rules: PhaseRules) -> GameCoordinator {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.phases = rules;
+    GameCoordinator::from_genesis(genesis)
+}
+
+fn report_hit(coordinator: &mut GameCoordinator, player_id: &str, pos: (u16, u16)) {
+    // player1 fires at the cell and mines the shot so the report is owed
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), pos.0, pos.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let proof = HitProof::prove_hit(pos, &[], "");
+    let report = HitReport::new(player_id.to_string(), pos.0, pos.1, true, proof.serialize());
+    coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap();
+
+    // The hit takes effect once the report is mined
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+}
+
+#[test]
+fn test_default_rules_start_battle_on_first_registration() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Lobby);
+
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Battle);
+
+    // Late joiners are allowed by default
+    try_register(&mut coordinator, "player2", 1).unwrap();
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 2);
+}
+
+#[test]
+fn test_lobby_waits_for_min_players() {
+    let mut coordinator = coordinator_with_rules(PhaseRules {
+        min_players: 2,
+        placement_blocks: 2,
+        ..PhaseRules::default()
+    });
+
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Lobby);
+
+    let err = coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap_err();
+    assert!(err.contains("Cannot mine during the Lobby phase"));
+    let err = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap_err();
+    assert!(err.contains("Lobby"));
+
+    try_register(&mut coordinator, "player2", 1).unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Placement);
+}
+
+#[test]
+fn test_placement_window_closes_after_blocks() {
+    let mut coordinator = coordinator_with_rules(PhaseRules {
+        min_players: 2,
+        placement_blocks: 2,
+        allow_late_join: false,
+        ..PhaseRules::default()
+    });
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    try_register(&mut coordinator, "player2", 1).unwrap();
+
+    // Shooting is not allowed while fleets are still being placed
+    let err = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap_err();
+    assert!(err.contains("Cannot fire shots during the Placement phase"));
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Placement);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Battle);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().lifecycle.phase_started_at, 2);
+
+    assert!(coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).is_ok());
+
+    // Registration closed once the battle started
+    let err = try_register(&mut coordinator, "player3", 2).unwrap_err();
+    assert!(err.contains("Battle"));
+}
+
+#[test]
+fn test_max_players() {
+    let mut coordinator = coordinator_with_rules(PhaseRules {
+        max_players: 2,
+        ..PhaseRules::default()
+    });
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    try_register(&mut coordinator, "player2", 1).unwrap();
+    let err = try_register(&mut coordinator, "player3", 2).unwrap_err();
+    assert!(err.contains("Game is full"));
+}
+
+#[test]
+fn test_duplicate_registration_rejected() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    let err = try_register(&mut coordinator, "player1", 1).unwrap_err();
+    assert!(err.contains("already registered"));
+}
+
+#[test]
+fn test_last_player_standing_wins() {
+    // Players only report the shots aimed at them, so unanswered shots must not be penalized
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.reports.deadline_blocks = 0;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    try_register(&mut coordinator, "player2", 1).unwrap();
+    try_register(&mut coordinator, "player3", 2).unwrap();
+
+    for y in 0..10 {
+        report_hit(&mut coordinator, "player3", (2, y));
+    }
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Battle);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().lifecycle.eliminations.len(), 1);
+
+    for y in 0..10 {
+        report_hit(&mut coordinator, "player2", (1, y));
+    }
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::GameOver);
+
+    let outcome = coordinator.game(DEFAULT_GAME_ID).unwrap().lifecycle.outcome.clone().unwrap();
+    assert_eq!(outcome.winner, Some("player1".to_string()));
+    assert_eq!(outcome.standings, vec!["player1", "player2", "player3"]);
+    assert_eq!(coordinator.get_stats(DEFAULT_GAME_ID).unwrap().winner, Some("player1".to_string()));
+
+    // No more shots once the game is over, but blocks are still mined so reveals reach the chain
+    let err = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap_err();
+    assert!(err.contains("GameOver"));
+    assert!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player3").is_ok());
+}
+
+#[test]
+fn test_single_player_game_never_ends() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Battle);
+    assert!(coordinator.game(DEFAULT_GAME_ID).unwrap().lifecycle.outcome.is_none());
+}
+
+#[test]
+fn test_report_deadlines_stop_when_the_game_is_over() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.reports.deadline_blocks = 1;
+    genesis.game.shots.self_fire = SelfFire::Forbid;
+    genesis.rewards.registration_shots = 10;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    try_register(&mut coordinator, "player1", 0).unwrap();
+    try_register(&mut coordinator, "player2", 1).unwrap();
+
+    // player2 never reports, so its whole fleet counts as hit once block 3 is mined
+    for y in 0..10 {
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 1, y).unwrap();
+    }
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player2".to_string(), 0, 0).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::GameOver);
+
+    // player1 cannot report player2's shot any more, so it is not penalized for it
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert!(game.players["player1"].hit_cells.is_empty());
+    assert_eq!(game.open_obligations("player1").len(), 1);
+}
@@ -0,0 +1,153 @@
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::crypto::{generate_salt, create_commitment};
+use fleetchain::game::{FleetSpec, Ship, ShipClass, DEFAULT_GAME_ID};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::map::GameMap;
+use std::fs;
+use std::path::PathBuf;
+
+fn fleet_in_row(y: u16, x: u16) -> Vec<Ship> {
+    vec![
+        Ship::new("Carrier".to_string(), vec![(x, y), (x + 1, y), (x + 2, y), (x + 3, y)]),
+        Ship::new("Cruiser".to_string(), vec![(x + 5, y), (x + 6, y), (x + 7, y)]),
+        Ship::new("Submarine".to_string(), vec![(x + 9, y), (x + 10, y)]),
+        Ship::new("Destroyer".to_string(), vec![(x + 12, y)]),
+    ]
+}
+
+fn register(coordinator: &mut GameCoordinator, player_id: &str, ships: Vec<Ship>) -> Result<(), String> {
+    let positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|s| s.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    coordinator.register_player(DEFAULT_GAME_ID, player_id.to_string(), ships, commitment, salt)
+}
+
+/// 16x4 board with an island at (6..=8, 1..=2)
+fn island_map() -> GameMap {
+    GameMap::parse("
+        ................
+        ......###.......
+        ......###.......
+        ................
+    ").unwrap()
+}
+
+fn game_on(map: GameMap) -> GameCoordinator {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.registration_shots = 4;
+    genesis.game.map = Some(map);
+    GameCoordinator::from_genesis(genesis)
+}
+
+#[test]
+fn test_parse_text_maps() {
+    let map = island_map();
+    assert_eq!((map.width, map.height), (16, 4));
+    assert_eq!(map.blocked.len(), 6);
+    assert!(map.is_blocked((7, 2)));
+    assert!(map.is_open((5, 2)));
+    assert!(!map.is_open((16, 0)));
+    assert_eq!(map.open_cells(), 58);
+
+    assert!(GameMap::parse("...\n..").unwrap_err().contains("row 1"));
+    assert!(GameMap::parse("..x").unwrap_err().contains("Unknown map cell 'x'"));
+    assert!(GameMap::parse("##\n##").unwrap_err().contains("no open cells"));
+    assert!(GameMap::parse("").is_err());
+
+    let mut stray = GameMap::new(4, 4);
+    stray.blocked.push((4, 0));
+    assert!(stray.validate().unwrap_err().contains("outside the 4x4 grid"));
+}
+
+#[test]
+fn test_placement_respects_width_height_and_land() {
+    let mut coordinator = game_on(island_map());
+    register(&mut coordinator, "player1", fleet_in_row(0, 0)).unwrap();
+
+    // The cruiser would sit on the island
+    let err = register(&mut coordinator, "player2", fleet_in_row(1, 0)).unwrap_err();
+    assert!(err.contains("Cruiser 'Cruiser' is on land at (6, 1)"));
+
+    // The board is only four rows tall
+    let err = register(&mut coordinator, "player2", fleet_in_row(4, 0)).unwrap_err();
+    assert!(err.contains("out of bounds"));
+
+    register(&mut coordinator, "player2", fleet_in_row(3, 0)).unwrap();
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().grid.map, island_map());
+}
+
+#[test]
+fn test_shots_cannot_target_land_or_leave_the_map() {
+    let mut coordinator = game_on(island_map());
+    register(&mut coordinator, "player1", fleet_in_row(0, 0)).unwrap();
+    register(&mut coordinator, "player2", fleet_in_row(3, 0)).unwrap();
+
+    let err = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 7, 1).unwrap_err();
+    assert!(err.contains("Target (7, 1) is land"));
+    let err = coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 3, 4).unwrap_err();
+    assert!(err.contains("Target (3, 4) is outside the 16x4 grid"));
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 15, 3).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 3);
+}
+
+#[test]
+fn test_boards_beyond_255_cells() {
+    let mut coordinator = game_on(GameMap::new(400, 300));
+    register(&mut coordinator, "player1", fleet_in_row(299, 380)).unwrap();
+    register(&mut coordinator, "player2", fleet_in_row(0, 0)).unwrap();
+
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player2".to_string(), 392, 299).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    let obligations = coordinator.open_obligations(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!((obligations[0].shot_x, obligations[0].shot_y), (392, 299));
+}
+
+#[test]
+fn test_fleet_spec_must_fit_the_map() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.map = Some(GameMap::new(3, 2));
+    genesis.game.fleet = FleetSpec::new(vec![ShipClass::new("Carrier", 4, 1)]);
+    assert!(genesis.validate().unwrap_err().contains("does not fit on a 3x2 grid"));
+
+    genesis.game.fleet = FleetSpec::new(vec![ShipClass::new("Cruiser", 3, 2)]);
+    genesis.game.map.as_mut().unwrap().blocked.push((0, 0));
+    assert!(genesis.validate().unwrap_err().contains("5 open cells"));
+}
+
+#[test]
+fn test_genesis_loads_the_referenced_map_file() {
+    let dir = PathBuf::from("test_map_files");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("islands.txt"), "................\n......###.......\n......###.......\n................\n").unwrap();
+
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.map_file = Some("islands.txt".to_string());
+    assert!(genesis.validate().unwrap_err().contains("has not been loaded"));
+
+    let mut json: serde_json::Value = serde_json::to_value(&genesis).unwrap();
+    json["game"]["map_file"] = "islands.txt".into();
+    fs::write(dir.join("genesis.json"), json.to_string()).unwrap();
+
+    let loaded = GenesisConfig::load_from_file(dir.join("genesis.json")).unwrap();
+    assert_eq!(loaded.game.map, Some(island_map()));
+
+    // Only the loaded map is hashed, not the path it came from
+    let mut inline = GenesisConfig::new(10, 1);
+    inline.game.map = Some(island_map());
+    assert_eq!(loaded.hash(), inline.hash());
+
+    // JSON map files work too
+    fs::write(dir.join("open.json"), r#"{"width": 12, "height": 20}"#).unwrap();
+    json["game"]["map_file"] = "open.json".into();
+    fs::write(dir.join("genesis.json"), json.to_string()).unwrap();
+    let loaded = GenesisConfig::load_from_file(dir.join("genesis.json")).unwrap();
+    assert_eq!(loaded.game.map, Some(GameMap::new(12, 20)));
+
+    json["game"]["map_file"] = "missing.txt".into();
+    fs::write(dir.join("genesis.json"), json.to_string()).unwrap();
+    assert!(GenesisConfig::load_from_file(dir.join("genesis.json")).unwrap_err().contains("missing.txt"));
+
+    fs::remove_dir_all(&dir).ok();
+}
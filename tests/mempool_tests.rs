@@ -0,0 +1,163 @@
+use fleetchain::blockchain::{Blockchain, Transaction};
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::mempool::{Mempool, MempoolLimits};
+use fleetchain::weapons::Weapon;
+
+mod common;
+use common::{mined_block, register};
+
+/// Coordinator whose two players hold three shots each
+fn game(genesis: GenesisConfig) -> GameCoordinator {
+    let mut genesis = genesis;
+    genesis.rewards.registration_shots = 3;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator
+}
+
+fn shot(player_id: &str, nonce: u64, fee: u32) -> Transaction {
+    let mut transaction = Transaction::new(player_id.to_string(), 1, 1, nonce);
+    transaction.fee = fee;
+    transaction
+}
+
+#[test]
+fn test_players_get_sequence_nonces() {
+    let mut coordinator = game(GenesisConfig::new(10, 1));
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player2".to_string(), 5, 5).unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 6, 6).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.transfer_shots(DEFAULT_GAME_ID, "player1", "player2", 1).unwrap();
+
+    let nonces: Vec<(String, u64)> = coordinator.blockchain.chain[1].transactions.iter()
+        .chain(coordinator.blockchain.mempool.iter())
+        .map(|tx| (tx.player_id.clone(), tx.nonce))
+        .collect();
+    assert_eq!(nonces, [
+        ("player1".to_string(), 0),
+        ("player2".to_string(), 0),
+        ("player1".to_string(), 1),
+        ("player1".to_string(), 2),
+    ]);
+    assert_eq!(coordinator.blockchain.next_nonce(DEFAULT_GAME_ID, "player1"), 3);
+    assert_eq!(coordinator.blockchain.next_nonce("other", "player1"), 0);
+}
+
+#[test]
+fn test_rebroadcasts_and_replays_are_rejected() {
+    let mut blockchain = Blockchain::new(1);
+    let first = shot("player1", 0, 0);
+    blockchain.add_transaction(first.clone()).unwrap();
+    assert!(blockchain.add_transaction(first.clone()).unwrap_err().contains("already pending"));
+    assert!(blockchain.add_transaction(shot("player1", 0, 1)).unwrap_err().contains("pending transaction"));
+
+    blockchain.mine_pending_transactions("miner1");
+    assert!(blockchain.add_transaction(first).unwrap_err().contains("already used on-chain"));
+    blockchain.add_transaction(shot("player1", 1, 0)).unwrap();
+    assert_eq!(blockchain.mempool.len(), 1);
+}
+
+#[test]
+fn test_conflicting_spends_are_rejected() {
+    let mut coordinator = game(GenesisConfig::new(10, 1));
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    let spent = coordinator.blockchain.mempool.transactions()[0].spent_utxos.clone();
+
+    let mut double_spend = shot("player1", 7, 0);
+    double_spend.spent_utxos = spent.clone();
+    assert!(coordinator.blockchain.add_transaction(double_spend.clone()).unwrap_err().contains("already spent by a pending"));
+
+    // A peer refuses a block spending the same shot twice
+    let mut peer = game(GenesisConfig::new(10, 1));
+    let mut first = shot("player1", 0, 0);
+    first.spent_utxos = spent;
+    let block = mined_block(&peer, "player2", vec![first, double_spend]);
+    assert!(peer.accept_block(block).unwrap_err().contains("already spent on-chain"));
+}
+
+#[test]
+fn test_blocks_remove_exactly_their_transactions() {
+    let mut coordinator = game(GenesisConfig::new(10, 1));
+    let mut peer = game(GenesisConfig::new(10, 1));
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    peer.blockchain.add_transaction(coordinator.blockchain.mempool.transactions()[0].clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+
+    // The peer also holds a shot the coordinator never saw, which stays pending
+    peer.fire_shot(DEFAULT_GAME_ID, "player2".to_string(), 6, 6).unwrap();
+    peer.accept_block(coordinator.blockchain.chain[1].clone()).unwrap();
+    let pending: Vec<String> = peer.blockchain.mempool.iter().map(|tx| tx.player_id.clone()).collect();
+    assert_eq!(pending, ["player2"]);
+}
+
+#[test]
+fn test_full_mempool_evicts_the_lowest_fee() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.consensus.mempool.max_transactions = 2;
+    let mut coordinator = game(genesis);
+    coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player1".to_string(), 5, 5, Weapon::Single, 0).unwrap();
+    coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player2".to_string(), 5, 5, Weapon::Single, 1).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 2);
+
+    // A fee-less shot cannot get in; a higher fee evicts player1's shot and refunds it
+    assert!(coordinator.fire_shot(DEFAULT_GAME_ID, "player2".to_string(), 6, 6).unwrap_err().contains("full"));
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 1);
+    coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player1".to_string(), 7, 7, Weapon::Single, 1).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+    let pending: Vec<u32> = coordinator.blockchain.mempool.iter().map(|tx| tx.fee).collect();
+    assert_eq!(pending, [1, 1]);
+}
+
+#[test]
+fn test_transactions_expire_after_their_ttl() {
+    let mut mempool = Mempool::new(MempoolLimits { max_transactions: 0, ttl_blocks: 2 });
+    mempool.insert(shot("player1", 0, 0), 3).unwrap();
+    assert!(mempool.expire(4).is_empty());
+    assert_eq!(mempool.expire(5).len(), 1);
+    assert!(mempool.is_empty());
+
+    // A shot nobody mines in time is dropped and its shot handed back
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.consensus.mempool.ttl_blocks = 2;
+    let mut coordinator = game(genesis);
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    coordinator.accept_block(mined_block(&coordinator, "player2", Vec::new())).unwrap();
+    assert_eq!(coordinator.blockchain.mempool.len(), 1);
+    coordinator.accept_block(mined_block(&coordinator, "player2", Vec::new())).unwrap();
+    assert!(coordinator.blockchain.mempool.is_empty());
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 3);
+}
+
+#[test]
+fn test_transactions_a_block_drops_hand_back_their_shots() {
+    let mut coordinator = game(GenesisConfig::new(10, 1));
+    let mut peer = game(GenesisConfig::new(10, 1));
+
+    // Both nodes see player1 spend nonce 0; the peer's own shot loses to the mined one
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    peer.transfer_shots(DEFAULT_GAME_ID, "player1", "player2", 2).unwrap();
+    assert_eq!(peer.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    peer.accept_block(coordinator.blockchain.chain[1].clone()).unwrap();
+    assert!(peer.blockchain.mempool.is_empty());
+    assert_eq!(peer.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 2);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 2);
+}
+
+#[test]
+fn test_stale_transactions_hand_back_their_shots() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.shot_lifetime = 2;
+    let mut coordinator = game(genesis);
+
+    // The shot's UTXO expires with block 2, so the pending shot goes stale
+    coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player1".to_string(), 5, 5, Weapon::Single, 0).unwrap();
+    coordinator.accept_block(mined_block(&coordinator, "player2", Vec::new())).unwrap();
+    assert!(coordinator.blockchain.mempool.is_empty());
+    let spent = coordinator.blockchain.shot_utxos.iter().filter(|u| u.spent).count();
+    assert_eq!(spent, 0);
+}
@@ -1,5 +1,5 @@
 use fleetchain::network::{NetworkNode, Peer};
-use fleetchain::game::Ship;
+use fleetchain::game::{Ship, DEFAULT_GAME_ID};
 use fleetchain::crypto::{generate_salt, create_commitment};
 use fleetchain::blockchain::Transaction;
 
@@ -21,7 +21,7 @@ async fn test_network_node_creation() {
     assert_eq!(node.port, 8080);
     
     let coordinator = node.coordinator.read().await;
-    assert_eq!(coordinator.grid.size, 10);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().grid.map.width, 10);
     assert_eq!(coordinator.blockchain.difficulty, 2);
 }
 
@@ -134,7 +134,7 @@ async fn test_node_with_game_state() {
     let node = NetworkNode::new("node1".to_string(), 8080, 10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
@@ -142,6 +142,7 @@ async fn test_node_with_game_state() {
     
     let mut coordinator = node.coordinator.write().await;
     let result = coordinator.register_player(
+        DEFAULT_GAME_ID,
         "player1".to_string(),
         ships,
         commitment,
@@ -149,7 +150,7 @@ async fn test_node_with_game_state() {
     );
     
     assert!(result.is_ok());
-    assert_eq!(coordinator.players.len(), 1);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players.len(), 1);
 }
 
 #[tokio::test]
@@ -157,17 +158,17 @@ async fn test_node_mining() {
     let node = NetworkNode::new("node1".to_string(), 8080, 10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     let mut coordinator = node.coordinator.write().await;
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     
     let initial_length = coordinator.blockchain.chain.len();
-    coordinator.mine_for_shots("player1").unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     
     assert_eq!(coordinator.blockchain.chain.len(), initial_length + 1);
 }
@@ -177,19 +178,19 @@ async fn test_node_transaction_handling() {
     let node = NetworkNode::new("node1".to_string(), 8080, 10, 2);
     
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     let mut coordinator = node.coordinator.write().await;
-    coordinator.register_player("player1".to_string(), ships, commitment, salt).unwrap();
-    coordinator.mine_for_shots("player1").unwrap();
+    coordinator.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
     
-    coordinator.fire_shot("player1".to_string(), 5, 5).unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
     
-    assert_eq!(coordinator.blockchain.pending_transactions.len(), 1);
+    assert_eq!(coordinator.blockchain.mempool.len(), 1);
 }
 
 #[tokio::test]
@@ -199,19 +200,19 @@ async fn test_multiple_nodes_independent_state() {
     
     // Register player on node1
     let ships = create_valid_fleet();
-    let positions: Vec<(u8, u8)> = ships.iter()
+    let positions: Vec<(u16, u16)> = ships.iter()
         .flat_map(|s| s.positions.clone())
         .collect();
     let salt = generate_salt();
     let commitment = create_commitment(&positions, &salt);
     
     let mut coordinator1 = node1.coordinator.write().await;
-    coordinator1.register_player("player1".to_string(), ships, commitment, salt).unwrap();
+    coordinator1.register_player(DEFAULT_GAME_ID, "player1".to_string(), ships, commitment, salt).unwrap();
     drop(coordinator1);
     
     // Node2 should have no players
     let coordinator2 = node2.coordinator.read().await;
-    assert_eq!(coordinator2.players.len(), 0);
+    assert_eq!(coordinator2.game(DEFAULT_GAME_ID).unwrap().players.len(), 0);
 }
 
 #[tokio::test]
@@ -252,7 +253,7 @@ async fn test_node_stats_access() {
     let node = NetworkNode::new("node1".to_string(), 8080, 10, 2);
     
     let coordinator = node.coordinator.read().await;
-    let stats = coordinator.get_stats();
+    let stats = coordinator.get_stats(DEFAULT_GAME_ID).unwrap();
     
     assert_eq!(stats.total_players, 0);
     assert_eq!(stats.round, 0);
@@ -350,6 +351,6 @@ async fn test_node_configuration() {
     assert_eq!(node.port, 9999);
     
     let coordinator = node.coordinator.read().await;
-    assert_eq!(coordinator.grid.size, 20);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().grid.map.width, 20);
     assert_eq!(coordinator.blockchain.difficulty, 5);
 }
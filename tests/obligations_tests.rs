@@ -0,0 +1,430 @@
+use fleetchain::blockchain::{Transaction, TransactionKind};
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::crypto::{generate_salt, create_commitment, fleet_root, HitProof, ShipProof};
+use fleetchain::game::{HitReport, DEFAULT_GAME_ID};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::lifecycle::GamePhase;
+use fleetchain::obligations::{MissedReportPenalty, ReportObligation, ReportRules};
+use fleetchain::shots::SelfFire;
+
+mod common;
+use common::{fleet_in_column, register};
+
+/// Two players, with player1's shot at (1, 0) mined into block 1
+///
+/// Self-fire is forbidden, so only player2 owes a report on the shot.
+fn game_with_mined_shot() -> GameCoordinator {
+    game_with_mined_shot_under(ReportRules::default())
+}
+
+fn game_with_mined_shot_under(reports: ReportRules) -> GameCoordinator {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.reports = reports;
+    genesis.game.shots.self_fire = SelfFire::Forbid;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 1, 0).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    coordinator
+}
+
+fn hit_report(player_id: &str, pos: (u16, u16)) -> HitReport {
+    let proof = HitProof::prove_hit(pos, &[], "");
+    HitReport::new(player_id.to_string(), pos.0, pos.1, true, proof.serialize())
+}
+
+#[test]
+fn test_obligation_fulfil() {
+    let mut obligation = ReportObligation::new(
+        "shot".to_string(), "player1".to_string(), "player2".to_string(), (1, 0), 1,
+    );
+    assert!(obligation.is_open());
+
+    obligation.fulfil(true, 2);
+    assert!(!obligation.is_open());
+    assert_eq!(obligation.fulfilled_in_block, Some(2));
+    assert_eq!(obligation.is_hit, Some(true));
+}
+
+#[test]
+fn test_pending_shot_creates_no_obligations() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 1, 0).unwrap();
+
+    assert!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+}
+
+#[test]
+fn test_mined_shot_creates_obligation_for_every_other_player() {
+    let coordinator = game_with_mined_shot();
+    let shot = &coordinator.blockchain.chain[1].transactions[0];
+
+    let owed = coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap();
+    assert_eq!(owed.len(), 1);
+    assert_eq!(owed[0].shot_id, shot.hash());
+    assert_eq!(owed[0].shooter, "player1");
+    assert_eq!((owed[0].shot_x, owed[0].shot_y), (1, 0));
+    assert_eq!(owed[0].shot_block, 1);
+
+    // The shooter does not report on their own shot
+    assert!(coordinator.open_obligations(DEFAULT_GAME_ID, "player1").unwrap().is_empty());
+}
+
+#[test]
+fn test_shooter_reports_on_their_own_shot_when_self_fire_is_allowed() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 0, 0).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+
+    // The shot covers player1's own ship, which takes the hit once player1 reports it
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player1").unwrap().len(), 1);
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player1", (0, 0))).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert!(game.players["player1"].hit_cells.contains(&(0, 0)));
+}
+
+#[test]
+fn test_report_is_recorded_on_chain_and_fulfils_obligation() {
+    let mut coordinator = game_with_mined_shot();
+
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap();
+
+    // Still owed until the report is mined
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+    assert_eq!(coordinator.blockchain.mempool.len(), 1);
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+
+    let report_tx = &coordinator.blockchain.chain[2].transactions[0];
+    let shot_id = coordinator.blockchain.chain[1].transactions[0].hash();
+    match &report_tx.kind {
+        TransactionKind::HitReport { shot_id: reported, is_hit, .. } => {
+            assert_eq!(reported, &shot_id);
+            assert!(is_hit);
+        }
+        other => panic!("Expected a hit report, got {:?}", other),
+    }
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    let fulfilled = game.obligations.iter()
+        .find(|o| o.player_id == "player2")
+        .unwrap();
+    assert_eq!(fulfilled.fulfilled_in_block, Some(2));
+
+    // Reports do not count as shots
+    assert_eq!(coordinator.get_stats(DEFAULT_GAME_ID).unwrap().total_shots, 1);
+}
+
+#[test]
+fn test_report_without_obligation_rejected() {
+    let mut coordinator = game_with_mined_shot();
+
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (5, 5))).unwrap_err();
+    assert!(err.contains("owes no report"));
+}
+
+#[test]
+fn test_duplicate_pending_report_rejected() {
+    let mut coordinator = game_with_mined_shot();
+
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap();
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap_err();
+    assert!(err.contains("already pending"));
+}
+
+#[test]
+fn test_received_block_creates_obligations() {
+    let source = game_with_mined_shot();
+
+    let mut peer = GameCoordinator::from_genesis(source.genesis.clone());
+    register(&mut peer, "player1", 0);
+    register(&mut peer, "player2", 1);
+    peer.accept_block(source.blockchain.chain[1].clone()).unwrap();
+
+    assert_eq!(peer.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+}
+
+#[test]
+fn test_adopting_a_fork_drops_the_abandoned_blocks_obligations() {
+    let mut coordinator = game_with_mined_shot();
+    let mut peer = GameCoordinator::from_genesis(coordinator.genesis.clone());
+    register(&mut peer, "player1", 0);
+    register(&mut peer, "player2", 1);
+    peer.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    peer.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    assert!(coordinator.adopt_chain(peer.blockchain.clone()).is_ok());
+    assert_eq!(coordinator.blockchain.chain.len(), 3);
+    assert!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+    for player_id in ["player1", "player2"] {
+        assert_eq!(
+            coordinator.get_unspent_shots(DEFAULT_GAME_ID, player_id),
+            peer.get_unspent_shots(DEFAULT_GAME_ID, player_id)
+        );
+    }
+    assert!(coordinator.verify_blockchain());
+
+    // A chain extending ours is applied block by block; a chain no longer than ours is refused
+    peer.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 1, 0).unwrap();
+    peer.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.adopt_chain(peer.blockchain.clone()).is_ok());
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+    assert!(coordinator.adopt_chain(peer.blockchain.clone()).unwrap_err().contains("not longer"));
+}
+
+#[test]
+fn test_shot_transaction_json_has_no_kind() {
+    let tx = Transaction::new("player1".to_string(), 5, 5, 0);
+    let json = serde_json::to_string(&tx).unwrap();
+    assert!(!json.contains("kind"));
+
+    let deserialized: Transaction = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.kind, TransactionKind::Shot);
+}
+
+/// Mine blocks (as player1) until the chain reaches the given height
+fn mine_to(coordinator: &mut GameCoordinator, height: u64) {
+    while coordinator.current_height() < height {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    }
+}
+
+fn rules(deadline_blocks: u64, penalty: MissedReportPenalty) -> ReportRules {
+    ReportRules { deadline_blocks, penalty }
+}
+
+#[test]
+fn test_report_accepted_until_deadline() {
+    let mut coordinator = game_with_mined_shot_under(rules(3, MissedReportPenalty::Forfeit));
+
+    // Shot mined in block 1, so block 4 is the last one that may carry the report
+    mine_to(&mut coordinator, 3);
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap();
+    mine_to(&mut coordinator, 4);
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(game.obligations[0].fulfilled_in_block, Some(4));
+    assert_eq!(game.obligations[0].penalized_in_block, None);
+    assert!(!coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+}
+
+#[test]
+fn test_missed_deadline_treated_as_hit() {
+    let mut coordinator = game_with_mined_shot_under(rules(3, MissedReportPenalty::TreatAsHit));
+
+    mine_to(&mut coordinator, 4);
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+
+    mine_to(&mut coordinator, 5);
+    assert!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(game.obligations[0].penalized_in_block, Some(5));
+    assert_eq!(game.obligations[0].is_hit, Some(true));
+    assert!(game.players["player2"].ships[0].is_hit_at(1, 0));
+
+    // Late reports are rejected
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap_err();
+    assert!(err.contains("owes no report"));
+}
+
+#[test]
+fn test_missed_deadline_loses_shots() {
+    let mut coordinator = game_with_mined_shot_under(rules(1, MissedReportPenalty::LoseShots { count: 2 }));
+    let shots_before = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2");
+    assert_eq!(shots_before, 2);
+
+    mine_to(&mut coordinator, 3);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 0);
+}
+
+#[test]
+fn test_missed_deadline_forfeits_game() {
+    let mut coordinator = game_with_mined_shot_under(rules(2, MissedReportPenalty::Forfeit));
+
+    mine_to(&mut coordinator, 4);
+    assert!(coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+    assert_eq!(coordinator.get_active_players(DEFAULT_GAME_ID), vec!["player1".to_string()]);
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(game.players["player2"].forfeited_at, Some(4));
+    assert_eq!(game.lifecycle.phase, GamePhase::GameOver);
+    assert_eq!(game.lifecycle.outcome.as_ref().unwrap().winner, Some("player1".to_string()));
+}
+
+#[test]
+fn test_no_deadline_never_penalizes() {
+    let mut coordinator = game_with_mined_shot_under(rules(0, MissedReportPenalty::Forfeit));
+
+    mine_to(&mut coordinator, 20);
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+    assert!(!coordinator.is_player_defeated(DEFAULT_GAME_ID, "player2"));
+}
+
+#[test]
+fn test_penalty_config_round_trip() {
+    let reports = rules(5, MissedReportPenalty::LoseShots { count: 3 });
+    let json = serde_json::to_string(&reports).unwrap();
+    assert!(json.contains("\"type\":\"LoseShots\""));
+    assert_eq!(serde_json::from_str::<ReportRules>(&json).unwrap(), reports);
+}
+
+#[test]
+fn test_report_takes_effect_when_mined() {
+    let mut coordinator = game_with_mined_shot();
+
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap();
+    assert!(!coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].ships[0].is_hit_at(1, 0));
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].ships[0].is_hit_at(1, 0));
+}
+
+#[test]
+fn test_peer_learns_report_from_block() {
+    let mut source = game_with_mined_shot();
+    source.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap();
+    source.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let mut peer = GameCoordinator::from_genesis(source.genesis.clone());
+    register(&mut peer, "player1", 0);
+    register(&mut peer, "player2", 1);
+    peer.accept_block(source.blockchain.chain[1].clone()).unwrap();
+    peer.accept_block(source.blockchain.chain[2].clone()).unwrap();
+
+    assert!(peer.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+    assert!(peer.game(DEFAULT_GAME_ID).unwrap().players["player2"].ships[0].is_hit_at(1, 0));
+}
+
+#[test]
+fn test_mined_report_with_invalid_proof_ignored() {
+    let mut coordinator = game_with_mined_shot();
+
+    let mut report = hit_report("player2", (1, 0));
+    report.shot_id = coordinator.blockchain.chain[1].transactions[0].hash();
+    report.proof = b"not a proof".to_vec();
+    coordinator.blockchain.add_transaction(Transaction::hit_report(DEFAULT_GAME_ID, &report)).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+    assert!(!coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].ships[0].is_hit_at(1, 0));
+}
+
+#[test]
+fn test_shot_results_for_shooter() {
+    let mut coordinator = game_with_mined_shot();
+    register(&mut coordinator, "player3", 2);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 2, 0).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", (1, 0))).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let results = coordinator.shot_results(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(results.len(), 2);
+
+    assert_eq!((results[0].shot_x, results[0].shot_y), (1, 0));
+    assert!(results[0].is_hit());
+    assert_eq!(results[0].hits, vec!["player2".to_string()]);
+
+    // player3 registered after the first shot was mined, so only the second shot involves them
+    assert_eq!((results[1].shot_x, results[1].shot_y), (2, 0));
+    assert!(!results[1].is_hit());
+    assert_eq!(results[1].pending, vec!["player2".to_string(), "player3".to_string()]);
+
+    assert!(coordinator.shot_results(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+}
+
+/// Each ship's cells with the salt of its commitment
+type CommittedFleet = Vec<(Vec<(u16, u16)>, String)>;
+
+/// player1 in column 0 and player2 in column 1 with a per-ship fleet root; returns player2's ship salts
+fn game_with_ship_roots() -> (GameCoordinator, CommittedFleet) {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+
+    let ships = fleet_in_column(1);
+    let committed: CommittedFleet = ships.iter()
+        .map(|ship| (ship.positions.clone(), generate_salt()))
+        .collect();
+    let positions: Vec<(u16, u16)> = ships.iter()
+        .flat_map(|s| s.positions.clone())
+        .collect();
+    let salt = generate_salt();
+    let commitment = create_commitment(&positions, &salt);
+    coordinator.register_player_with_ship_root(
+        DEFAULT_GAME_ID,
+        "player2".to_string(),
+        ships,
+        commitment,
+        salt,
+        Some(fleet_root(&committed)),
+    ).unwrap();
+
+    (coordinator, committed)
+}
+
+/// player1 fires at a cell of player2, who reports the hit; both are mined
+fn hit_player2(coordinator: &mut GameCoordinator, pos: (u16, u16)) {
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), pos.0, pos.1).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.report_hit(DEFAULT_GAME_ID, hit_report("player2", pos)).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+}
+
+#[test]
+fn test_sunk_declaration_reaches_shooter() {
+    let (mut coordinator, committed) = game_with_ship_roots();
+
+    // The Submarine at (1, 7) and (1, 8) is ship index 2
+    let proof = ShipProof::prove(&committed, 2);
+    hit_player2(&mut coordinator, (1, 7));
+    let err = coordinator.declare_sunk(DEFAULT_GAME_ID, "player2", "Submarine", proof.clone()).unwrap_err();
+    assert!(err.contains("(1, 8) has no confirmed hit"));
+
+    hit_player2(&mut coordinator, (1, 8));
+    coordinator.declare_sunk(DEFAULT_GAME_ID, "player2", "submarine", proof.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(game.sunk_ships.len(), 1);
+    assert_eq!(game.sunk_ships[0].ship_name, "Submarine");
+
+    let results = coordinator.shot_results(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(results[0].sunk.is_empty());
+    assert_eq!(results[1].sunk.len(), 1);
+    assert_eq!(results[1].sunk[0].player_id, "player2");
+    assert_eq!(results[1].sunk[0].ship_name, "Submarine");
+
+    let err = coordinator.declare_sunk(DEFAULT_GAME_ID, "player2", "Submarine", proof).unwrap_err();
+    assert!(err.contains("already declared sunk"));
+}
+
+#[test]
+fn test_sunk_declaration_must_match_commitment_and_class() {
+    let (mut coordinator, committed) = game_with_ship_roots();
+    hit_player2(&mut coordinator, (1, 9));
+
+    let destroyer = ShipProof::prove(&committed, 3);
+    let err = coordinator.declare_sunk(DEFAULT_GAME_ID, "player2", "Cruiser", destroyer.clone()).unwrap_err();
+    assert!(err.contains("Cruiser has 3 cells but 1 were revealed"));
+
+    let mut forged = destroyer.clone();
+    forged.positions = vec![(1, 9)];
+    forged.salt = generate_salt();
+    let err = coordinator.declare_sunk(DEFAULT_GAME_ID, "player2", "Destroyer", forged).unwrap_err();
+    assert!(err.contains("does not match"));
+
+    // Players who registered without a fleet root cannot declare sunk ships
+    let err = coordinator.declare_sunk(DEFAULT_GAME_ID, "player1", "Destroyer", destroyer).unwrap_err();
+    assert!(err.contains("did not commit"));
+}
@@ -0,0 +1,201 @@
+use fleetchain::coinbase::Coinbase;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::pool::{split_reward, MiningPool, Share};
+use fleetchain::rewards::RewardPolicy;
+
+mod common;
+use common::{register, register_in_game};
+
+fn pool_genesis() -> GenesisConfig {
+    let mut genesis = GenesisConfig::new(10, 2);
+    genesis.rewards.mining_reward = 4;
+    genesis
+}
+
+fn pool_game() -> GameCoordinator {
+    let mut coordinator = GameCoordinator::from_genesis(pool_genesis());
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator
+}
+
+/// Work `count` fresh shares for a player and submit them to the default game's pool
+fn contribute(coordinator: &mut GameCoordinator, player_id: &str, count: usize) {
+    contribute_in(coordinator, DEFAULT_GAME_ID, player_id, count);
+}
+
+/// Work `count` fresh shares for a player and submit them to a game's pool
+fn contribute_in(coordinator: &mut GameCoordinator, game_id: &str, player_id: &str, count: usize) {
+    let worker = coordinator.shot_account(game_id, player_id);
+    let template = coordinator.blockchain.get_latest_block().hash.clone();
+    let difficulty = coordinator.blockchain.share_difficulty;
+    let mut nonce = 0;
+    for _ in 0..count {
+        let share = Share::find(&worker, &template, difficulty, nonce);
+        coordinator.submit_share(game_id, player_id, share.nonce).unwrap();
+        nonce = share.nonce + 1;
+    }
+}
+
+fn shares(worker: &str, count: u64) -> Vec<Share> {
+    (0..count).map(|nonce| Share { worker: worker.to_string(), nonce }).collect()
+}
+
+#[test]
+fn test_split_reward_in_proportion_to_shares() {
+    let mut all = shares("a", 3);
+    all.extend(shares("b", 1));
+    assert_eq!(split_reward(&all, 4), vec![("a".to_string(), 3), ("b".to_string(), 1)]);
+
+    // Leftover shots go to the largest remainders, ties to the lower worker ID
+    let mut all = shares("a", 2);
+    all.extend(shares("b", 1));
+    assert_eq!(split_reward(&all, 5), vec![("a".to_string(), 3), ("b".to_string(), 2)]);
+
+    let mut all = shares("b", 1);
+    all.extend(shares("a", 1));
+    assert_eq!(split_reward(&all, 1), vec![("a".to_string(), 1)]);
+    assert!(split_reward(&[], 3).is_empty());
+}
+
+#[test]
+fn test_pool_accepts_valid_shares_once() {
+    let mut pool = MiningPool::default();
+    let share = Share::find("a", "block1", 2, 0);
+    assert!(share.hash("block1").starts_with("00"));
+
+    pool.submit(share.clone(), "block1", 2).unwrap();
+    assert!(pool.submit(share.clone(), "block1", 2).unwrap_err().contains("already submitted"));
+
+    let weak = (0..).map(|nonce| Share { worker: "a".to_string(), nonce })
+        .find(|s| !s.meets("block1", 2))
+        .unwrap();
+    assert!(pool.submit(weak, "block1", 2).unwrap_err().contains("share difficulty"));
+    assert_eq!(pool.shares_of("a"), 1);
+
+    // Shares for an older block are dropped once work moves on
+    pool.submit(Share::find("b", "block2", 2, 0), "block2", 2).unwrap();
+    assert_eq!(pool.shares_of("a"), 0);
+    assert_eq!(pool.shares_of("b"), 1);
+}
+
+#[test]
+fn test_coinbase_verification() {
+    // Shares worked on "prev" that are not valid work on "other"
+    let stale_elsewhere = |worker: &str| (0..)
+        .map(|nonce| Share { worker: worker.to_string(), nonce })
+        .find(|s| s.meets("prev", 1) && !s.meets("other", 1))
+        .unwrap();
+    let shares = vec![stale_elsewhere("a"), stale_elsewhere("b")];
+    let coinbase = Coinbase::pool(DEFAULT_GAME_ID, shares.clone());
+    assert!(coinbase.check_shares("prev", 1).is_ok());
+    assert!(coinbase.check_shares("other", 1).unwrap_err().contains("share difficulty"));
+
+    let mut greedy = coinbase.clone();
+    greedy.payouts = vec![("a".to_string(), 2)];
+    assert!(greedy.check_payouts(&split_reward(&shares, 2)).unwrap_err().contains("payouts"));
+
+    let doubled = Coinbase::pool(DEFAULT_GAME_ID, vec![shares[0].clone(), shares[0].clone()]);
+    assert!(doubled.check_shares("prev", 1).unwrap_err().contains("twice"));
+    let mut named = coinbase.clone();
+    named.miner = Some("a".to_string());
+    assert!(named.check_shares("prev", 1).is_err());
+}
+
+#[test]
+fn test_pool_block_splits_reward_among_workers() {
+    let mut coordinator = pool_game();
+    contribute(&mut coordinator, "player1", 3);
+    contribute(&mut coordinator, "player2", 1);
+
+    let payouts = coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(payouts, vec![
+        (GameCoordinator::account_id(DEFAULT_GAME_ID, "player1"), 3),
+        (GameCoordinator::account_id(DEFAULT_GAME_ID, "player2"), 1),
+    ]);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 4);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 2);
+
+    let block = coordinator.blockchain.get_latest_block();
+    assert_eq!(block.coinbase.as_ref().unwrap().shares.len(), 4);
+    assert!(coordinator.verify_blockchain());
+    assert!(coordinator.pool(DEFAULT_GAME_ID).unwrap().shares.is_empty());
+    assert!(coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap_err().contains("no shares"));
+}
+
+#[test]
+fn test_peers_check_pool_coinbase() {
+    let mut coordinator = pool_game();
+    contribute(&mut coordinator, "player1", 2);
+    contribute(&mut coordinator, "player2", 2);
+    coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap();
+    let block = coordinator.blockchain.get_latest_block().clone();
+
+    let mut peer = pool_game();
+    let mut tampered = block.clone();
+    tampered.coinbase.as_mut().unwrap().payouts = vec![("default/player1".to_string(), 4)];
+    tampered.hash = tampered.calculate_hash();
+    tampered.mine(coordinator.blockchain.difficulty);
+    assert!(peer.accept_block(tampered).unwrap_err().contains("payouts"));
+    peer.accept_block(block).unwrap();
+
+    // Rewriting the split after the fact breaks the block hash
+    coordinator.blockchain.chain[1].coinbase.as_mut().unwrap().payouts.reverse();
+    assert!(!coordinator.verify_blockchain());
+}
+
+#[test]
+fn test_stale_shares_are_not_paid() {
+    let mut coordinator = pool_game();
+    contribute(&mut coordinator, "player1", 1);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+
+    assert!(coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap_err().contains("no shares"));
+    assert!(coordinator.submit_share(DEFAULT_GAME_ID, "ghost", 0).unwrap_err().contains("not found"));
+}
+
+#[test]
+fn test_pools_pay_their_game_within_its_caps() {
+    let mut coordinator = pool_game();
+    let mut config = coordinator.genesis.game.clone();
+    config.rewards = Some(RewardPolicy { mining_reward: 6, max_balance: 3, ..RewardPolicy::default() });
+    coordinator.create_game("capped", config).unwrap();
+    register_in_game(&mut coordinator, "capped", "player1", 0);
+    register_in_game(&mut coordinator, "capped", "player2", 1);
+
+    // Each game collects its own shares
+    contribute_in(&mut coordinator, "capped", "player1", 1);
+    contribute_in(&mut coordinator, "capped", "player2", 1);
+    assert!(coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap_err().contains("no shares"));
+
+    // Half of the game's reward each, cut down to the game's maximum balance
+    assert_eq!(coordinator.mine_for_pool("capped").unwrap(), vec![
+        (GameCoordinator::account_id("capped", "player1"), 2),
+        (GameCoordinator::account_id("capped", "player2"), 2),
+    ]);
+    assert_eq!(coordinator.get_unspent_shots("capped", "player1"), 3);
+    assert!(coordinator.verify_blockchain());
+}
+
+#[test]
+fn test_pool_workers_must_be_able_to_mine_in_the_game() {
+    let mut coordinator = pool_game();
+    let mut config = coordinator.genesis.game.clone();
+    config.phases.min_players = 3;
+    coordinator.create_game("lobby", config).unwrap();
+    register_in_game(&mut coordinator, "lobby", "player1", 0);
+    let template = coordinator.blockchain.get_latest_block().hash.clone();
+    let difficulty = coordinator.blockchain.share_difficulty;
+
+    // Shares from another game's account, or from a game still in its lobby, are not paid
+    let outsider = Share::find(&GameCoordinator::account_id("lobby", "player1"), &template, difficulty, 0);
+    let coinbase = Coinbase::pool(DEFAULT_GAME_ID, vec![outsider.clone()]);
+    let err = coordinator.coinbase_payouts(&coinbase, 1, &[]).unwrap_err();
+    assert!(err.contains("not a player of game default"), "{}", err);
+
+    let coinbase = Coinbase::pool("lobby", vec![outsider]);
+    let err = coordinator.coinbase_payouts(&coinbase, 1, &[]).unwrap_err();
+    assert!(err.contains("Lobby"), "{}", err);
+}
@@ -0,0 +1,222 @@
+use fleetchain::blockchain::{Transaction, TransactionKind};
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::crypto::{generate_salt, create_commitment, HitProof, MoveProof};
+use fleetchain::game::{Ship, HitReport, DEFAULT_GAME_ID};
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::lifecycle::GamePhase;
+use fleetchain::map::GameMap;
+use fleetchain::relocation::{check_slide, relocate, RevealedBoard};
+
+mod common;
+use common::{Board, fleet_in_column, register};
+
+/// Two players with no report deadline; player2 has enough shots to move twice
+fn two_player_game() -> (GameCoordinator, Board) {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.reports.deadline_blocks = 0;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register(&mut coordinator, "player1", 0);
+    let board2 = register(&mut coordinator, "player2", 5);
+    for _ in 0..3 {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    }
+    (coordinator, board2)
+}
+
+/// player2's board after sliding the Destroyer from (5, 9) to (7, 9)
+fn moved_board() -> Vec<(u16, u16)> {
+    let mut ships = fleet_in_column(5);
+    ships[3] = Ship::new("Destroyer".to_string(), vec![(7, 9)]);
+    ships.iter().flat_map(|s| s.positions.clone()).collect()
+}
+
+fn miss(player_id: &str, pos: (u16, u16), board: &Board) -> HitReport {
+    let proof = HitProof::prove_miss(pos, &board.0, &board.1);
+    HitReport::new(player_id.to_string(), pos.0, pos.1, false, proof.serialize())
+}
+
+#[test]
+fn test_relocate_allows_short_slides_only() {
+    let fleet = fleet_in_column(5);
+    let mut map = GameMap::square(10);
+
+    let moved = relocate(&fleet, "Submarine", &[(6, 7), (6, 8)], &[], 2, &map).unwrap();
+    assert_eq!(moved[2].positions, vec![(6, 7), (6, 8)]);
+    assert_eq!(moved[0], fleet[0]);
+
+    let err = relocate(&fleet, "Submarine", &[(8, 7), (8, 8)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("between 1 and 2"));
+    let err = relocate(&fleet, "Submarine", &[(5, 7), (5, 8)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("between 1 and 2"));
+    let err = relocate(&fleet, "Submarine", &[(6, 7), (7, 7)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("shape"));
+    let err = relocate(&fleet, "Destroyer", &[(5, 10)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("grid"));
+    let err = relocate(&fleet, "Destroyer", &[(5, 8)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("overlap"));
+    let err = relocate(&fleet, "Submarine", &[(6, 7), (6, 8)], &[(5, 8)], 2, &map).unwrap_err();
+    assert!(err.contains("has been hit"));
+    assert!(relocate(&fleet, "Battleship", &[(0, 0)], &[], 2, &map).unwrap_err().contains("not found"));
+
+    let mut crossing = fleet.clone();
+    crossing[3] = Ship::new("Destroyer".to_string(), vec![(5, 8)]);
+    let err = relocate(&crossing, "Destroyer", &[(6, 8)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("shares cells"));
+
+    map.blocked.push((6, 8));
+    let err = relocate(&fleet, "Submarine", &[(6, 7), (6, 8)], &[], 2, &map).unwrap_err();
+    assert!(err.contains("land"));
+}
+
+#[test]
+fn test_move_swaps_commitment_once_mined() {
+    let (mut coordinator, board2) = two_player_game();
+    let shots = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2");
+    let old_commitment = coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].board_commitment.clone();
+
+    let new_salt = generate_salt();
+    let commitment = coordinator.move_ship(DEFAULT_GAME_ID, "player2", "Destroyer", vec![(7, 9)], new_salt.clone()).unwrap();
+    assert_eq!(commitment, create_commitment(&moved_board(), &new_salt));
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), shots - 2);
+
+    let err = coordinator.move_ship(DEFAULT_GAME_ID, "player2", "Submarine", vec![(6, 7), (6, 8)], generate_salt()).unwrap_err();
+    assert!(err.contains("already pending"));
+
+    // The old board still answers until the move is mined
+    let player = &coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"];
+    assert_eq!(player.board_commitment, old_commitment);
+    assert_eq!(player.get_all_ship_positions(), board2.0);
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let mined = &coordinator.blockchain.get_latest_block().transactions;
+    assert!(mined.iter().any(|tx| matches!(tx.kind, TransactionKind::ShipMove { .. })));
+
+    let player = &coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"];
+    assert_eq!(player.board_commitment, commitment);
+    assert_eq!(player.board_history.len(), 1);
+    assert_eq!(player.board_history[0].commitment, old_commitment);
+    assert_eq!(player.salt, new_salt);
+    assert_eq!(player.get_all_ship_positions(), moved_board());
+}
+
+#[test]
+fn test_move_rejected_for_ship_under_fire() {
+    let (mut coordinator, _) = two_player_game();
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 9).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let err = coordinator.move_ship(DEFAULT_GAME_ID, "player2", "Destroyer", vec![(7, 9)], generate_salt()).unwrap_err();
+    assert!(err.contains("unreported shots"));
+}
+
+#[test]
+fn test_reports_use_the_board_held_when_the_shot_was_mined() {
+    let (mut coordinator, board2) = two_player_game();
+
+    // A shot at (7, 9) is mined before the Destroyer moves there
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 7, 9).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let new_salt = generate_salt();
+    coordinator.move_ship(DEFAULT_GAME_ID, "player2", "Destroyer", vec![(7, 9)], new_salt.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    // A miss proven against the new board is rejected for the earlier shot
+    let new_board = (moved_board(), new_salt);
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, miss("player2", (7, 9), &new_board)).unwrap_err();
+    assert_eq!(err, "Invalid proof");
+    coordinator.report_hit(DEFAULT_GAME_ID, miss("player2", (7, 9), &board2)).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    // Later shots are answered against the new board
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 9).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.report_hit(DEFAULT_GAME_ID, miss("player2", (5, 9), &board2)).is_err());
+    coordinator.report_hit(DEFAULT_GAME_ID, miss("player2", (5, 9), &new_board)).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().is_empty());
+}
+
+#[test]
+fn test_reveal_after_moving_opens_every_board() {
+    let (mut coordinator, board2) = two_player_game();
+    let new_salt = generate_salt();
+    coordinator.move_ship(DEFAULT_GAME_ID, "player2", "Destroyer", vec![(7, 9)], new_salt.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    coordinator.declare_defeat(DEFAULT_GAME_ID, "player2", moved_board(), new_salt.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::GameOver);
+
+    // The defeat opened the final board; the audit still needs the earlier one
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert!(audit.unrevealed.contains(&"player2".to_string()));
+
+    let err = coordinator.reveal_board(DEFAULT_GAME_ID, "player2", moved_board(), new_salt.clone()).unwrap_err();
+    assert!(err.contains("every earlier board"));
+    let wrong = vec![RevealedBoard { positions: moved_board(), salt: board2.1.clone() }];
+    let err = coordinator.reveal_board_with_history(DEFAULT_GAME_ID, "player2", moved_board(), new_salt.clone(), wrong).unwrap_err();
+    assert!(err.contains("earlier board"));
+
+    let earlier = vec![RevealedBoard { positions: board2.0.clone(), salt: board2.1.clone() }];
+    coordinator.reveal_board_with_history(DEFAULT_GAME_ID, "player2", moved_board(), new_salt, earlier).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let player = &coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"];
+    assert!(player.has_revealed());
+    assert_eq!(player.revealed_history[0].positions, board2.0);
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert!(!audit.unrevealed.contains(&"player2".to_string()));
+    assert!(audit.cheaters.is_empty());
+}
+
+#[test]
+fn test_check_slide_accepts_one_legal_slide_only() {
+    let map = GameMap::new(10, 10);
+    let board = vec![(2, 2), (3, 2), (4, 2), (8, 8)];
+
+    // Along its own line and sideways
+    assert!(check_slide(&board, &[(3, 2), (4, 2), (5, 2), (8, 8)], &[], 1, &map).is_ok());
+    assert!(check_slide(&board, &[(2, 3), (3, 3), (4, 3), (8, 8)], &[], 1, &map).is_ok());
+    assert!(check_slide(&board, &[(2, 2), (3, 2), (4, 2), (8, 6)], &[], 2, &map).is_ok());
+
+    // Too far, unchanged, two ships at once, reshaped
+    assert!(check_slide(&board, &[(2, 2), (3, 2), (4, 2), (8, 6)], &[], 1, &map).is_err());
+    assert!(check_slide(&board, &board, &[], 1, &map).is_err());
+    assert!(check_slide(&board, &[(3, 2), (4, 2), (5, 2), (8, 7)], &[], 2, &map).is_err());
+    assert!(check_slide(&board, &[(2, 2), (3, 2), (3, 3), (8, 8)], &[], 1, &map).is_err());
+
+    // A hit ship stays put
+    let err = check_slide(&board, &[(3, 2), (4, 2), (5, 2), (8, 8)], &[(4, 2)], 1, &map).unwrap_err();
+    assert!(err.contains("hit"));
+}
+
+#[test]
+fn test_audit_flags_a_forged_ship_move() {
+    let (mut coordinator, board2) = two_player_game();
+
+    // Teleport the Destroyer across the board behind a proof that only binds the commitments
+    let mut ships = fleet_in_column(5);
+    ships[3] = Ship::new("Destroyer".to_string(), vec![(0, 0)]);
+    let forged: Vec<(u16, u16)> = ships.iter().flat_map(|s| s.positions.clone()).collect();
+    let new_salt = generate_salt();
+    let proof = MoveProof::prove(&board2.0, &board2.1, &forged, &new_salt, 1);
+    let account = coordinator.shot_account(DEFAULT_GAME_ID, "player2");
+    let mut transaction = Transaction::ship_move(DEFAULT_GAME_ID, "player2", proof, None);
+    transaction.nonce = coordinator.blockchain.next_nonce(DEFAULT_GAME_ID, "player2");
+    transaction.spent_utxos = coordinator.blockchain.consume_shots(&account, 2).unwrap();
+    coordinator.receive_transaction(transaction).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().players["player2"].board_history.len(), 1);
+
+    coordinator.declare_defeat(DEFAULT_GAME_ID, "player2", forged.clone(), new_salt.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let earlier = vec![RevealedBoard { positions: board2.0.clone(), salt: board2.1.clone() }];
+    coordinator.reveal_board_with_history(DEFAULT_GAME_ID, "player2", forged, new_salt, earlier).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let audit = coordinator.audit(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(audit.cheaters, vec!["player2".to_string()]);
+    assert_eq!(audit.illegal_moves.len(), 1);
+    assert_eq!(audit.illegal_moves[0].player_id, "player2");
+    assert!(audit.discrepancies.is_empty());
+}
@@ -0,0 +1,251 @@
+use fleetchain::blockchain::Transaction;
+use fleetchain::coinbase::Coinbase;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::pool::Share;
+use fleetchain::rewards::{RewardDecay, RewardPolicy};
+
+mod common;
+use common::{mined_block, register_in_game};
+
+/// Submit one fresh pool share for a player on top of the latest block
+fn contribute(coordinator: &mut GameCoordinator, player_id: &str) -> Result<usize, String> {
+    let worker = coordinator.shot_account(DEFAULT_GAME_ID, player_id);
+    let template = coordinator.blockchain.get_latest_block().hash.clone();
+    let share = Share::find(&worker, &template, coordinator.blockchain.share_difficulty, 0);
+    coordinator.submit_share(DEFAULT_GAME_ID, player_id, share.nonce)
+}
+
+#[test]
+fn test_block_reward_decays_with_height() {
+    let halving = RewardPolicy {
+        mining_reward: 8,
+        decay: RewardDecay::Halving { interval: 10 },
+        ..RewardPolicy::default()
+    };
+    assert_eq!(halving.block_reward(1), 8);
+    assert_eq!(halving.block_reward(10), 4);
+    assert_eq!(halving.block_reward(25), 2);
+    assert_eq!(halving.block_reward(1000), 0);
+
+    let linear = RewardPolicy {
+        mining_reward: 5,
+        decay: RewardDecay::Linear { interval: 5, step: 2 },
+        min_reward: 1,
+        ..RewardPolicy::default()
+    };
+    assert_eq!(linear.block_reward(4), 5);
+    assert_eq!(linear.block_reward(5), 3);
+    assert_eq!(linear.block_reward(10), 1);
+    assert_eq!(linear.block_reward(50), 1);
+}
+
+#[test]
+fn test_catch_up_bonus_and_balance_cap() {
+    let policy = RewardPolicy {
+        mining_reward: 2,
+        catch_up_per_hit: 1,
+        catch_up_max: 3,
+        max_balance: 6,
+        ..RewardPolicy::default()
+    };
+    assert_eq!(policy.catch_up_bonus(0), 0);
+    assert_eq!(policy.catch_up_bonus(2), 2);
+    assert_eq!(policy.catch_up_bonus(9), 3);
+
+    assert_eq!(policy.mining_payout(1, 2, 0), 4);
+    assert_eq!(policy.mining_payout(1, 9, 3), 3);
+    assert_eq!(policy.mining_payout(1, 0, 6), 0);
+    assert_eq!(policy.registration_grant(6), 0);
+    assert!(policy.is_full(6));
+    assert!(!RewardPolicy::default().is_full(1000));
+}
+
+#[test]
+fn test_games_choose_their_own_policy() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    let mut config = coordinator.genesis.game.clone();
+    config.rewards = Some(RewardPolicy {
+        mining_reward: 3,
+        registration_shots: 4,
+        ..RewardPolicy::default()
+    });
+    coordinator.create_game("rich", config).unwrap();
+
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player1", 0);
+    register_in_game(&mut coordinator, "rich", "player1", 0);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+    assert_eq!(coordinator.get_unspent_shots("rich", "player1"), 4);
+
+    assert_eq!(coordinator.mine_for_shots("rich", "player1").unwrap(), 3);
+    assert_eq!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap(), 1);
+    assert_eq!(coordinator.reward_policy("rich").unwrap().mining_reward, 3);
+    assert_eq!(coordinator.reward_policy(DEFAULT_GAME_ID).unwrap(), coordinator.blockchain.rewards);
+}
+
+#[test]
+fn test_peers_mint_and_check_the_miners_coinbase() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.max_balance = 2;
+    let mut coordinator = GameCoordinator::from_genesis(genesis.clone());
+    let mut peer = GameCoordinator::from_genesis(genesis);
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player1", 0);
+    register_in_game(&mut peer, DEFAULT_GAME_ID, "player1", 0);
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    peer.accept_block(coordinator.blockchain.chain[1].clone()).unwrap();
+    assert_eq!(peer.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 2);
+
+    // A full account earns nothing, so a coinbase paying it anyway is refused, as is a block without one
+    let mut block = mined_block(&peer, "player1", Vec::new());
+    assert_eq!(block.coinbase.as_ref().unwrap().total(), 0);
+    block.coinbase.as_mut().unwrap().payouts[0].1 = 1;
+    block.hash = block.calculate_hash();
+    block.mine(peer.blockchain.difficulty);
+    assert!(peer.accept_block(block.clone()).unwrap_err().contains("payouts"));
+    block.coinbase = None;
+    block.hash = block.calculate_hash();
+    block.mine(peer.blockchain.difficulty);
+    assert!(peer.accept_block(block).unwrap_err().contains("no coinbase"));
+}
+
+#[test]
+fn test_peers_refuse_coinbases_of_games_they_do_not_host() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "alice", 0);
+    let balance = coordinator.get_unspent_shots(DEFAULT_GAME_ID, "alice");
+
+    let mut block = mined_block(&coordinator, "alice", Vec::new());
+    let mut coinbase = Coinbase::sealed("ghost");
+    coinbase.payouts = vec![("default/alice".to_string(), 1000)];
+    block.coinbase = Some(coinbase);
+    block.hash = block.calculate_hash();
+    block.mine(coordinator.blockchain.difficulty);
+    assert!(coordinator.accept_block(block).unwrap_err().contains("ghost"));
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "alice"), balance);
+}
+
+#[test]
+fn test_chain_validation_checks_coinbases_against_their_game() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    let mut config = coordinator.genesis.game.clone();
+    config.rewards = Some(RewardPolicy {
+        mining_reward: 3,
+        ..RewardPolicy::default()
+    });
+    coordinator.create_game("rich", config).unwrap();
+    register_in_game(&mut coordinator, "rich", "player1", 0);
+    coordinator.mine_for_shots("rich", "player1").unwrap();
+    assert!(coordinator.verify_blockchain());
+
+    // The chain's own checks only know the chain-wide rewards, so the overpaying coinbase
+    // is caught by replaying the block against the game's policy
+    let block = coordinator.blockchain.chain.last_mut().unwrap();
+    block.coinbase.as_mut().unwrap().payouts[0].1 = 5;
+    block.hash = block.calculate_hash();
+    block.mine(coordinator.blockchain.difficulty);
+    assert!(coordinator.blockchain.is_chain_valid());
+    assert!(!coordinator.verify_blockchain());
+}
+
+#[test]
+fn test_players_who_took_hits_mine_a_bonus() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.catch_up_per_hit = 1;
+    genesis.rewards.max_balance = 4;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player1", 0);
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player2", 1);
+
+    let player2 = coordinator.game_mut(DEFAULT_GAME_ID).unwrap().players.get_mut("player2").unwrap();
+    player2.record_hit(1, 0);
+    player2.record_hit(1, 1);
+
+    assert_eq!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap(), 1);
+    assert_eq!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap(), 3);
+
+    // The maximum balance cuts the payout and keeps full accounts out of the pool
+    assert_eq!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap(), 0);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 4);
+    assert!(contribute(&mut coordinator, "player2").unwrap_err().contains("maximum"));
+    assert_eq!(contribute(&mut coordinator, "player1").unwrap(), 1);
+}
+
+#[test]
+fn test_peers_check_pool_rewards_against_the_decay() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.mining_reward = 4;
+    genesis.rewards.decay = RewardDecay::Halving { interval: 2 };
+    let mut coordinator = GameCoordinator::from_genesis(genesis.clone());
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player1", 0);
+
+    contribute(&mut coordinator, "player1").unwrap();
+    assert_eq!(coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap()[0].1, 4);
+    contribute(&mut coordinator, "player1").unwrap();
+    assert_eq!(coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap()[0].1, 2);
+    assert!(coordinator.verify_blockchain());
+
+    let mut peer = GameCoordinator::from_genesis(genesis.clone());
+    register_in_game(&mut peer, DEFAULT_GAME_ID, "player1", 0);
+    peer.accept_block(coordinator.blockchain.chain[1].clone()).unwrap();
+    peer.accept_block(coordinator.blockchain.chain[2].clone()).unwrap();
+
+    // A pool block still paying the undecayed reward is rejected
+    let mut greedy = GameCoordinator::from_genesis(genesis.clone());
+    register_in_game(&mut greedy, DEFAULT_GAME_ID, "player1", 0);
+    greedy.blockchain.rewards.decay = RewardDecay::None;
+    contribute(&mut greedy, "player1").unwrap();
+    greedy.mine_for_pool(DEFAULT_GAME_ID).unwrap();
+    contribute(&mut greedy, "player1").unwrap();
+    greedy.mine_for_pool(DEFAULT_GAME_ID).unwrap();
+
+    let mut peer = GameCoordinator::from_genesis(genesis);
+    register_in_game(&mut peer, DEFAULT_GAME_ID, "player1", 0);
+    peer.accept_block(greedy.blockchain.chain[1].clone()).unwrap();
+    assert!(peer.accept_block(greedy.blockchain.chain[2].clone()).unwrap_err().contains("payouts"));
+}
+
+#[test]
+fn test_shots_expire_after_their_lifetime() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.shot_lifetime = 2;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player1", 0);
+
+    let balance = coordinator.shot_balance(DEFAULT_GAME_ID, "player1");
+    assert_eq!((balance.spendable, balance.expiring, balance.next_expiry), (1, 1, Some(2)));
+
+    // The registration shot expires as block 2 is mined; the mined one lasts until block 3
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+    assert_eq!(coordinator.shot_balance(DEFAULT_GAME_ID, "player1").next_expiry, Some(3));
+
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    let shot = &coordinator.blockchain.mempool.transactions()[0];
+    assert_eq!(shot.spent_utxos, vec![coordinator.blockchain.shot_utxos[1].id.clone()]);
+    assert!(coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 6, 6).is_err());
+}
+
+#[test]
+fn test_peers_reject_blocks_spending_expired_shots() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.shot_lifetime = 2;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register_in_game(&mut coordinator, DEFAULT_GAME_ID, "player1", 0);
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    let registration_shot = coordinator.blockchain.shot_utxos[0].id.clone();
+    let mined_shot = coordinator.blockchain.shot_utxos[1].id.clone();
+
+    let spending = |utxo: &str| {
+        let mut shot = Transaction::new("player1".to_string(), 5, 5, 0);
+        shot.spent_utxos = vec![utxo.to_string()];
+        mined_block(&coordinator, "player1", vec![shot])
+    };
+    let stale = spending(&registration_shot);
+    let fresh = spending(&mined_shot);
+
+    assert!(coordinator.accept_block(stale).unwrap_err().contains("expired at block 2"));
+    coordinator.accept_block(fresh).unwrap();
+    assert!(coordinator.verify_blockchain());
+}
@@ -0,0 +1,152 @@
+use fleetchain::blockchain::Transaction;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::shots::SelfFire;
+use fleetchain::weapons::Weapon;
+
+mod common;
+use common::{mined_block, register_in_team};
+
+/// player1 and player2 in team red against player3, each holding four shots
+fn game(genesis: GenesisConfig) -> GameCoordinator {
+    let mut genesis = genesis;
+    genesis.rewards.registration_shots = 4;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register_in_team(&mut coordinator, "player1", 0, Some("red"));
+    register_in_team(&mut coordinator, "player2", 1, Some("red"));
+    register_in_team(&mut coordinator, "player3", 2, None);
+    coordinator
+}
+
+fn no_repeats() -> GenesisConfig {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.shots.no_repeat_targets = true;
+    genesis
+}
+
+/// A shot as another node would relay it, spending the player's `i`th spendable shot
+fn relayed_shot(coordinator: &GameCoordinator, player_id: &str, target: (u16, u16), nonce: u64, i: usize) -> Transaction {
+    let account = coordinator.shot_account(DEFAULT_GAME_ID, player_id);
+    let mut shot = Transaction::new(player_id.to_string(), target.0, target.1, nonce);
+    shot.spent_utxos = vec![coordinator.blockchain.select_shots(&account, i as u32 + 1).unwrap()[i].clone()];
+    shot
+}
+
+fn fire(coordinator: &mut GameCoordinator, player_id: &str, x: u16, y: u16) -> Result<(), String> {
+    coordinator.fire_shot(DEFAULT_GAME_ID, player_id.to_string(), x, y)
+}
+
+#[test]
+fn test_targets_off_the_grid_are_rejected() {
+    let mut coordinator = game(GenesisConfig::new(10, 1));
+    let err = fire(&mut coordinator, "player3", 10, 3).unwrap_err();
+    assert!(err.contains("outside the 10x10 grid"), "{}", err);
+    assert!(fire(&mut coordinator, "player3", 3, 200).is_err());
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player3"), 4);
+
+    // Area weapons may reach past the edge as long as they aim at a cell on the grid
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player1".to_string(), 9, 9, Weapon::Bomb).unwrap();
+}
+
+#[test]
+fn test_repeat_targets_are_allowed_by_default() {
+    let mut coordinator = game(GenesisConfig::new(10, 1));
+    fire(&mut coordinator, "player1", 5, 5).unwrap();
+    fire(&mut coordinator, "player1", 5, 5).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player3").unwrap();
+    fire(&mut coordinator, "player1", 5, 5).unwrap();
+}
+
+#[test]
+fn test_repeat_targets_can_be_banned() {
+    let mut coordinator = game(no_repeats());
+    fire(&mut coordinator, "player3", 5, 5).unwrap();
+    assert!(fire(&mut coordinator, "player3", 5, 5).unwrap_err().contains("already fired at (5, 5)"));
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert!(fire(&mut coordinator, "player3", 5, 5).is_err());
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player3"), 3);
+
+    // The ban is per player, even within a team, and sonar scans may revisit a cell
+    fire(&mut coordinator, "player1", 5, 5).unwrap();
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player2".to_string(), 5, 5, Weapon::Sonar).unwrap();
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player2".to_string(), 5, 5, Weapon::Sonar).unwrap();
+}
+
+#[test]
+fn test_self_fire_rules() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.shots.self_fire = SelfFire::Forbid;
+    let mut coordinator = game(genesis);
+    let err = fire(&mut coordinator, "player1", 0, 3).unwrap_err();
+    assert!(err.contains("player1's ship at (0, 3)"), "{}", err);
+    assert!(coordinator.fire_weapon(DEFAULT_GAME_ID, "player3".to_string(), 3, 5, Weapon::Bomb).is_err());
+    fire(&mut coordinator, "player1", 1, 3).unwrap();
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player1".to_string(), 0, 3, Weapon::Sonar).unwrap();
+
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.shots.self_fire = SelfFire::ForbidTeam;
+    let mut coordinator = game(genesis);
+    assert!(fire(&mut coordinator, "player1", 1, 3).unwrap_err().contains("player2's ship"));
+    fire(&mut coordinator, "player1", 2, 3).unwrap();
+    fire(&mut coordinator, "player3", 1, 3).unwrap();
+}
+
+#[test]
+fn test_peers_refuse_blocks_with_illegal_targets() {
+    let mut coordinator = game(no_repeats());
+    let mut peer = game(no_repeats());
+
+    let off_grid = relayed_shot(&peer, "player1", (12, 0), 0, 0);
+    let block = mined_block(&peer, "player3", vec![off_grid]);
+    assert!(peer.accept_block(block).unwrap_err().contains("outside"));
+
+    // Two shots at one cell in the same block
+    let first = relayed_shot(&peer, "player1", (4, 4), 0, 0);
+    let repeat = relayed_shot(&peer, "player1", (4, 4), 1, 1);
+    let block = mined_block(&peer, "player3", vec![first, repeat.clone()]);
+    assert!(peer.accept_block(block).unwrap_err().contains("already fired"));
+
+    // A shot at a cell the player's mined shot already covered
+    fire(&mut coordinator, "player1", 4, 4).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player3").unwrap();
+    peer.accept_block(coordinator.blockchain.chain[1].clone()).unwrap();
+    let repeat = relayed_shot(&peer, "player1", (4, 4), 5, 0);
+    let block = mined_block(&peer, "player3", vec![repeat]);
+    assert!(peer.accept_block(block).unwrap_err().contains("already fired"));
+}
+
+#[test]
+fn test_relayed_and_mined_shots_follow_the_same_rules() {
+    let mut genesis = no_repeats();
+    genesis.game.shots.self_fire = SelfFire::Forbid;
+    let mut peer = game(genesis);
+
+    // Shots relayed by other nodes have their targets checked like shots fired here
+    let off_grid = relayed_shot(&peer, "player1", (12, 0), 0, 0);
+    assert!(peer.receive_transaction(off_grid.clone()).unwrap_err().contains("outside"));
+
+    // Blocks are refused for the same reason, and a chain holding one fails validation
+    let block = mined_block(&peer, "player3", vec![off_grid]);
+    assert!(peer.accept_block(block.clone()).unwrap_err().contains("outside"));
+    peer.blockchain.append_block(block, None);
+    assert!(peer.blockchain.is_chain_valid());
+    assert!(!peer.verify_blockchain());
+}
+
+#[test]
+fn test_self_fire_is_only_checked_where_the_shot_is_fired() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.shots.self_fire = SelfFire::Forbid;
+    let mut peer = game(genesis);
+    assert!(fire(&mut peer, "player1", 0, 3).unwrap_err().contains("player1's ship"));
+
+    // Other nodes may not know the shooter's fleet after a ship move, so they accept the
+    // shot rather than disagree over the block holding it
+    let own_ship = relayed_shot(&peer, "player1", (0, 3), 0, 0);
+    let block = mined_block(&peer, "player3", vec![own_ship.clone()]);
+    peer.receive_transaction(own_ship).unwrap();
+    peer.accept_block(block).unwrap();
+    assert!(peer.verify_blockchain());
+}
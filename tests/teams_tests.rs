@@ -0,0 +1,109 @@
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::lifecycle::GamePhase;
+
+mod common;
+use common::{Board, try_register_in_team};
+
+/// player1 and player2 in team red against player3 in team blue
+fn red_vs_blue(genesis: GenesisConfig) -> (GameCoordinator, Vec<Board>) {
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    let boards = vec![
+        try_register_in_team(&mut coordinator, "player1", 0, Some("red")).unwrap(),
+        try_register_in_team(&mut coordinator, "player2", 1, Some("red")).unwrap(),
+        try_register_in_team(&mut coordinator, "player3", 2, Some("blue")).unwrap(),
+    ];
+    (coordinator, boards)
+}
+
+fn owed_by(coordinator: &GameCoordinator, player_id: &str) -> usize {
+    coordinator.open_obligations(DEFAULT_GAME_ID, player_id).unwrap().len()
+}
+
+#[test]
+fn test_teams_listed_with_members() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.teams.max_team_size = 2;
+    let (mut coordinator, _) = red_vs_blue(genesis);
+    try_register_in_team(&mut coordinator, "player4", 3, None).unwrap();
+
+    let teams = coordinator.teams(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(teams.len(), 2);
+    assert_eq!(teams["red"], vec!["player1", "player2"]);
+    assert_eq!(teams["blue"], vec!["player3"]);
+
+    let err = try_register_in_team(&mut coordinator, "player5", 4, Some("red")).unwrap_err();
+    assert!(err.contains("Team red is full"));
+    assert!(try_register_in_team(&mut coordinator, "player5", 4, Some("")).unwrap_err().contains("empty"));
+}
+
+#[test]
+fn test_teammates_spared_without_friendly_fire() {
+    let (mut coordinator, _) = red_vs_blue(GenesisConfig::new(10, 1));
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player3").unwrap();
+
+    assert_eq!(owed_by(&coordinator, "player2"), 0);
+    assert_eq!(owed_by(&coordinator, "player3"), 1);
+}
+
+#[test]
+fn test_friendly_fire_reaches_teammates() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.teams.friendly_fire = true;
+    let (mut coordinator, _) = red_vs_blue(genesis);
+    coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player3").unwrap();
+
+    assert_eq!(owed_by(&coordinator, "player2"), 1);
+    assert_eq!(owed_by(&coordinator, "player3"), 1);
+}
+
+#[test]
+fn test_team_members_share_one_shot_pool() {
+    let (mut coordinator, _) = red_vs_blue(GenesisConfig::new(10, 1));
+
+    // Both red registrations paid into the pool, and any member can spend from it
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 2);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 2);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player3"), 1);
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 3);
+
+    for _ in 0..3 {
+        coordinator.fire_shot(DEFAULT_GAME_ID, "player1".to_string(), 5, 5).unwrap();
+    }
+    assert!(coordinator.fire_shot(DEFAULT_GAME_ID, "player2".to_string(), 5, 5).is_err());
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player3"), 1);
+}
+
+#[test]
+fn test_private_pools_when_shots_not_shared() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.game.teams.shared_shots = false;
+    let (coordinator, _) = red_vs_blue(genesis);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 1);
+}
+
+#[test]
+fn test_last_team_standing_wins() {
+    let (mut coordinator, boards) = red_vs_blue(GenesisConfig::new(10, 1));
+
+    // Losing one member does not end the game while the team still fights
+    coordinator.declare_defeat(DEFAULT_GAME_ID, "player1", boards[0].0.clone(), boards[0].1.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player3").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::Battle);
+
+    coordinator.declare_defeat(DEFAULT_GAME_ID, "player3", boards[2].0.clone(), boards[2].1.clone()).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    assert_eq!(coordinator.phase(DEFAULT_GAME_ID).unwrap(), GamePhase::GameOver);
+
+    let outcome = coordinator.game(DEFAULT_GAME_ID).unwrap().lifecycle.outcome.clone().unwrap();
+    assert_eq!(outcome.winning_team, Some("red".to_string()));
+    assert_eq!(outcome.winner, Some("player2".to_string()));
+    assert_eq!(outcome.standings, vec!["player2", "player3", "player1"]);
+    assert_eq!(coordinator.get_stats(DEFAULT_GAME_ID).unwrap().winning_team, Some("red".to_string()));
+}
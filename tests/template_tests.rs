@@ -0,0 +1,141 @@
+use fleetchain::blockchain::{Blockchain, Transaction};
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::pool::Share;
+use fleetchain::template::{transaction_size, BlockLimits, BlockTemplate};
+use fleetchain::weapons::Weapon;
+
+mod common;
+use common::{mined_block, register};
+
+/// A shot paying `fee`, created at `timestamp`
+fn shot(player_id: &str, fee: u32, timestamp: i64) -> Transaction {
+    let mut transaction = Transaction::new(player_id.to_string(), 1, 1, 0);
+    transaction.fee = fee;
+    transaction.timestamp = timestamp;
+    transaction
+}
+
+fn players(transactions: &[Transaction]) -> Vec<&str> {
+    transactions.iter().map(|tx| tx.player_id.as_str()).collect()
+}
+
+/// Coordinator whose players hold four shots each
+fn fee_game(genesis: GenesisConfig) -> GameCoordinator {
+    let mut genesis = genesis;
+    genesis.rewards.registration_shots = 4;
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    coordinator
+}
+
+#[test]
+fn test_template_orders_by_fee_then_age() {
+    let pending = vec![shot("old", 0, 10), shot("rich", 3, 30), shot("young", 0, 20), shot("tipper", 1, 40)];
+    let (template, leftover) = BlockTemplate::build(pending.clone(), &BlockLimits::default());
+    assert_eq!(players(&template.transactions), ["rich", "tipper", "old", "young"]);
+    assert_eq!(template.fees, 4);
+    assert!(leftover.is_empty());
+
+    let limits = BlockLimits { max_transactions: 2, max_bytes: 0 };
+    let (template, leftover) = BlockTemplate::build(pending, &limits);
+    assert_eq!(players(&template.transactions), ["rich", "tipper"]);
+    assert_eq!(players(&leftover), ["old", "young"]);
+}
+
+#[test]
+fn test_template_skips_transactions_over_the_byte_limit() {
+    let mut bulky = shot("bulky", 5, 10);
+    bulky.spent_utxos = vec!["a".repeat(200)];
+    let small = shot("small", 0, 20);
+    let limits = BlockLimits { max_transactions: 0, max_bytes: transaction_size(&small) + 10 };
+
+    let (template, leftover) = BlockTemplate::build(vec![bulky, small.clone()], &limits);
+    assert_eq!(players(&template.transactions), ["small"]);
+    assert_eq!(template.bytes, transaction_size(&small));
+    assert_eq!(players(&leftover), ["bulky"]);
+    assert!(limits.check(&template.transactions).is_ok());
+}
+
+#[test]
+fn test_mining_keeps_what_does_not_fit_pending() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.consensus.limits.max_transactions = 2;
+    let mut blockchain = Blockchain::from_genesis(&genesis);
+    for (i, fee) in [0, 2, 0, 1].into_iter().enumerate() {
+        blockchain.add_transaction(shot(&format!("player{}", i), fee, i as i64)).unwrap();
+    }
+
+    // The miner earns the block reward plus the fees of the two shots that fit
+    assert_eq!(blockchain.mine_pending_transactions("miner1"), 1 + 3);
+    assert_eq!(players(&blockchain.chain[1].transactions), ["player1", "player3"]);
+    assert_eq!(players(&blockchain.mempool.transactions()), ["player0", "player2"]);
+
+    assert_eq!(blockchain.mine_pending_transactions("miner1"), 1);
+    assert!(blockchain.mempool.is_empty());
+    assert!(blockchain.is_chain_valid());
+}
+
+#[test]
+fn test_fees_are_paid_to_the_miner() {
+    let mut coordinator = fee_game(GenesisConfig::new(10, 1));
+    coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player1".to_string(), 5, 5, Weapon::Single, 2).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+    assert!(coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player1".to_string(), 6, 6, Weapon::Single, 1).is_err());
+    assert!(coordinator.transfer_shots_with_fee(DEFAULT_GAME_ID, "player1", "player2", 1, 1).is_err());
+
+    coordinator.transfer_shots_with_fee(DEFAULT_GAME_ID, "player2", "player1", 1, 2).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 1);
+    assert_eq!(coordinator.blockchain.block_template().fees, 4);
+
+    // The transfer's fee is spent, only the transferred shot is escrowed
+    let transfer = &coordinator.blockchain.mempool.transactions()[1];
+    assert_eq!(transfer.spent_utxos.len(), 3);
+    assert_eq!(coordinator.blockchain.escrowed_shots(&transfer.hash()), 1);
+
+    assert_eq!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap(), 1 + 4);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 2);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 6);
+}
+
+#[test]
+fn test_peers_check_pool_fees_and_block_limits() {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.consensus.limits.max_transactions = 1;
+    let mut coordinator = fee_game(genesis.clone());
+    coordinator.fire_weapon_with_fee(DEFAULT_GAME_ID, "player1".to_string(), 5, 5, Weapon::Single, 3).unwrap();
+
+    let worker = coordinator.shot_account(DEFAULT_GAME_ID, "player2");
+    let template = coordinator.blockchain.get_latest_block().hash.clone();
+    let share = Share::find(&worker, &template, coordinator.blockchain.share_difficulty, 0);
+    coordinator.submit_share(DEFAULT_GAME_ID, "player2", share.nonce).unwrap();
+    assert_eq!(coordinator.mine_for_pool(DEFAULT_GAME_ID).unwrap(), vec![(worker, 1 + 3)]);
+    assert!(coordinator.verify_blockchain());
+
+    let mut peer = fee_game(genesis);
+    peer.accept_block(coordinator.blockchain.chain[1].clone()).unwrap();
+
+    let overfull = vec![shot("player1", 0, 1), shot("player2", 0, 2)];
+    let block = mined_block(&peer, "player2", overfull);
+    assert!(peer.accept_block(block).unwrap_err().contains("more than the limit of 1"));
+}
+
+#[test]
+fn test_fees_must_be_covered_by_the_senders_shots() {
+    let mut coordinator = fee_game(GenesisConfig::new(10, 1));
+    let mut commit = Transaction::beacon_commit(DEFAULT_GAME_ID, "player1", "draw", "commitment");
+    commit.fee = 500;
+    assert!(coordinator.receive_transaction(commit.clone()).unwrap_err().contains("needs 500"));
+
+    // A miner including it anyway cannot collect the fee
+    let block = mined_block(&coordinator, "player2", vec![commit.clone()]);
+    assert!(coordinator.accept_block(block).unwrap_err().contains("needs 500"));
+
+    let account = coordinator.shot_account(DEFAULT_GAME_ID, "player1");
+    commit.fee = 2;
+    commit.spent_utxos = coordinator.blockchain.select_shots(&account, 2).unwrap();
+    coordinator.receive_transaction(commit).unwrap();
+    assert_eq!(coordinator.blockchain.block_template().fees, 2);
+}
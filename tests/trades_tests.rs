@@ -0,0 +1,153 @@
+use fleetchain::blockchain::Transaction;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::trades::SwapStatus;
+
+mod common;
+use common::{mined_block, register_in_team};
+
+fn trade_genesis() -> GenesisConfig {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.registration_shots = 3;
+    genesis.game.trades.swap_blocks = 2;
+    genesis
+}
+
+/// Coordinator with player1 and player2 holding three shots each
+fn trade_game() -> GameCoordinator {
+    let mut coordinator = GameCoordinator::from_genesis(trade_genesis());
+    register_in_team(&mut coordinator, "player1", 0, None);
+    register_in_team(&mut coordinator, "player2", 1, None);
+    coordinator
+}
+
+fn shots(coordinator: &GameCoordinator, player_id: &str) -> usize {
+    coordinator.get_unspent_shots(DEFAULT_GAME_ID, player_id)
+}
+
+#[test]
+fn test_transfer_moves_shots_once_mined() {
+    let mut coordinator = trade_game();
+    coordinator.transfer_shots(DEFAULT_GAME_ID, "player1", "player2", 2).unwrap();
+
+    // The shots are locked at once and listed as spent by the transfer
+    assert_eq!(shots(&coordinator, "player1"), 1);
+    assert_eq!(shots(&coordinator, "player2"), 3);
+    assert_eq!(coordinator.blockchain.mempool.transactions()[0].spent_utxos.len(), 2);
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(shots(&coordinator, "player1"), 2);
+    assert_eq!(shots(&coordinator, "player2"), 5);
+    assert!(coordinator.verify_blockchain());
+}
+
+#[test]
+fn test_transfer_validation() {
+    let mut coordinator = trade_game();
+    register_in_team(&mut coordinator, "player3", 2, Some("red"));
+    register_in_team(&mut coordinator, "player4", 3, Some("red"));
+
+    let mut transfer = |from: &str, to: &str, amount: u32| {
+        coordinator.transfer_shots(DEFAULT_GAME_ID, from, to, amount).unwrap_err()
+    };
+    assert!(transfer("player1", "player1", 1).contains("themselves"));
+    assert!(transfer("player1", "ghost", 1).contains("not found"));
+    assert!(transfer("player1", "player2", 0).contains("positive"));
+    assert!(transfer("player1", "player2", 4).contains("Need 4"));
+    assert!(transfer("player3", "player4", 1).contains("share a shot pool"));
+
+    let mut genesis = trade_genesis();
+    genesis.game.trades.enabled = false;
+    let mut closed = GameCoordinator::from_genesis(genesis);
+    register_in_team(&mut closed, "player1", 0, None);
+    register_in_team(&mut closed, "player2", 1, None);
+    assert!(closed.transfer_shots(DEFAULT_GAME_ID, "player1", "player2", 1).unwrap_err().contains("disabled"));
+}
+
+#[test]
+fn test_swap_trades_both_sides_in_one_block() {
+    let mut coordinator = trade_game();
+    let offer_id = coordinator.offer_swap(DEFAULT_GAME_ID, "player1", "player2", 2, 1).unwrap();
+    assert_eq!(shots(&coordinator, "player1"), 1);
+
+    // The offer must be mined before it can be accepted
+    assert!(coordinator.accept_swap(DEFAULT_GAME_ID, "player2", &offer_id).unwrap_err().contains("not found"));
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(coordinator.swaps(DEFAULT_GAME_ID).unwrap()[0].status, SwapStatus::Open);
+
+    assert!(coordinator.accept_swap(DEFAULT_GAME_ID, "player1", &offer_id).unwrap_err().contains("not made to"));
+    coordinator.accept_swap(DEFAULT_GAME_ID, "player2", &offer_id).unwrap();
+    assert!(coordinator.accept_swap(DEFAULT_GAME_ID, "player2", &offer_id).unwrap_err().contains("already pending"));
+    assert_eq!(shots(&coordinator, "player2"), 2);
+
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(shots(&coordinator, "player1"), 1 + 1 + 1 + 1);
+    assert_eq!(shots(&coordinator, "player2"), 2 + 2);
+    let offer = &coordinator.swaps(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!(offer.status, SwapStatus::Completed);
+    assert_eq!(offer.resolved_in_block, Some(2));
+}
+
+#[test]
+fn test_unaccepted_swap_returns_to_maker() {
+    let mut coordinator = trade_game();
+    let offer_id = coordinator.offer_swap(DEFAULT_GAME_ID, "player1", "player2", 3, 1).unwrap();
+    for _ in 0..4 {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    }
+
+    let offer = &coordinator.swaps(DEFAULT_GAME_ID).unwrap()[0];
+    assert_eq!(offer.status, SwapStatus::Expired);
+    assert_eq!(offer.resolved_in_block, Some(4));
+    assert_eq!(shots(&coordinator, "player1"), 3);
+    assert!(coordinator.accept_swap(DEFAULT_GAME_ID, "player2", &offer_id).unwrap_err().contains("no longer open"));
+}
+
+#[test]
+fn test_peers_apply_transfers_and_swaps() {
+    let mut coordinator = trade_game();
+    let mut peer = GameCoordinator::from_genesis(trade_genesis());
+    register_in_team(&mut peer, "player1", 0, None);
+    register_in_team(&mut peer, "player2", 1, None);
+
+    coordinator.transfer_shots(DEFAULT_GAME_ID, "player2", "player1", 1).unwrap();
+    let offer_id = coordinator.offer_swap(DEFAULT_GAME_ID, "player1", "player2", 1, 2).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    coordinator.accept_swap(DEFAULT_GAME_ID, "player2", &offer_id).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    for block in coordinator.blockchain.chain[1..].iter().cloned() {
+        peer.accept_block(block).unwrap();
+    }
+    assert_eq!(peer.swaps(DEFAULT_GAME_ID).unwrap()[0].status, SwapStatus::Completed);
+    // The peer mints the same mining rewards, so both nodes agree on every balance
+    assert_eq!(shots(&peer, "player1"), shots(&coordinator, "player1"));
+    assert_eq!(shots(&peer, "player2"), shots(&coordinator, "player2"));
+    assert_eq!(peer.blockchain.shot_utxos.len(), coordinator.blockchain.shot_utxos.len());
+}
+
+#[test]
+fn test_transfers_spend_only_the_senders_own_shots() {
+    let mut coordinator = trade_game();
+    register_in_team(&mut coordinator, "player3", 2, None);
+    let own = |coordinator: &GameCoordinator, player_id: &str, count: u32| {
+        let account = coordinator.shot_account(DEFAULT_GAME_ID, player_id);
+        coordinator.blockchain.select_shots(&account, count).unwrap()
+    };
+
+    // player3 relays a transfer naming player2's shot
+    let mut stolen = Transaction::transfer(DEFAULT_GAME_ID, "player3", "player1", 1);
+    stolen.spent_utxos = own(&coordinator, "player2", 1);
+    assert!(coordinator.receive_transaction(stolen.clone()).unwrap_err().contains("not default/player3"));
+    let block = mined_block(&coordinator, "player1", vec![stolen]);
+    assert!(coordinator.accept_block(block).is_err());
+
+    // A transfer handing over more shots than it spends
+    let mut inflated = Transaction::transfer(DEFAULT_GAME_ID, "player3", "player1", 3);
+    inflated.spent_utxos = own(&coordinator, "player3", 1);
+    assert!(coordinator.receive_transaction(inflated).unwrap_err().contains("needs 3"));
+
+    assert!(coordinator.blockchain.mempool.is_empty());
+    assert_eq!(shots(&coordinator, "player2"), 3);
+}
@@ -0,0 +1,129 @@
+use fleetchain::beacon::Beacon;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::game::DEFAULT_GAME_ID;
+use fleetchain::genesis::GenesisConfig;
+use fleetchain::turns::{TurnMode, TurnOrder, TurnRules};
+
+mod common;
+use common::register;
+
+/// Turn-based game of three players who start without shots
+fn turn_based(order: TurnOrder) -> GameCoordinator {
+    let mut genesis = GenesisConfig::new(10, 1);
+    genesis.rewards.registration_shots = 0;
+    genesis.game.turns = TurnRules { mode: TurnMode::TurnBased, order };
+    let mut coordinator = GameCoordinator::from_genesis(genesis);
+    register(&mut coordinator, "player1", 0);
+    register(&mut coordinator, "player2", 1);
+    register(&mut coordinator, "player3", 2);
+    coordinator
+}
+
+fn fire(coordinator: &mut GameCoordinator, player_id: &str, x: u16, y: u16) -> Result<(), String> {
+    coordinator.fire_shot(DEFAULT_GAME_ID, player_id.to_string(), x, y)
+}
+
+fn current_turn(coordinator: &GameCoordinator) -> Option<String> {
+    coordinator.game(DEFAULT_GAME_ID).unwrap().current_turn().map(str::to_string)
+}
+
+#[test]
+fn test_turn_based_games_do_not_mine() {
+    let mut coordinator = turn_based(TurnOrder::Fixed);
+    assert!(coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap_err().contains("turn-based"));
+    assert!(coordinator.submit_share(DEFAULT_GAME_ID, "player1", 0).unwrap_err().contains("turn-based"));
+    assert!(fire(&mut coordinator, "player1", 5, 5).unwrap_err().contains("No turns are left in round 0"));
+}
+
+#[test]
+fn test_rounds_grant_one_shot_per_player() {
+    let mut coordinator = turn_based(TurnOrder::Fixed);
+    coordinator.next_round(DEFAULT_GAME_ID).unwrap();
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(game.round, 1);
+    assert_eq!(game.turn_order, ["player1", "player2", "player3"]);
+    for player_id in ["player1", "player2", "player3"] {
+        assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, player_id), 1);
+    }
+
+    // The round goes on until everyone has fired or passed
+    let err = coordinator.next_round(DEFAULT_GAME_ID).unwrap_err();
+    assert!(err.contains("Round 1 is still on: it is player1's turn"));
+
+    // Unused shots carry over to the next round
+    for player_id in ["player1", "player2", "player3"] {
+        coordinator.pass_turn(DEFAULT_GAME_ID, player_id).unwrap();
+    }
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().round, 2);
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 2);
+}
+
+#[test]
+fn test_out_of_turn_shots_are_rejected() {
+    let mut coordinator = turn_based(TurnOrder::Fixed);
+    coordinator.next_round(DEFAULT_GAME_ID).unwrap();
+
+    assert!(fire(&mut coordinator, "player2", 5, 5).unwrap_err().contains("It is player1's turn"));
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player2"), 1);
+    fire(&mut coordinator, "player1", 5, 5).unwrap();
+    assert!(fire(&mut coordinator, "player1", 6, 6).is_err());
+    assert_eq!(current_turn(&coordinator).as_deref(), Some("player2"));
+
+    coordinator.pass_turn(DEFAULT_GAME_ID, "player2").unwrap();
+    assert!(coordinator.pass_turn(DEFAULT_GAME_ID, "player1").is_err());
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().round, 1);
+
+    // The last turn of the round seals the round's shots into a block and starts the next one
+    let height = coordinator.current_height();
+    fire(&mut coordinator, "player3", 7, 7).unwrap();
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().round, 2);
+    assert_eq!(coordinator.current_height(), height + 1);
+    assert_eq!(coordinator.blockchain.get_latest_block().transactions.len(), 2);
+    assert!(coordinator.blockchain.mempool.is_empty());
+    // player3 owes a report on player1's shot and, as self-fire is allowed, on its own
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player3").unwrap().len(), 2);
+    assert_eq!(current_turn(&coordinator).as_deref(), Some("player1"));
+}
+
+#[test]
+fn test_random_turn_order_follows_the_seed() {
+    let rules = TurnRules { mode: TurnMode::TurnBased, order: TurnOrder::Random };
+    let players: Vec<String> = (0..6).map(|i| format!("player{}", i)).collect();
+
+    let beacon = |seed: &str| Beacon::from_block_hash(1, "default/turns", seed);
+    let order = rules.turn_order(&players, &beacon("seed"));
+    assert_eq!(order, rules.turn_order(&players, &beacon("seed")));
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(sorted, players);
+
+    let shuffles: Vec<Vec<String>> = (0..8)
+        .map(|i| rules.turn_order(&players, &beacon(&format!("seed{}", i))))
+        .collect();
+    assert!(shuffles.iter().any(|o| *o != order));
+
+    let fixed = TurnRules { mode: TurnMode::TurnBased, order: TurnOrder::Fixed };
+    assert_eq!(fixed.turn_order(&players, &beacon("seed")), players);
+}
+
+#[test]
+fn test_random_rounds_are_shuffled_by_the_chain_beacon() {
+    let mut coordinator = turn_based(TurnOrder::Random);
+    coordinator.next_round(DEFAULT_GAME_ID).unwrap();
+
+    let mut expected = vec!["player1".to_string(), "player2".to_string(), "player3".to_string()];
+    let height = coordinator.current_height();
+    coordinator.blockchain.beacon(height, "default/turns").unwrap().shuffle(&mut expected);
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().turn_order, expected);
+}
+
+#[test]
+fn test_next_round_without_turns_only_counts_rounds() {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    let height = coordinator.current_height();
+    coordinator.next_round(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(coordinator.game(DEFAULT_GAME_ID).unwrap().round, 1);
+    assert_eq!(coordinator.current_height(), height);
+    assert!(coordinator.pass_turn(DEFAULT_GAME_ID, "player1").is_err());
+}
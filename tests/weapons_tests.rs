@@ -0,0 +1,186 @@
+use fleetchain::blockchain::Transaction;
+use fleetchain::coordinator::GameCoordinator;
+use fleetchain::crypto::{CountProof, HitProof};
+use fleetchain::game::{HitReport, DEFAULT_GAME_ID};
+use fleetchain::map::GameMap;
+use fleetchain::weapons::{sonar_count, Direction, Region, Weapon, WeaponRules};
+
+mod common;
+use common::{Board, register};
+
+/// Two players, with player1 holding enough shots for any weapon
+fn armed_game() -> (GameCoordinator, Board) {
+    let mut coordinator = GameCoordinator::new(10, 1);
+    register(&mut coordinator, "player1", 0);
+    let board2 = register(&mut coordinator, "player2", 1);
+    for _ in 0..4 {
+        coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+    }
+    (coordinator, board2)
+}
+
+/// Fire a weapon as player1, mine it and return the shot ID
+fn fire_and_mine(coordinator: &mut GameCoordinator, target: (u16, u16), weapon: Weapon) -> String {
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player1".to_string(), target.0, target.1, weapon).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player2").unwrap();
+    coordinator.blockchain.get_latest_block().transactions[0].hash()
+}
+
+fn hit(player_id: &str, pos: (u16, u16)) -> HitReport {
+    let proof = HitProof::prove_hit(pos, &[], "");
+    HitReport::new(player_id.to_string(), pos.0, pos.1, true, proof.serialize())
+}
+
+#[test]
+fn test_weapon_cells_clipped_to_grid() {
+    let rules = WeaponRules::default();
+    let mut map = GameMap::new(10, 10);
+    assert_eq!(Weapon::Single.cells((3, 3), &map, &rules), vec![(3, 3)]);
+    assert_eq!(Weapon::Bomb.cells((5, 5), &map, &rules).len(), 9);
+    assert_eq!(Weapon::Bomb.cells((0, 0), &map, &rules), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    assert_eq!(Weapon::Sonar.cells((9, 5), &map, &rules).len(), 6);
+
+    let vertical = Weapon::Torpedo { direction: Direction::Vertical };
+    assert_eq!(vertical.cells((2, 1), &map, &rules), vec![(2, 1), (2, 2), (2, 3), (2, 4)]);
+    let horizontal = Weapon::Torpedo { direction: Direction::Horizontal };
+    assert_eq!(horizontal.cells((8, 0), &map, &rules), vec![(8, 0), (9, 0)]);
+
+    // Land cells are never affected
+    map.blocked = vec![(1, 1), (2, 3)];
+    assert_eq!(Weapon::Bomb.cells((0, 0), &map, &rules), vec![(0, 0), (1, 0), (0, 1)]);
+    assert_eq!(vertical.cells((2, 1), &map, &rules), vec![(2, 1), (2, 2), (2, 4)]);
+}
+
+#[test]
+fn test_weapon_costs() {
+    let rules = WeaponRules { bomb_cost: 5, torpedo_cost: 2, torpedo_length: 3, sonar_cost: 1 };
+    assert_eq!(Weapon::Single.cost(&rules), 1);
+    assert_eq!(Weapon::Bomb.cost(&rules), 5);
+    assert_eq!(Weapon::Torpedo { direction: Direction::Vertical }.cost(&rules), 2);
+    assert_eq!(Weapon::Sonar.cost(&rules), 1);
+}
+
+#[test]
+fn test_sonar_count() {
+    let board = vec![(1, 0), (1, 1), (1, 2), (1, 3), (5, 5)];
+    assert_eq!(sonar_count(&board, (1, 1)), 3);
+    assert_eq!(sonar_count(&board, (0, 0)), 2);
+    assert_eq!(sonar_count(&board, (8, 8)), 0);
+}
+
+#[test]
+fn test_region_around_clipped_to_grid() {
+    assert_eq!(Region::around((5, 5), &GameMap::square(10)), Region { x: 4, y: 4, width: 3, height: 3 });
+    assert_eq!(Region::around((0, 9), &GameMap::square(10)), Region { x: 0, y: 8, width: 2, height: 2 });
+
+    let region = Region::around((0, 0), &GameMap::square(10));
+    assert!(region.contains((1, 1)));
+    assert!(!region.contains((2, 0)));
+}
+
+#[test]
+fn test_single_shots_serialize_without_weapon() {
+    let shot = Transaction::new("player1".to_string(), 1, 2, 0);
+    assert!(!serde_json::to_string(&shot).unwrap().contains("weapon"));
+
+    let mut bomb = shot.clone();
+    bomb.weapon = Weapon::Bomb;
+    let json = serde_json::to_string(&bomb).unwrap();
+    assert!(json.contains("Bomb"));
+    assert_ne!(bomb.hash(), shot.hash());
+
+    let parsed: Transaction = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.weapon, Weapon::Bomb);
+}
+
+#[test]
+fn test_weapon_spends_its_cost() {
+    let (mut coordinator, _) = armed_game();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 5);
+
+    coordinator.fire_weapon(DEFAULT_GAME_ID, "player1".to_string(), 1, 1, Weapon::Bomb).unwrap();
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+
+    // Nothing is spent when the player cannot cover the whole cost
+    let err = coordinator.fire_weapon(DEFAULT_GAME_ID, "player1".to_string(), 1, 1, Weapon::Sonar).unwrap_err();
+    assert!(err.contains("Need 2"));
+    assert_eq!(coordinator.get_unspent_shots(DEFAULT_GAME_ID, "player1"), 1);
+}
+
+#[test]
+fn test_bomb_creates_obligation_per_cell() {
+    let (mut coordinator, _) = armed_game();
+    let shot_id = fire_and_mine(&mut coordinator, (1, 1), Weapon::Bomb);
+
+    let owed = coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap();
+    assert_eq!(owed.len(), 9);
+    assert!(owed.iter().all(|o| o.shot_id == shot_id));
+
+    // Reports for different cells of the same shot can be pending together
+    coordinator.report_hit(DEFAULT_GAME_ID, hit("player2", (1, 0))).unwrap();
+    coordinator.report_hit(DEFAULT_GAME_ID, hit("player2", (1, 1))).unwrap();
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, hit("player2", (1, 1))).unwrap_err();
+    assert!(err.contains("already pending"));
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert_eq!(game.players["player2"].hit_cells, vec![(1, 0), (1, 1)]);
+
+    let results = coordinator.shot_results(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(results.len(), 9);
+    assert_eq!(results.iter().filter(|r| r.is_hit()).count(), 2);
+}
+
+#[test]
+fn test_torpedo_covers_a_line() {
+    let (mut coordinator, _) = armed_game();
+    fire_and_mine(&mut coordinator, (1, 2), Weapon::Torpedo { direction: Direction::Vertical });
+
+    let cells: Vec<(u16, u16)> = coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap()
+        .iter()
+        .map(|o| (o.shot_x, o.shot_y))
+        .collect();
+    assert_eq!(cells, vec![(1, 2), (1, 3), (1, 4), (1, 5)]);
+}
+
+#[test]
+fn test_sonar_answered_with_a_count() {
+    let (mut coordinator, board2) = armed_game();
+    let shot_id = fire_and_mine(&mut coordinator, (1, 1), Weapon::Sonar);
+
+    let owed = coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap();
+    assert_eq!(owed.len(), 1);
+    assert!(owed[0].sonar);
+
+    // A hit/miss report does not answer a sonar
+    let err = coordinator.report_hit(DEFAULT_GAME_ID, hit("player2", (1, 1))).unwrap_err();
+    assert!(err.contains("owes no report"));
+
+    let proof = CountProof::prove(Region::around((1, 1), &GameMap::square(10)), &board2.0, &board2.1);
+    coordinator.report_scan(DEFAULT_GAME_ID, "player2", (1, 1), proof).unwrap();
+    coordinator.mine_for_shots(DEFAULT_GAME_ID, "player1").unwrap();
+
+    let game = coordinator.game(DEFAULT_GAME_ID).unwrap();
+    assert!(game.players["player2"].hit_cells.is_empty());
+    let results = coordinator.shot_results(DEFAULT_GAME_ID, "player1").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].shot_id, shot_id);
+    assert_eq!(results[0].counts, vec![("player2".to_string(), 3)]);
+    assert!(!results[0].is_hit());
+}
+
+#[test]
+fn test_sonar_rejects_a_false_count() {
+    let (mut coordinator, board2) = armed_game();
+    fire_and_mine(&mut coordinator, (1, 1), Weapon::Sonar);
+
+    // A proof for the true count does not back a different one
+    let proof = CountProof::prove(Region::around((1, 1), &GameMap::square(10)), &board2.0, &board2.1);
+    let report = HitReport::sonar("player2".to_string(), 1, 1, 0, proof.serialize());
+    assert_eq!(coordinator.report_hit(DEFAULT_GAME_ID, report).unwrap_err(), "Invalid proof");
+
+    // Nor does a proof for another area
+    let elsewhere = CountProof::prove(Region::around((5, 5), &GameMap::square(10)), &board2.0, &board2.1);
+    assert!(coordinator.report_scan(DEFAULT_GAME_ID, "player2", (1, 1), elsewhere).is_err());
+    assert_eq!(coordinator.open_obligations(DEFAULT_GAME_ID, "player2").unwrap().len(), 1);
+}